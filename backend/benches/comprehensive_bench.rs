@@ -13,7 +13,7 @@ use spiking_neural_networks::neuron::plasticity::{
     STDP, BCM, RewardModulatedSTDP, TripletSTDP
 };
 use spiking_neural_networks::classifiers::{
-    STDPClassifier, RSTDPClassifier, LSMClassifier
+    STDPClassifier, RSTDPClassifier, LSMClassifier, RSTDPParams
 };
 use spiking_neural_networks::digital_twin::DigitalTwin;
 
@@ -138,7 +138,7 @@ fn bench_classifiers(c: &mut Criterion) {
 
     // R-STDP Classifier
     group.bench_function("rstdp_classifier", |b| {
-        let classifier = RSTDPClassifier::new(10, 5);
+        let classifier = RSTDPClassifier::new(10, 5, RSTDPParams::default());
         let input_pattern = vec![1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0];
         b.iter(|| {
             black_box(classifier.predict(&input_pattern));
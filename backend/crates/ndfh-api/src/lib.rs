@@ -4,19 +4,26 @@ pub mod observability;
 
 use ndfh_core::{HyperedgeCatalog, HypergraphNetwork, MembershipLog};
 use ndfh_hgts::AsOfEngine;
+use std::collections::BTreeMap;
 use tracing::{info, instrument};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HeCreate {
     pub head_v: u64,
     pub fe_spec_json: String,
     pub state_schema_json: Option<String>,
+    /// Client-chosen key making retries safe: a repeated key with the same payload
+    /// returns the h_id created the first time instead of allocating a new hyperedge,
+    /// a repeated key with a different payload is rejected
+    pub idempotency_key: Option<String>,
 }
 
 /// Transactional API for topology/state mutations and logs
 pub trait TxnApi {
     fn he_create(&mut self, req: HeCreate) -> anyhow::Result<u64>;
-    fn he_retire(&mut self, h_id: u64) -> anyhow::Result<()>;
+    /// Retire a hyperedge at `t_end`, closing all of its open memberships; later
+    /// `mem_add` calls on it are rejected
+    fn he_retire(&mut self, h_id: u64, t_end: i64) -> anyhow::Result<()>;
     fn mem_add(&mut self, h_id: u64, tail_v: u64, t_start: i64) -> anyhow::Result<()>;
     fn mem_rem(&mut self, h_id: u64, tail_v: u64, t_end: i64) -> anyhow::Result<()>;
     fn state_upd(&mut self, h_id: u64, op: &str, payload_json: &str) -> anyhow::Result<()>;
@@ -28,6 +35,10 @@ pub struct InMemoryTxn {
     pub membership: MembershipLog,
     pub catalog: HyperedgeCatalog,
     next_h_id: u64,
+    /// h_id allocated for each idempotency key seen by `he_create`, with the request that created it
+    idempotency_keys: BTreeMap<String, (u64, HeCreate)>,
+    /// Retire time of each retired h_id
+    retired: BTreeMap<u64, i64>,
 }
 
 impl Default for InMemoryTxn {
@@ -36,6 +47,8 @@ impl Default for InMemoryTxn {
             membership: MembershipLog::new(),
             catalog: HyperedgeCatalog::new(),
             next_h_id: 1,
+            idempotency_keys: BTreeMap::new(),
+            retired: BTreeMap::new(),
         }
     }
 }
@@ -52,22 +65,57 @@ impl InMemoryTxn {
 impl TxnApi for InMemoryTxn {
     fn he_create(&mut self, req: HeCreate) -> anyhow::Result<u64> {
         info!("creating hyperedge");
+        if let Some((h_id, original)) = req
+            .idempotency_key
+            .as_ref()
+            .and_then(|key| self.idempotency_keys.get(key))
+        {
+            if *original != req {
+                anyhow::bail!(
+                    "idempotency key {:?} was already used for a different he_create request",
+                    original.idempotency_key.as_deref().unwrap_or_default()
+                );
+            }
+            let h_id = *h_id;
+            info!(
+                h_id,
+                "idempotency key already used; returning existing hyperedge"
+            );
+            return Ok(h_id);
+        }
         let h_id = self.next_h_id;
         self.next_h_id += 1;
 
         // Register head mapping in catalog; fe_spec/state_schema would be persisted in a full impl
         self.catalog.register_head(h_id, req.head_v);
+        if let Some(key) = req.idempotency_key.clone() {
+            self.idempotency_keys.insert(key, (h_id, req));
+        }
         Ok(h_id)
     }
 
-    fn he_retire(&mut self, _h_id: u64) -> anyhow::Result<()> {
-        info!(h_id = _h_id, "retiring hyperedge");
-        // For now: no-op (retire would affect a hyperedges table in storage)
+    fn he_retire(&mut self, h_id: u64, t_end: i64) -> anyhow::Result<()> {
+        info!(h_id, t_end, "retiring hyperedge");
+        if self.catalog.head_of(h_id).is_none() {
+            anyhow::bail!("unknown hyperedge {}", h_id);
+        }
+        // The head mapping stays in the catalog so AS OF snapshots before t_end
+        // still materialize the edge; with no open memberships left, and new ones
+        // rejected by mem_add, it vanishes after
+        let closed = self
+            .membership
+            .close_all(h_id, t_end)
+            .map_err(anyhow::Error::msg)?;
+        info!(h_id, closed, "closed open memberships");
+        self.retired.insert(h_id, t_end);
         Ok(())
     }
 
     fn mem_add(&mut self, h_id: u64, tail_v: u64, t_start: i64) -> anyhow::Result<()> {
         info!(h_id, tail_v, t_start, "adding membership");
+        if let Some(t_end) = self.retired.get(&h_id) {
+            anyhow::bail!("hyperedge {} was retired at {}", h_id, t_end);
+        }
         self.membership.add(h_id, tail_v, t_start);
         Ok(())
     }
@@ -105,6 +153,7 @@ mod tests {
                 head_v: 99,
                 fe_spec_json: "{}".to_string(),
                 state_schema_json: None,
+                idempotency_key: None,
             })
             .expect("he_create");
 
@@ -135,4 +184,74 @@ mod tests {
             edge.sources.len()
         );
     }
+
+    #[test]
+    fn he_retire_closes_open_memberships() {
+        let mut txn = InMemoryTxn::default();
+        let h_id = txn
+            .he_create(HeCreate {
+                head_v: 99,
+                fe_spec_json: "{}".to_string(),
+                state_schema_json: None,
+                idempotency_key: None,
+            })
+            .expect("he_create");
+        txn.mem_add(h_id, 10, 100).expect("mem_add 10");
+        txn.mem_add(h_id, 11, 120).expect("mem_add 11");
+
+        txn.he_retire(h_id, 200).expect("he_retire");
+        let id = ndfh_core::HyperedgeId::from(h_id as u32);
+        let before = txn.snapshot_as_of(150);
+        assert_eq!(
+            before
+                .get_hyperedge(id)
+                .expect("edge before retire")
+                .sources
+                .len(),
+            2
+        );
+        assert!(txn.snapshot_as_of(250).get_hyperedge(id).is_none());
+
+        // A retired hyperedge takes no new tails, so it stays gone
+        let err = txn.mem_add(h_id, 12, 300).unwrap_err().to_string();
+        assert!(err.contains("retired at 200"), "{}", err);
+        assert!(txn.snapshot_as_of(350).get_hyperedge(id).is_none());
+
+        assert!(txn.he_retire(h_id + 1, 200).is_err(), "unknown hyperedge");
+    }
+
+    #[test]
+    fn he_create_with_repeated_idempotency_key_returns_same_h_id() {
+        let mut txn = InMemoryTxn::default();
+        let req = |key: Option<&str>| HeCreate {
+            head_v: 99,
+            fe_spec_json: "{}".to_string(),
+            state_schema_json: None,
+            idempotency_key: key.map(str::to_string),
+        };
+
+        let first = txn.he_create(req(Some("create-99"))).expect("he_create");
+        let retry = txn.he_create(req(Some("create-99"))).expect("retry");
+        assert_eq!(first, retry);
+
+        // Other keys and keyless requests still allocate fresh ids
+        let other = txn.he_create(req(Some("create-99-b"))).expect("he_create");
+        let keyless = txn.he_create(req(None)).expect("he_create");
+        assert_ne!(other, first);
+        assert_ne!(keyless, other);
+
+        // Reusing a key for a different payload is a client bug, not a retry
+        let conflicting = HeCreate {
+            head_v: 100,
+            ..req(Some("create-99"))
+        };
+        let err = txn.he_create(conflicting).unwrap_err().to_string();
+        assert!(err.contains("create-99"), "{}", err);
+        let conflicting = HeCreate {
+            fe_spec_json: "{\"k\": 1}".to_string(),
+            ..req(Some("create-99"))
+        };
+        assert!(txn.he_create(conflicting).is_err());
+        assert_eq!(txn.he_create(req(Some("create-99"))).expect("retry"), first);
+    }
 }
@@ -47,11 +47,14 @@ pub fn record_policy_decision(decision: &str, reason: Option<&str>) {
 }
 
 /// Record snapshot construction metrics.
-pub fn record_snapshot_metrics(as_of: i64, hyperedges_count: usize, mode: &str) {
+/// `latency_ms` covers materialization only (not shard loading or encoding) and is
+/// emitted as a histogram field (`histogram.*` is picked up by OTEL metrics layers).
+pub fn record_snapshot_metrics(as_of: i64, hyperedges_count: usize, mode: &str, latency_ms: f64) {
     info!(target: "ndfh.snapshot",
         as_of,
         hyperedges_count,
         mode,
+        histogram.snapshot_latency_ms = latency_ms,
         "snapshot built");
 }
 
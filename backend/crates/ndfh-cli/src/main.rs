@@ -1,11 +1,12 @@
 use anyhow::{bail, Context, Result};
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use ndfh_api::{HeCreate, InMemoryTxn, TxnApi};
+use ndfh_core::ArityPolicy;
 use ndfh_hdx::io as hdx_io;
-use ndfh_hdx::DatasetManifest;
+use ndfh_hdx::{DatasetManifest, PII_CLASSES};
 use ndfh_hgts::AsOfEngine;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
@@ -15,6 +16,36 @@ use std::time::Instant;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Unit of every time argument (--as-of, --t-ns, --t-start, --dt); converted to nanoseconds internally
+    #[arg(long, global = true, value_enum, default_value_t = TimeUnit::Ns)]
+    time_unit: TimeUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum TimeUnit {
+    Ns,
+    Us,
+    Ms,
+    S,
+}
+
+impl TimeUnit {
+    /// Nanoseconds in one unit
+    fn nanos(self) -> i64 {
+        match self {
+            TimeUnit::Ns => 1,
+            TimeUnit::Us => 1_000,
+            TimeUnit::Ms => 1_000_000,
+            TimeUnit::S => 1_000_000_000,
+        }
+    }
+
+    /// Convert `value` given in this unit to nanoseconds, failing on overflow
+    fn to_ns(self, value: i64) -> Result<i64> {
+        value
+            .checked_mul(self.nanos())
+            .with_context(|| format!("time {} {:?} overflows i64 nanoseconds", value, self))
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -25,51 +56,212 @@ enum Commands {
         input: String,
         #[arg(short, long)]
         output: String,
+        /// Fail when a shard has too many malformed JSONL lines instead of skipping them
+        #[arg(long, default_value_t = false)]
+        strict: bool,
+        /// Maximum tolerated fraction of malformed lines per shard with --strict
+        #[arg(long, default_value_t = 0.0)]
+        max_invalid_fraction: f64,
     },
 
     /// Validate a dataset.yaml manifest (basic checks)
     Verify {
-        #[arg(short, long)]
-        manifest: String,
+        #[arg(short, long, required_unless_present = "manifest_glob")]
+        manifest: Option<String>,
+        /// Verify every manifest matching this glob (basic checks and checksums) and
+        /// print a pass/fail summary; fails if any manifest fails
+        #[arg(long, conflicts_with = "manifest")]
+        manifest_glob: Option<String>,
+        /// Worker threads for --manifest-glob (0 = all cores, 1 = sequential)
+        #[arg(long, default_value_t = 0)]
+        jobs: usize,
         /// Optional path to JSON Schema file for strict validation
         #[arg(long)]
         schema: Option<String>,
         /// Additionally verify shard checksums (blake3) relative to the manifest directory
         #[arg(long, default_value_t = false)]
         check_checksums: bool,
+        /// Additionally verify event/membership shard rows are time-sorted
+        #[arg(long, default_value_t = false)]
+        check_sorted: bool,
+        /// Treat warnings (e.g. a shard shared by several splits) as errors
+        #[arg(long, default_value_t = false)]
+        strict: bool,
         /// Optional path to a security policy YAML to validate against the policy schema
         #[arg(long)]
         policy: Option<String>,
+        /// Warn (error under --strict) when the PII policy review is older than this many days
+        #[arg(long, default_value_t = 365)]
+        max_review_age_days: i64,
+        /// Also print the tables behind the conformance level and those missing for the next
+        #[arg(long, default_value_t = false)]
+        explain: bool,
     },
 
     /// Run evaluation/benchmarks on a dataset (placeholder)
     Eval {
-        #[arg(short, long)]
-        dataset: String,
+        /// Dataset directory or manifest path; repeat to compare several datasets
+        #[arg(short, long, required = true)]
+        dataset: Vec<String>,
     },
 
     /// Inspect a manifest and print a summary
     Inspect {
         #[arg(short, long)]
         manifest: String,
+        /// Also print the tables behind the conformance level and those missing for the next
+        #[arg(long, default_value_t = false)]
+        explain: bool,
+    },
+
+    /// Repair a manifest by dropping shards whose files are missing
+    Repair {
+        #[arg(short, long)]
+        manifest: String,
+        /// Report what would be pruned without rewriting the manifest
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Pretty-print the parsed rows of one shard referenced by a manifest
+    Cat {
+        #[arg(short, long)]
+        manifest: String,
+        /// Shard id as listed under `shards` in the manifest
+        #[arg(long)]
+        shard: String,
+        /// Maximum number of rows to print
+        #[arg(long)]
+        limit: Option<usize>,
     },
 
     /// Demonstrate AS OF snapshot using in-memory membership/catalog
     AsOfDemo {
-        /// Event time to snapshot at (nanoseconds)
+        /// Event time to snapshot at (nanoseconds unless --time-unit is given)
         #[arg(long, default_value_t = 150_i64)]
         t_ns: i64,
+        /// Also write the demo snapshot in this format (as snapshot.<ext> under --out)
+        #[arg(short = 'f', long, value_enum)]
+        format: Option<ExportFormat>,
+        /// Output directory for --format
+        #[arg(short, long, default_value = "./out")]
+        out: String,
+        /// Number of hyperedges to create
+        #[arg(long, default_value_t = 1)]
+        edges: u64,
+        /// Tails joining each hyperedge (plus one tail that joins and leaves early)
+        #[arg(long, default_value_t = 2)]
+        tails: u64,
+        /// Event time at which the first tail of each hyperedge joins (nanoseconds unless --time-unit is given)
+        #[arg(long, default_value_t = 100_i64)]
+        t_start: i64,
+        /// Gap between successive tail joins (nanoseconds unless --time-unit is given)
+        #[arg(long, default_value_t = 20_i64)]
+        dt: i64,
+    },
+
+    /// Write the raw AS OF HypergraphNetwork snapshot of a dataset as JSON or YAML
+    Snapshot {
+        /// Path to dataset root (directory containing dataset.yaml), or a path to dataset.yaml
+        #[arg(short = 'd', long)]
+        dataset: String,
+        /// Snapshot time (nanoseconds unless --time-unit is given)
+        #[arg(long, default_value_t = 150_i64)]
+        as_of: i64,
+        /// Output file
+        #[arg(short, long)]
+        out: String,
+        /// Output encoding; inferred from the --out extension (.yaml/.yml) when omitted
+        #[arg(short = 'f', long, value_enum)]
+        format: Option<SnapshotFormat>,
     },
 
     /// Export snapshot(s) to compatibility formats
     Export(ExportCmd),
 }
 
+impl Commands {
+    /// Rescale the time arguments of this command from `unit` to nanoseconds
+    fn times_to_ns(&mut self, unit: TimeUnit) -> Result<()> {
+        match self {
+            Commands::AsOfDemo {
+                t_ns, t_start, dt, ..
+            } => {
+                *t_ns = unit.to_ns(*t_ns)?;
+                *t_start = unit.to_ns(*t_start)?;
+                *dt = unit.to_ns(*dt)?;
+            }
+            Commands::Snapshot { as_of, .. } => *as_of = unit.to_ns(*as_of)?,
+            Commands::Export(cmd) => cmd.as_of = unit.to_ns(cmd.as_of)?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum SnapshotFormat {
+    Json,
+    Yaml,
+}
+
+impl SnapshotFormat {
+    /// Encoding implied by a file extension: YAML for .yaml/.yml, JSON otherwise
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml") | Some("yml") => SnapshotFormat::Yaml,
+            _ => SnapshotFormat::Json,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum ArityPolicyArg {
+    /// Split into several hyperedges sharing the head
+    Split,
+    /// Drop the hyperedge with a warning
+    Drop,
+    /// Fail the export
+    Error,
+}
+
+impl From<ArityPolicyArg> for ArityPolicy {
+    fn from(policy: ArityPolicyArg) -> Self {
+        match policy {
+            ArityPolicyArg::Split => ArityPolicy::Split,
+            ArityPolicyArg::Drop => ArityPolicy::Drop,
+            ArityPolicyArg::Error => ArityPolicy::Error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum BundleCompression {
+    /// Pack into <NAME>.tar.zst
+    Zstd,
+    /// Pack into <NAME>.tar.gz
+    Gzip,
+    /// Leave the bundle as loose files
+    None,
+}
+
+impl BundleCompression {
+    /// Archive extension after `.tar` and the `tar` flag selecting the compressor
+    fn extension_and_flag(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            BundleCompression::Zstd => Some(("zst", "--zstd")),
+            BundleCompression::Gzip => Some(("gz", "--gzip")),
+            BundleCompression::None => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 enum ExportFormat {
     LpgGraphml,
     LpgJson,
     RdfNquads,
+    Dot,
 }
 
 #[derive(Args, Debug)]
@@ -77,7 +269,7 @@ struct ExportCmd {
     /// Path to dataset root (directory containing dataset.yaml), or a path to dataset.yaml
     #[arg(short = 'd', long)]
     dataset: String,
-    /// Snapshot time (nanoseconds)
+    /// Snapshot time (nanoseconds unless --time-unit is given)
     #[arg(long, default_value_t = 150_i64)]
     as_of: i64,
     /// Output directory
@@ -86,27 +278,75 @@ struct ExportCmd {
     /// Export format
     #[arg(short = 'f', long, value_enum, default_value_t = ExportFormat::LpgGraphml)]
     format: ExportFormat,
+    /// Output file stem: writes <NAME>.graphml, <NAME>.lpg.json, <NAME>.nq or <NAME>.dot
+    #[arg(long, default_value = "snapshot")]
+    basename: String,
+    /// Append the AS OF time to the output file stem (<NAME>_asof<AS_OF>)
+    #[arg(long, default_value_t = false)]
+    include_timestamp: bool,
     /// Include labels as properties when available
     #[arg(long, default_value_t = false)]
     include_labels: bool,
     /// Filter by head vertex id
     #[arg(long)]
     filter_head: Option<u64>,
+    /// Emit vertices known to the membership log/catalog but absent from every exported hyperedge
+    #[arg(long, default_value_t = false)]
+    include_orphans: bool,
+    /// Cap the number of exported hyperedges in every format (lowest ids first); truncation is reported as a warning
+    #[arg(long)]
+    max_edges: Option<usize>,
+    /// Limit the number of tails (sources) per materialized hyperedge
+    #[arg(long)]
+    max_arity: Option<usize>,
+    /// What to do with hyperedges over --max-arity
+    #[arg(long, value_enum, default_value_t = ArityPolicyArg::Split)]
+    arity_policy: ArityPolicyArg,
+    /// Worker threads for parsing membership shards (0 = all cores, 1 = sequential)
+    #[arg(long, default_value_t = 1)]
+    threads: usize,
+    /// Pretty-print JSON outputs (LPG JSON and export.meta.json); this is the default
+    #[arg(long, default_value_t = false, overrides_with = "compact")]
+    pretty: bool,
+    /// Write compact JSON outputs without indentation
+    #[arg(long, default_value_t = false, overrides_with = "pretty")]
+    compact: bool,
     /// Optional path to a security policy file to enforce before export
     #[arg(long)]
     policy: Option<String>,
+    /// Print a per-rule trace of the policy evaluation (matched/failed constraints and decision)
+    #[arg(long, default_value_t = false)]
+    explain_policy: bool,
     /// Purpose of use (for ABAC)
     #[arg(long)]
     purpose: Option<String>,
+    /// Optional path to a licenses.yaml extending the built-in license table
+    #[arg(long)]
+    licenses: Option<String>,
+    /// Print vertex, hyperedge, arity and degree counts of the exported snapshot
+    #[arg(long, default_value_t = false)]
+    summary: bool,
+    /// Cache storage-backed snapshots here, keyed by the manifest content hash and AS OF time
+    #[arg(long)]
+    cache_dir: Option<String>,
+    /// Also pack the bundle into <NAME>.tar.<ext> (see --basename) next to the output directory (needs `tar`)
+    #[arg(long, value_enum, default_value_t = BundleCompression::None)]
+    compress: BundleCompression,
 }
 
 fn main() -> Result<()> {
     // Initialize tracing/logging (stdout by default; OTEL stdout when feature is enabled in ndfh-api)
     __ndfh_cli_init_tracing();
 
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    cli.command.times_to_ns(cli.time_unit)?;
     match cli.command {
-        Commands::Convert { input, output } => {
+        Commands::Convert {
+            input,
+            output,
+            strict,
+            max_invalid_fraction,
+        } => {
             // Build manifest from directory and write dataset.yaml
             let input_path = std::path::Path::new(&input);
             let out_path = std::path::Path::new(&output);
@@ -115,11 +355,16 @@ fn main() -> Result<()> {
             } else {
                 out_path.join("dataset.yaml")
             };
-            let mf = ndfh_hdx::DatasetManifest::build_from_dir(
+            let opts = ndfh_hdx::BuildOptions {
+                strict,
+                max_invalid_fraction,
+            };
+            let mf = ndfh_hdx::DatasetManifest::build_from_dir_with_options(
                 input_path,
                 "converted-ndfh",
                 "0.1.0",
                 "NDF-H 1.0",
+                &opts,
             )
             .with_context(|| format!("failed to build manifest from {}", input))?;
             mf.write_to_path(&out_file)
@@ -128,14 +373,47 @@ fn main() -> Result<()> {
         }
         Commands::Verify {
             manifest,
+            manifest_glob,
+            jobs,
             schema,
             check_checksums,
+            check_sorted,
+            strict,
             policy,
+            max_review_age_days,
+            explain,
         } => {
-            let mf = DatasetManifest::from_path(&manifest)
-                .with_context(|| format!("failed to read manifest: {}", manifest))?;
-            mf.validate_basic()
-                .with_context(|| "basic validation failed".to_string())?;
+            if let Some(pattern) = manifest_glob {
+                return verify_manifest_batch(&pattern, jobs);
+            }
+            let Some(manifest) = manifest else {
+                bail!("either --manifest or --manifest-glob is required");
+            };
+            let mf = load_verified_manifest(Path::new(&manifest))?;
+
+            // Split leakage: a shard in several splits is a warning (error under --strict)
+            let overlaps = mf.split_overlaps();
+            if !overlaps.is_empty() {
+                let detail = overlaps
+                    .iter()
+                    .map(|(id, splits)| format!("{} in [{}]", id, splits.join(", ")))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                if strict {
+                    bail!("shards shared across splits: {}", detail);
+                }
+                eprintln!("warning: shards shared across splits: {}", detail);
+            }
+
+            // PII review cadence: a stale or undated review is a warning (error under --strict)
+            if let Some(pp) = &mf.pii_policy {
+                if let Err(e) = pp.validate_review_fresh(max_review_age_days, chrono::Utc::now()) {
+                    if strict {
+                        bail!("{}", e);
+                    }
+                    eprintln!("warning: {}", e);
+                }
+            }
 
             // If --schema provided, use it; otherwise, attempt default path if present.
             if let Some(schema_path) = schema {
@@ -177,6 +455,28 @@ fn main() -> Result<()> {
                 }
             }
 
+            // Optional row-level time-sortedness verification
+            if check_sorted {
+                let root = std::path::Path::new(&manifest)
+                    .parent()
+                    .unwrap_or(std::path::Path::new("."));
+                match mf.verify_time_sorted(root) {
+                    Ok(unsorted) => {
+                        if unsorted.is_empty() {
+                            println!("Time-sortedness verification OK");
+                        } else {
+                            println!("Out-of-order shards ({}):", unsorted.len());
+                            for id in unsorted {
+                                println!("  - {}", id);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        println!("Time-sortedness verification error: {}", e);
+                    }
+                }
+            }
+
             // Optional security policy validation against schema if provided or present next to manifest
             {
                 let policy_path = if let Some(p) = policy {
@@ -216,53 +516,44 @@ fn main() -> Result<()> {
             }
 
             // Print detected conformance level (heuristic)
-            let level = mf.detect_conformance();
-            println!("Manifest OK: {} (conformance {:?})", mf.dataset_name, level);
+            let report = mf.detect_conformance_explained();
+            println!(
+                "Manifest OK: {} (conformance {:?})",
+                mf.dataset_name, report.level
+            );
+            if explain {
+                println!("Conformance: {}", report);
+            }
         }
         Commands::Eval { dataset } => {
-            // Load and inspect dataset for basic evaluation metrics
-            let ds_path = PathBuf::from(&dataset);
-            let manifest_path: PathBuf = if ds_path.is_dir() {
-                ds_path.join("dataset.yaml")
-            } else {
-                ds_path.clone()
-            };
-            if !manifest_path.exists() {
-                bail!("dataset manifest not found at {}", manifest_path.display());
+            if dataset.len() > 1 {
+                // Comparative table across datasets plus a combined totals row
+                let stats = dataset
+                    .iter()
+                    .map(|ds| load_eval_dataset(ds).map(|(mf, _)| EvalStats::from_manifest(&mf)))
+                    .collect::<Result<Vec<_>>>()?;
+                print!("{}", render_eval_table(&stats));
+                return Ok(());
             }
-            let mf = DatasetManifest::from_path(&manifest_path)
-                .with_context(|| format!("failed to read manifest: {}", manifest_path.display()))?;
-            mf.validate_basic()
-                .context("manifest basic validation failed")?;
+
+            // Load and inspect dataset for basic evaluation metrics
+            let (mf, manifest_path) = load_eval_dataset(&dataset[0])?;
+            let stats = EvalStats::from_manifest(&mf);
 
             // Basic evaluation: conformance level and dataset statistics
-            let conformance = mf.detect_conformance();
             println!("Dataset: {} v{}", mf.dataset_name, mf.dataset_version);
-            println!("Conformance Level: {:?}", conformance);
+            println!("Conformance Level: {}", stats.conformance);
             println!("NDF Version: {}", mf.ndf_version);
             println!("License: {}", mf.license);
-            println!("Shards: {}", mf.shards.len());
-
-            // Compute time range and row counts if available
-            let mut total_rows = 0u64;
-            let mut min_time: Option<i64> = None;
-            let mut max_time: Option<i64> = None;
-
-            for shard in mf.shards.values() {
-                // Consolidated fields (post-schema normalization)
-                total_rows += shard.num_rows;
-                let (start, end) = shard.time_range;
-                min_time = Some(min_time.map(|t| t.min(start)).unwrap_or(start));
-                max_time = Some(max_time.map(|t| t.max(end)).unwrap_or(end));
-            }
+            println!("Shards: {}", stats.shards);
 
-            println!("Total Rows: {}", total_rows);
-            if let (Some(min), Some(max)) = (min_time, max_time) {
+            println!("Total Rows: {}", stats.rows);
+            if let Some((min, max)) = stats.time_range {
                 println!("Time Range: {} to {} ({} ns span)", min, max, max - min);
             }
 
             // PII classification summary
-            if let Some(pii_max) = pii_max_class(&mf) {
+            if let Some(pii_max) = &stats.pii_max {
                 println!("Max PII Classification: {}", pii_max);
             }
             if let Some(pp) = &mf.pii_policy {
@@ -270,39 +561,167 @@ fn main() -> Result<()> {
                     println!("Dataset PII Policy: {}", class);
                 }
             }
+
+            // Membership ledger diagnostics (e.g. a flood of never-closed memberships)
+            let dataset_root = manifest_path.parent().unwrap_or(Path::new("."));
+            if let Some(log) = hdx_io::load_membership_log_from_manifest(&mf, dataset_root)
+                .context("failed to load membership shards")?
+            {
+                let ms = log.stats();
+                println!(
+                    "Memberships: {} ({} open, {} closed) across {} hyperedges",
+                    ms.rows,
+                    ms.open,
+                    ms.closed,
+                    ms.hyperedges()
+                );
+            }
         }
-        Commands::Inspect { manifest } => {
+        Commands::Inspect { manifest, explain } => {
             let mf = DatasetManifest::from_path(&manifest)
                 .with_context(|| format!("failed to read manifest: {}", manifest))?;
             println!("{}", mf.summary());
-            println!("Detected conformance: {:?}", mf.detect_conformance());
+            if explain {
+                println!(
+                    "Detected conformance: {}",
+                    mf.detect_conformance_explained()
+                );
+            } else {
+                println!("Detected conformance: {:?}", mf.detect_conformance());
+            }
         }
-        Commands::AsOfDemo { t_ns } => {
-            // Build a simple in-memory topology: one hyperedge with head 99 and tails 10,11 active at t_ns=150
-            let mut txn = InMemoryTxn::default();
-            let h_id = txn.he_create(HeCreate {
-                head_v: 99,
-                fe_spec_json: "{}".to_string(),
-                state_schema_json: None,
-            })?;
-            // add tails and a removed tail
-            txn.mem_add(h_id, 10, 100)?;
-            txn.mem_add(h_id, 11, 120)?;
-            txn.mem_add(h_id, 12, 90)?;
-            txn.mem_rem(h_id, 12, 110)?;
+        Commands::Repair { manifest, dry_run } => {
+            let manifest_path = std::path::Path::new(&manifest);
+            let mut mf = DatasetManifest::from_path(manifest_path)
+                .with_context(|| format!("failed to read manifest: {}", manifest))?;
+            let root = manifest_path
+                .parent()
+                .unwrap_or_else(|| std::path::Path::new("."));
+            let pruned = mf
+                .prune_missing(root)
+                .with_context(|| "repair failed".to_string())?;
+            if pruned.is_empty() {
+                println!("No missing shards; manifest unchanged");
+            } else {
+                for id in &pruned {
+                    println!("pruned shard: {}", id);
+                }
+                if dry_run {
+                    println!("Dry run: {} shard(s) would be pruned", pruned.len());
+                } else {
+                    mf.write_to_path(manifest_path)
+                        .with_context(|| format!("failed to write manifest: {}", manifest))?;
+                    println!("Pruned {} shard(s); wrote {}", pruned.len(), manifest);
+                }
+            }
+        }
+        Commands::Cat {
+            manifest,
+            shard,
+            limit,
+        } => {
+            let manifest_path = std::path::Path::new(&manifest);
+            let mf = DatasetManifest::from_path(manifest_path)
+                .with_context(|| format!("failed to read manifest: {}", manifest))?;
+            let root = manifest_path
+                .parent()
+                .unwrap_or_else(|| std::path::Path::new("."));
+            let rows = hdx_io::read_shard_rows(&mf, root, &shard, limit)
+                .with_context(|| format!("failed to read shard: {}", shard))?;
+            for row in &rows {
+                println!("{}", to_json_string(row, true));
+            }
+        }
+        Commands::AsOfDemo {
+            t_ns,
+            format,
+            out,
+            edges,
+            tails,
+            t_start,
+            dt,
+        } => {
+            // Build an in-memory topology; the defaults give one hyperedge with head 99
+            // and tails 10,11 active at t_ns=150
+            let txn = build_demo_txn(edges, tails, t_start, dt)?;
             let net = txn.snapshot_as_of(t_ns);
             let ids = net.hyperedge_ids();
             println!("AS OF t_ns={} -> hyperedges: {}", t_ns, ids.len());
-            if let Some(first) = ids.first() {
-                if let Some(edge) = net.get_hyperedge(*first) {
+            for id in &ids {
+                if let Some(edge) = net.get_hyperedge(*id) {
                     println!(
                         "edge {}: sources={}, targets={}",
-                        first.raw(),
+                        id.raw(),
                         edge.sources.len(),
                         edge.targets.len()
                     );
                 }
             }
+
+            if let Some(format) = format {
+                fs::create_dir_all(&out).with_context(|| format!("creating {}", out))?;
+                let mut hids: Vec<u32> = ids.iter().map(|h| h.raw()).collect();
+                hids.sort_unstable();
+                let (file_name, contents) = match format {
+                    ExportFormat::LpgGraphml => {
+                        let mut buf = Vec::new();
+                        write_graphml(&mut buf, &net, false, Some(&hids), &[], None)?;
+                        ("snapshot.graphml", String::from_utf8(buf)?)
+                    }
+                    ExportFormat::LpgJson => (
+                        "snapshot.lpg.json",
+                        encode_lpg_json(&net, false, Some(&hids), &[], true),
+                    ),
+                    ExportFormat::RdfNquads => {
+                        ("snapshot.nq", encode_rdf_nquads(&net, Some(&hids), &[]))
+                    }
+                    ExportFormat::Dot => {
+                        ("snapshot.dot", encode_dot(&net, false, Some(&hids), &[]))
+                    }
+                };
+                let path = Path::new(&out).join(file_name);
+                fs::write(&path, contents)
+                    .with_context(|| format!("writing {}", path.display()))?;
+                println!("Demo snapshot export -> {}", path.display());
+            }
+        }
+        Commands::Snapshot {
+            dataset,
+            as_of,
+            out,
+            format,
+        } => {
+            let (mf, manifest_path) = load_eval_dataset(&dataset)?;
+            let dataset_root = manifest_path.parent().unwrap_or(Path::new("."));
+            let mem_log = hdx_io::load_membership_log_from_manifest(&mf, dataset_root)
+                .context("failed to load membership shards")?;
+            let cat = hdx_io::load_hyperedge_catalog_from_manifest(&mf, dataset_root)
+                .context("failed to load hyperedges shards")?;
+            let (Some(mem_log), Some(cat)) = (mem_log, cat) else {
+                bail!(
+                    "dataset at {} has no membership/hyperedges shards to snapshot",
+                    dataset_root.display()
+                );
+            };
+            let (net, _) = materialize_snapshot(&mem_log, &cat, as_of, None, ArityPolicy::Split)?;
+
+            let out_path = Path::new(&out);
+            let contents = match format.unwrap_or_else(|| SnapshotFormat::from_path(out_path)) {
+                SnapshotFormat::Json => serde_json::to_string_pretty(&net)?,
+                SnapshotFormat::Yaml => serde_yaml::to_string(&net)?,
+            };
+            if let Some(parent) = out_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("creating {}", parent.display()))?;
+            }
+            fs::write(out_path, contents)
+                .with_context(|| format!("writing {}", out_path.display()))?;
+            println!(
+                "AS OF t_ns={} snapshot ({} hyperedges) -> {}",
+                as_of,
+                net.hyperedge_ids().len(),
+                out_path.display()
+            );
         }
         Commands::Export(cmd) => {
             // Resolve dataset.yaml path
@@ -317,451 +736,885 @@ fn main() -> Result<()> {
             }
             let mf = DatasetManifest::from_path(&manifest_path)
                 .with_context(|| format!("failed to read manifest: {}", manifest_path.display()))?;
-            mf.validate_basic()
-                .context("manifest basic validation failed")?;
 
-            // Subject roles: currently assumed ["exporter"] until CLI accepts --role flags.
-            let subject_roles = vec!["exporter".to_string()];
+            let ctx = build_export_context(mf, &manifest_path, &cmd)?;
+            let copied_license = copy_license_file(&ctx);
+            write_export_notice(&ctx, &cmd, copied_license.as_deref())?;
+            let snapshot_path = write_export_output(&ctx, &cmd)?;
+            write_export_meta(&ctx, &cmd)?;
 
-            // If a policy file is provided OR found next to the manifest, evaluate it; otherwise fall back to minimal built-in enforcement.
-            let default_policy_path = manifest_path
-                .parent()
-                .unwrap_or(std::path::Path::new("."))
-                .join("security.policy.yaml");
-            let effective_policy_path = cmd.policy.as_ref().map(PathBuf::from).or_else(|| {
-                if default_policy_path.exists() {
-                    Some(default_policy_path.clone())
-                } else {
-                    None
-                }
-            });
-
-            if let Some(ppath) = effective_policy_path {
-                let policy = load_security_policy(&ppath).with_context(|| {
-                    format!("failed to read security policy: {}", ppath.display())
-                })?;
-                // Resource table reflects the export format to enable per-format rules
-                let resource_table = match cmd.format {
-                    ExportFormat::LpgGraphml => "lpg-graphml",
-                    ExportFormat::LpgJson => "lpg-json",
-                    ExportFormat::RdfNquads => "rdf-nquads",
-                };
-                let decision = evaluate_policy(
-                    &policy,
-                    &mf,
-                    &subject_roles,
-                    cmd.purpose.as_deref(),
-                    "export",
-                    resource_table,
-                );
-                match decision {
-                    Decision::Deny(reason) => {
-                        ndfh_api::observability::record_policy_decision("deny", Some(&reason));
-                        bail!("export denied by policy: {}", reason);
-                    }
-                    Decision::Allow => {
-                        ndfh_api::observability::record_policy_decision("allow", None);
-                    }
-                }
-            } else {
-                enforce_export_policy(&mf, &subject_roles, cmd.purpose.as_deref())
-                    .context("export blocked by minimal policy")?;
+            let mut bundle = vec![
+                snapshot_path,
+                ctx.out_dir.join("NOTICE.txt"),
+                ctx.out_dir.join("export.meta.json"),
+            ];
+            if copied_license.is_some() {
+                bundle.push(ctx.out_dir.join("LICENSE.txt"));
             }
+            write_bundle_checksums(&ctx.out_dir, &bundle)?;
+            bundle.push(ctx.out_dir.join(BUNDLE_CHECKSUMS_FILE));
+            if let Some(archive) = pack_bundle(&ctx.out_dir, &cmd.basename, &bundle, cmd.compress)?
+            {
+                println!("Export bundle archive -> {}", archive.display());
+            }
+            if cmd.summary {
+                print_export_summary(&ctx);
+            }
+        }
+    }
+    ndfh_api::observability::shutdown_tracer();
+    Ok(())
+}
 
-            // Observability: start timer for export latency
-            let t_start = Instant::now();
-
-            // Build a snapshot from storage if possible, otherwise fall back to deterministic demo snapshot
-            let dataset_root = manifest_path.parent().unwrap_or(std::path::Path::new("."));
-            let mem_log_opt = hdx_io::load_membership_log_from_manifest(&mf, dataset_root)
-                .with_context(|| "failed to load membership shards")?;
-            let cat_opt = hdx_io::load_hyperedge_catalog_from_manifest(&mf, dataset_root)
-                .with_context(|| "failed to load hyperedges shards")?;
-
-            let net = if let (Some(mem_log), Some(cat)) = (mem_log_opt, cat_opt) {
-                // Real AS OF snapshot from manifest-backed shards
-                let snapshot = AsOfEngine::snapshot_with_catalog(&mem_log, &cat, cmd.as_of);
-                ndfh_api::observability::record_snapshot_metrics(
-                    cmd.as_of,
-                    snapshot.hyperedge_ids().len(),
-                    "storage",
-                );
-                snapshot
-            } else {
-                // Deterministic demo snapshot (when shards are absent)
-                let mut txn = InMemoryTxn::default();
-                let seed_head = 99u64;
-                let h_id = txn.he_create(HeCreate {
-                    head_v: seed_head,
-                    fe_spec_json: "{}".to_string(),
-                    state_schema_json: None,
-                })?;
-                txn.mem_add(h_id, 10, cmd.as_of - 50)?;
-                txn.mem_add(h_id, 11, cmd.as_of - 30)?;
-                txn.mem_add(h_id, 12, cmd.as_of - 60)?;
-                txn.mem_rem(h_id, 12, cmd.as_of - 40)?;
-                let snapshot = txn.snapshot_as_of(cmd.as_of);
-                ndfh_api::observability::record_snapshot_metrics(
-                    cmd.as_of,
-                    snapshot.hyperedge_ids().len(),
-                    "demo",
-                );
-                snapshot
-            };
-            // Record total hyperedges before any filtering (for metrics)
-            let orig_total_hyperedges = net.hyperedge_ids().len();
-
-            // license_permits_derivatives moved to top-level helper below to avoid duplication
+/// Read a manifest and run the basic validation shared by single and batch `verify`
+fn load_verified_manifest(manifest: &Path) -> Result<DatasetManifest> {
+    let mf = DatasetManifest::from_path(manifest)
+        .with_context(|| format!("failed to read manifest: {}", manifest.display()))?;
+    mf.validate_basic()
+        .with_context(|| "basic validation failed".to_string())?;
+    Ok(mf)
+}
 
-            /// Security policy structures (minimal evaluator)
-            #[derive(Debug, Clone, Serialize, Deserialize)]
-            struct SecurityPolicy {
-                #[serde(default)]
-                rules: Vec<PolicyRule>,
-            }
+/// Basic validation plus checksum verification of one manifest, as applied to each
+/// match of `verify --manifest-glob`; the error describes why the manifest failed
+fn verify_manifest_with_checksums(manifest: &Path) -> Result<()> {
+    let mf = load_verified_manifest(manifest)?;
+    let root = manifest.parent().unwrap_or(Path::new("."));
+    let mismatches = mf
+        .verify_checksums(root)
+        .context("checksum verification error")?;
+    if !mismatches.is_empty() {
+        bail!("checksum mismatches: {}", mismatches.join("; "));
+    }
+    Ok(())
+}
 
-            #[derive(Debug, Clone, Serialize, Deserialize)]
-            struct PolicyRule {
-                id: Option<String>,
-                description: Option<String>,
-                #[serde(default)]
-                r#match: BTreeMap<String, serde_yaml::Value>,
-                effect: String, // "allow" | "deny"
-            }
+/// Verify every manifest matching `pattern` on a pool of `jobs` threads
+/// (0 = all cores), print a pass/fail line per manifest and a summary, and
+/// fail if any manifest failed
+fn verify_manifest_batch(pattern: &str, jobs: usize) -> Result<()> {
+    use rayon::prelude::*;
+
+    let manifests = glob::glob(pattern)
+        .with_context(|| format!("invalid --manifest-glob pattern: {}", pattern))?
+        .collect::<std::result::Result<Vec<PathBuf>, _>>()
+        .context("failed to expand --manifest-glob")?;
+    if manifests.is_empty() {
+        bail!("no manifests match {}", pattern);
+    }
 
-            enum Decision {
-                Allow,
-                Deny(String),
-            }
+    let failures: Vec<Option<String>> = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .context("failed to start verify workers")?
+        .install(|| {
+            manifests
+                .par_iter()
+                .map(|manifest| {
+                    verify_manifest_with_checksums(manifest)
+                        .err()
+                        .map(|e| format!("{:#}", e))
+                })
+                .collect()
+        });
 
-            fn load_security_policy(path: &Path) -> Result<SecurityPolicy> {
-                let s = std::fs::read_to_string(path)?;
-                let p: SecurityPolicy = serde_yaml::from_str(&s)?;
-                Ok(p)
-            }
+    for (manifest, failure) in manifests.iter().zip(&failures) {
+        match failure {
+            None => println!("PASS {}", manifest.display()),
+            Some(reason) => println!("FAIL {}: {}", manifest.display(), reason),
+        }
+    }
+    let failed = failures.iter().filter(|f| f.is_some()).count();
+    println!(
+        "Verified {} manifests: {} passed, {} failed",
+        manifests.len(),
+        manifests.len() - failed,
+        failed
+    );
+    if failed > 0 {
+        bail!(
+            "{} of {} manifests failed verification",
+            failed,
+            manifests.len()
+        );
+    }
+    Ok(())
+}
 
-            /// Evaluate minimal policy by exact/contains matching on a small vocabulary:
-            /// - subject.roles: [..]
-            /// - action: "export"
-            /// - context.purpose: string
-            /// - resource.license.permits_derivatives: bool
-            /// - resource.pii_max_class: "none"|"low"|"moderate"|"high"
-            /// - resource.pii_class: per-export sensitivity (here equal to pii_max_class)
-            /// - resource.table: export target ("lpg-graphml"|"lpg-json"|"rdf-nquads")
-            fn evaluate_policy(
-                policy: &SecurityPolicy,
-                mf: &DatasetManifest,
-                subject_roles: &[String],
-                purpose: Option<&str>,
-                action: &str,
-                resource_table: &str,
-            ) -> Decision {
-                // Build context
-                let resource_license = mf.license.clone();
-                let derivatives = license_permits_derivatives(&resource_license);
-                let pii_max = pii_max_class(mf).unwrap_or_else(|| "none".to_string());
-                // Expose a single-class view aligned to the most sensitive shard class
-                let resource_pii_class = pii_max.clone();
-
-                // Helper to test a rule's match constraints
-                let mut matched_allow = None::<String>;
-                for rule in &policy.rules {
-                    let mut ok = true;
-                    for (k, v) in &rule.r#match {
-                        match k.as_str() {
-                            "action" => {
-                                // Support string or sequence
-                                if let Some(exp) = v.as_str() {
-                                    ok = ok && (exp == action);
-                                } else if let Some(arr) = v.as_sequence() {
-                                    let mut any = false;
-                                    for item in arr {
-                                        if let Some(s) = item.as_str() {
-                                            if s == action {
-                                                any = true;
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    ok = ok && any;
-                                } else {
-                                    ok = false;
-                                }
-                            }
-                            "context.purpose" => {
-                                // Support string or sequence
-                                if let Some(exp) = v.as_str() {
-                                    ok = ok && (Some(exp) == purpose);
-                                } else if let Some(arr) = v.as_sequence() {
-                                    let mut any = false;
-                                    for item in arr {
-                                        if let Some(s) = item.as_str() {
-                                            if Some(s) == purpose {
-                                                any = true;
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    ok = ok && any;
-                                } else {
-                                    ok = false;
-                                }
-                            }
-                            "subject.roles" => {
-                                // Expect sequence of strings; require all present in subject_roles (subset)
-                                if let Some(arr) = v.as_sequence() {
-                                    for item in arr {
-                                        if let Some(role) = item.as_str() {
-                                            if !subject_roles.iter().any(|r| r == role) {
-                                                ok = false;
-                                                break;
-                                            }
-                                        } else {
-                                            ok = false;
-                                            break;
-                                        }
-                                    }
-                                } else {
-                                    ok = false;
-                                }
-                            }
-                            "resource.license.permits_derivatives" => {
-                                if let Some(b) = v.as_bool() {
-                                    ok = ok && (derivatives == b);
-                                } else {
-                                    ok = false;
-                                }
-                            }
-                            "resource.pii_max_class" => {
-                                if let Some(exp) = v.as_str() {
-                                    ok = ok && (pii_max == exp);
-                                } else if let Some(arr) = v.as_sequence() {
-                                    let mut any = false;
-                                    for item in arr {
-                                        if let Some(s) = item.as_str() {
-                                            if pii_max == s {
-                                                any = true;
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    ok = ok && any;
-                                } else {
-                                    ok = false;
-                                }
-                            }
-                            "resource.pii_class" => {
-                                if let Some(exp) = v.as_str() {
-                                    ok = ok && (resource_pii_class == exp);
-                                } else if let Some(arr) = v.as_sequence() {
-                                    let mut any = false;
-                                    for item in arr {
-                                        if let Some(s) = item.as_str() {
-                                            if resource_pii_class == s {
-                                                any = true;
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    ok = ok && any;
-                                } else {
-                                    ok = false;
-                                }
-                            }
-                            "resource.table" => {
-                                if let Some(exp) = v.as_str() {
-                                    ok = ok && (resource_table == exp);
-                                } else if let Some(arr) = v.as_sequence() {
-                                    let mut any = false;
-                                    for item in arr {
-                                        if let Some(s) = item.as_str() {
-                                            if resource_table == s {
-                                                any = true;
-                                                break;
-                                            }
-                                        }
-                                    }
-                                    ok = ok && any;
-                                } else {
-                                    ok = false;
-                                }
-                            }
-                            _ => {
-                                // Unknown field: treat as non-match
-                                ok = false;
-                            }
-                        }
-                        if !ok {
-                            break;
-                        }
-                    }
+/// State shared by the export steps once policy checks have passed and the
+/// AS OF snapshot has been built (see `build_export_context`)
+struct ExportContext {
+    mf: DatasetManifest,
+    dataset_root: PathBuf,
+    net: ndfh_core::HypergraphNetwork,
+    /// Hyperedge ids selected for export, sorted ascending
+    allowed_ids: Vec<u32>,
+    orphans: Vec<u32>,
+    /// Hyperedges in the snapshot before any filtering (for metrics)
+    orig_total_hyperedges: usize,
+    decision: Decision,
+    snapshot_source: SnapshotSource,
+    /// Whether the snapshot came from --cache-dir (None without a cache)
+    cache_hit: Option<bool>,
+    /// Time spent materializing the AS OF snapshot (None on a cache hit)
+    snapshot_latency_ms: Option<f64>,
+    out_dir: PathBuf,
+    stem: String,
+    started: Instant,
+}
 
-                    if ok {
-                        let rid = rule.id.clone().unwrap_or_else(|| "<unnamed>".into());
-                        if rule.effect.eq_ignore_ascii_case("deny") {
-                            return Decision::Deny(rid);
-                        } else if rule.effect.eq_ignore_ascii_case("allow") {
-                            matched_allow = Some(rid);
-                            // keep scanning in case a later deny should take precedence
-                        }
-                    }
-                }
+/// Populate an in-memory ledger for `as-of-demo`: `edges` hyperedges, each with
+/// `tails` tails joining every `dt` ns from `t_start`, plus one tail active over
+/// `[t_start - 10, t_start + 10)`. Tail vertices count up from 10; heads follow them
+/// (starting no lower than 99) so the two never collide.
+fn build_demo_txn(edges: u64, tails: u64, t_start: i64, dt: i64) -> Result<InMemoryTxn> {
+    let mut txn = InMemoryTxn::default();
+    let per_edge = tails + 1;
+    let first_head = DEMO_HEAD_V.max(10 + edges * per_edge);
+    for e in 0..edges {
+        let h_id = txn.he_create(HeCreate {
+            head_v: first_head + e,
+            fe_spec_json: "{}".to_string(),
+            state_schema_json: None,
+            idempotency_key: None,
+        })?;
+        let base = 10 + e * per_edge;
+        for k in 0..tails {
+            txn.mem_add(h_id, base + k, t_start + k as i64 * dt)?;
+        }
+        // a tail that has already left by the default snapshot time
+        txn.mem_add(h_id, base + tails, t_start - 10)?;
+        txn.mem_rem(h_id, base + tails, t_start + 10)?;
+    }
+    Ok(txn)
+}
 
-                // Default decision: if a policy is provided but no allow matched, deny by default.
-                match matched_allow {
-                    Some(_) => Decision::Allow,
-                    None => Decision::Deny("no-allowing-rule-matched".into()),
-                }
-            }
+/// Head vertex of the single hyperedge in the demo snapshot
+const DEMO_HEAD_V: u64 = 99;
+/// Demo snapshot tails as (tail_v, t_start offset, optional t_end offset) relative to AS OF
+const DEMO_TAILS: [(u64, i64, Option<i64>); 3] =
+    [(10, -50, None), (11, -30, None), (12, -60, Some(-40))];
+
+/// Provenance of the exported snapshot, recorded in export.meta.json
+#[derive(Debug, Clone, PartialEq)]
+enum SnapshotSource {
+    /// Built from the manifest's membership/hyperedges shards
+    Storage { shards_loaded: usize },
+    /// Deterministic demo snapshot used when those shards are absent
+    Demo { as_of: i64 },
+}
 
-            // Ensure output directory
-            fs::create_dir_all(&cmd.out).with_context(|| format!("creating {}", cmd.out))?;
-
-            // Determine hyperedges to export with optional head filter
-            let mut allowed_ids: Vec<u32> =
-                net.hyperedge_ids().into_iter().map(|h| h.raw()).collect();
-            allowed_ids.sort_unstable();
-            if let Some(head_filter) = cmd.filter_head {
-                allowed_ids.retain(|hid| {
-                    if let Some(edge) = net.get_hyperedge(ndfh_core::HyperedgeId::from(*hid)) {
-                        edge.targets.iter().any(|t| t.raw() as u64 == head_filter)
-                    } else {
-                        false
-                    }
-                });
-            }
+impl SnapshotSource {
+    fn name(&self) -> &'static str {
+        match self {
+            SnapshotSource::Storage { .. } => "storage",
+            SnapshotSource::Demo { .. } => "demo",
+        }
+    }
 
-            // Attempt to propagate LICENSE/COPYING file from dataset root into export bundle
-            let dataset_root = manifest_path.parent().unwrap_or(std::path::Path::new("."));
-            let license_candidates = [
-                "LICENSE",
-                "LICENSE.txt",
-                "LICENSE.md",
-                "COPYING",
-                "COPYING.txt",
-                "COPYING.md",
-            ];
-            let mut copied_license: Option<String> = None;
-            for cand in &license_candidates {
-                let src = dataset_root.join(cand);
-                if src.exists() {
-                    let dst = Path::new(&cmd.out).join("LICENSE.txt");
-                    // Best-effort copy; do not fail export if copy fails
-                    if fs::copy(&src, &dst).is_ok() {
-                        copied_license = Some(cand.to_string());
-                        break;
-                    }
-                }
+    /// Parameters needed to reproduce the snapshot
+    fn params(&self) -> serde_json::Value {
+        match self {
+            SnapshotSource::Storage { shards_loaded } => {
+                serde_json::json!({ "shards_loaded": shards_loaded })
             }
-
-            // Emit NOTICE (metadata with latency is written after export below)
-            let notice = format!(
-                "NDF-H Export NOTICE\n\
-                 Dataset: {name} v{ver} (NDF {ndf})\n\
-                 License: {lic}\n\
-                 Purpose: {purpose}\n\
-                 AS OF: {asof}\n\
-                 Generated: {ts}\n\
-                 LicenseFileCopied: {copied}\n",
-                name = mf.dataset_name,
-                ver = mf.dataset_version,
-                ndf = mf.ndf_version,
-                lic = mf.license,
-                purpose = cmd.purpose.clone().unwrap_or_else(|| "unspecified".into()),
-                asof = cmd.as_of,
-                ts = chrono::Utc::now().to_rfc3339(),
-                copied = copied_license.unwrap_or_else(|| "none".into()),
-            );
-            let notice_path = Path::new(&cmd.out).join("NOTICE.txt");
-            fs::write(&notice_path, notice)
-                .with_context(|| format!("writing {}", notice_path.display()))?;
-
-            // Deterministic export
-            match cmd.format {
-                ExportFormat::LpgGraphml => {
-                    let s = encode_graphml(&net, cmd.include_labels, Some(&allowed_ids));
-                    let out = Path::new(&cmd.out).join("snapshot.graphml");
-                    fs::write(&out, s).with_context(|| format!("writing {}", out.display()))?;
-                    println!("GraphML export -> {}", out.display());
-                }
-                ExportFormat::LpgJson => {
-                    let s = encode_lpg_json(&net, cmd.include_labels, Some(&allowed_ids));
-                    let out = Path::new(&cmd.out).join("snapshot.lpg.json");
-                    fs::write(&out, s).with_context(|| format!("writing {}", out.display()))?;
-                    println!("LPG JSON export -> {}", out.display());
-                }
-                ExportFormat::RdfNquads => {
-                    let s = encode_rdf_nquads(&net, Some(&allowed_ids));
-                    let out = Path::new(&cmd.out).join("snapshot.nq");
-                    fs::write(&out, s).with_context(|| format!("writing {}", out.display()))?;
-                    println!("RDF N-Quads export -> {}", out.display());
-                }
+            SnapshotSource::Demo { as_of } => {
+                let tails: Vec<serde_json::Value> = DEMO_TAILS
+                    .iter()
+                    .map(|(tail_v, start, end)| {
+                        serde_json::json!({
+                            "tail_v": tail_v,
+                            "t_start": as_of + start,
+                            "t_end": end.map(|e| as_of + e),
+                        })
+                    })
+                    .collect();
+                // The demo is fully deterministic, so there is no random seed
+                serde_json::json!({
+                    "seed": null,
+                    "as_of": as_of,
+                    "head_v": DEMO_HEAD_V,
+                    "tails": tails,
+                })
             }
-
-            // Observability: finalize metrics and write export.meta.json
-            let exported_hyperedges = allowed_ids.len();
-            let filtered_count = if orig_total_hyperedges >= exported_hyperedges {
-                (orig_total_hyperedges - exported_hyperedges) as u64
-            } else {
-                0
-            };
-            let latency_ms: u64 = t_start.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
-
-            let export_meta = serde_json::json!({
-                "dataset_name": mf.dataset_name,
-                "dataset_version": mf.dataset_version,
-                "ndf_version": mf.ndf_version,
-                "license": mf.license,
-                "purpose": cmd.purpose,
-                "as_of": cmd.as_of,
-                "format": match cmd.format { ExportFormat::LpgGraphml => "lpg-graphml", ExportFormat::LpgJson => "lpg-json", ExportFormat::RdfNquads => "rdf-nquads" },
-                "filter_head": cmd.filter_head,
-                "metrics": {
-                    "hyperedges_total": orig_total_hyperedges as u64,
-                    "hyperedges_exported": exported_hyperedges as u64,
-                    "filtered_count": filtered_count,
-                    "latency_ms": latency_ms
-                }
-            });
-            let meta_path = Path::new(&cmd.out).join("export.meta.json");
-            fs::write(
-                &meta_path,
-                serde_json::to_string_pretty(&export_meta).unwrap(),
-            )
-            .with_context(|| format!("writing {}", meta_path.display()))?;
-
-            // Also emit metrics via observability hook (tracing; OTLP-ready)
-            ndfh_api::observability::record_export_metrics(
-                orig_total_hyperedges as u64,
-                exported_hyperedges as u64,
-                filtered_count,
-                latency_ms,
-            );
         }
     }
-    ndfh_api::observability::shutdown_tracer();
-    Ok(())
 }
 
-/// Initialize tracing/logging once at process start using ndfh-api helper.
-/// This is done at the earliest entry to main to allow downstream crates to emit spans if enabled.
-#[doc(hidden)]
-fn __ndfh_cli_init_tracing() {
-    // Safe to call multiple times; tracing-subscriber handles global set only once.
-    // We avoid depending on tracing macros in this crate by just initializing.
-    ndfh_api::observability::init_tracer();
+impl ExportFormat {
+    /// Resource table name used by policy rules and export.meta.json
+    fn resource_table(&self) -> &'static str {
+        match self {
+            ExportFormat::LpgGraphml => "lpg-graphml",
+            ExportFormat::LpgJson => "lpg-json",
+            ExportFormat::RdfNquads => "rdf-nquads",
+            ExportFormat::Dot => "dot",
+        }
+    }
 }
 
-/// Minimal ABAC enforcement for exporters:
-/// - Require subject role "exporter"
-/// - If dataset-level PII classification is "moderate" or "high" and purpose == "demo", deny.
-/// - Also deny demo if any shard pii_class is "moderate" or "high".
-fn enforce_export_policy(
-    mf: &DatasetManifest,
-    subject_roles: &[String],
-    purpose: Option<&str>,
-) -> Result<()> {
-    // role check
+/// Validate `mf` against policy for `cmd`, build the snapshot (storage-backed, or the
+/// deterministic demo snapshot when shards are absent), select hyperedges and orphans,
+/// and create the output directory. `manifest_path` locates the dataset root.
+fn build_export_context(
+    mf: DatasetManifest,
+    manifest_path: &Path,
+    cmd: &ExportCmd,
+) -> Result<ExportContext> {
+    if cmd.basename.is_empty() || cmd.basename.contains(['/', '\\']) {
+        bail!(
+            "--basename must be a plain file stem (got '{}')",
+            cmd.basename
+        );
+    }
+    let stem = if cmd.include_timestamp {
+        format!("{}_asof{}", cmd.basename, cmd.as_of)
+    } else {
+        cmd.basename.clone()
+    };
+
+    mf.validate_basic()
+        .context("manifest basic validation failed")?;
+    check_pii_classes(&mf).context("export blocked: invalid PII classification")?;
+    let dataset_root = manifest_path
+        .parent()
+        .unwrap_or(std::path::Path::new("."))
+        .to_path_buf();
+
+    // Subject roles: currently assumed ["exporter"] until CLI accepts --role flags.
+    let subject_roles = vec!["exporter".to_string()];
+
+    // License table: built-in defaults merged with --licenses or licenses.yaml next to the manifest
+    let default_licenses_path = dataset_root.join("licenses.yaml");
+    let effective_licenses_path = cmd.licenses.as_ref().map(PathBuf::from).or_else(|| {
+        if default_licenses_path.exists() {
+            Some(default_licenses_path.clone())
+        } else {
+            None
+        }
+    });
+    let licenses = LicenseTable::load_merged(effective_licenses_path.as_deref())?;
+
+    // If a policy file is provided OR found next to the manifest, evaluate it; otherwise fall back to minimal built-in enforcement.
+    let default_policy_path = dataset_root.join("security.policy.yaml");
+    let effective_policy_path = cmd.policy.as_ref().map(PathBuf::from).or_else(|| {
+        if default_policy_path.exists() {
+            Some(default_policy_path.clone())
+        } else {
+            None
+        }
+    });
+
+    let decision = if let Some(ppath) = effective_policy_path {
+        let policy = load_security_policy(&ppath)
+            .with_context(|| format!("failed to read security policy: {}", ppath.display()))?;
+        // Resource table reflects the export format to enable per-format rules
+        let explanation = explain_policy(
+            &policy,
+            &mf,
+            &licenses,
+            &subject_roles,
+            cmd.purpose.as_deref(),
+            "export",
+            cmd.format.resource_table(),
+        );
+        if cmd.explain_policy {
+            print!("{}", explanation.render());
+        }
+        match explanation.decision {
+            Decision::Deny(reason) => {
+                ndfh_api::observability::record_policy_decision("deny", Some(&reason));
+                bail!("export denied by policy: {}", reason);
+            }
+            Decision::Allow => {
+                ndfh_api::observability::record_policy_decision("allow", None);
+                Decision::Allow
+            }
+        }
+    } else {
+        enforce_export_policy(&mf, &licenses, &subject_roles, cmd.purpose.as_deref())
+            .context("export blocked by minimal policy")?;
+        Decision::Allow
+    };
+
+    // Observability: start timer for export latency
+    let started = Instant::now();
+
+    // With --cache-dir, probe the snapshot cache before parsing any shard: a hit only
+    // loads what --include-orphans (ledger vertices) or --max-arity (catalog ids) needs
+    let cache_path = cmd
+        .cache_dir
+        .as_deref()
+        .map(|cache_dir| snapshot_cache_path(Path::new(cache_dir), &mf, cmd.as_of))
+        .transpose()?;
+    let cached = cache_path.as_deref().and_then(read_cached_snapshot);
+    let needs_ledger = cached.is_none() || cmd.include_orphans;
+    let needs_catalog = needs_ledger || cmd.max_arity.is_some();
+
+    // Build a snapshot from storage if possible, otherwise fall back to deterministic demo snapshot
+    let mem_log_opt = if needs_ledger {
+        hdx_io::load_membership_log_from_manifest_with_threads(&mf, &dataset_root, cmd.threads)
+            .with_context(|| "failed to load membership shards")?
+    } else {
+        None
+    };
+    let cat_opt = if needs_catalog {
+        hdx_io::load_hyperedge_catalog_from_manifest(&mf, &dataset_root)
+            .with_context(|| "failed to load hyperedges shards")?
+    } else {
+        None
+    };
+    let shards_loaded = || {
+        ["membership", "hyperedges"]
+            .iter()
+            .flat_map(|table| {
+                hdx_io::resolve_table_shards(&mf, &dataset_root, table).unwrap_or_default()
+            })
+            .filter(|path| path.exists())
+            .count()
+    };
+
+    // Vertices known to the ledger (active or not), used for --include-orphans
+    let mut known_vertices: BTreeSet<u32> = BTreeSet::new();
+    let snapshot_source;
+    let mut cache_hit = None;
+    let snapshot_latency_ms;
+    let net = if let Some(mut snapshot) = cached {
+        snapshot_source = SnapshotSource::Storage {
+            shards_loaded: shards_loaded(),
+        };
+        cache_hit = Some(true);
+        snapshot_latency_ms = None;
+        if let (Some(mem_log), Some(cat)) = (&mem_log_opt, &cat_opt) {
+            known_vertices.extend(ledger_vertices(mem_log, cat));
+        }
+        if let Some(max_arity) = cmd.max_arity {
+            let cat = cat_opt.unwrap_or_default();
+            limit_arity(&mut snapshot, &cat, max_arity, cmd.arity_policy.into())?;
+        }
+        snapshot
+    } else if let (Some(mem_log), Some(cat)) = (mem_log_opt, cat_opt) {
+        snapshot_source = SnapshotSource::Storage {
+            shards_loaded: shards_loaded(),
+        };
+        known_vertices.extend(ledger_vertices(&mem_log, &cat));
+        // Real AS OF snapshot from manifest-backed shards
+        match cache_path {
+            Some(cache_path) => {
+                // Cached without an arity limit so any --max-arity can reuse the entry
+                let (mut snapshot, latency_ms) =
+                    materialize_snapshot(&mem_log, &cat, cmd.as_of, None, ArityPolicy::Split)?;
+                write_cached_snapshot(&cache_path, &snapshot)?;
+                cache_hit = Some(false);
+                snapshot_latency_ms = Some(latency_ms);
+                if let Some(max_arity) = cmd.max_arity {
+                    limit_arity(&mut snapshot, &cat, max_arity, cmd.arity_policy.into())?;
+                }
+                snapshot
+            }
+            None => {
+                let (snapshot, latency_ms) = materialize_snapshot(
+                    &mem_log,
+                    &cat,
+                    cmd.as_of,
+                    cmd.max_arity,
+                    cmd.arity_policy.into(),
+                )?;
+                snapshot_latency_ms = Some(latency_ms);
+                snapshot
+            }
+        }
+    } else {
+        // Deterministic demo snapshot (when shards are absent)
+        let mut txn = InMemoryTxn::default();
+        let h_id = txn.he_create(HeCreate {
+            head_v: DEMO_HEAD_V,
+            fe_spec_json: "{}".to_string(),
+            state_schema_json: None,
+            idempotency_key: None,
+        })?;
+        for (tail_v, start_offset, end_offset) in DEMO_TAILS {
+            txn.mem_add(h_id, tail_v, cmd.as_of + start_offset)?;
+            if let Some(end_offset) = end_offset {
+                txn.mem_rem(h_id, tail_v, cmd.as_of + end_offset)?;
+            }
+        }
+        snapshot_source = SnapshotSource::Demo { as_of: cmd.as_of };
+        known_vertices.extend(ledger_vertices(&txn.membership, &txn.catalog));
+        let t_snapshot = Instant::now();
+        let mut snapshot = txn.snapshot_as_of(cmd.as_of);
+        let latency_ms = t_snapshot.elapsed().as_secs_f64() * 1000.0;
+        ndfh_api::observability::record_snapshot_metrics(
+            cmd.as_of,
+            snapshot.hyperedge_ids().len(),
+            "demo",
+            latency_ms,
+        );
+        snapshot_latency_ms = Some(latency_ms);
+        if let Some(max_arity) = cmd.max_arity {
+            limit_arity(
+                &mut snapshot,
+                &txn.catalog,
+                max_arity,
+                cmd.arity_policy.into(),
+            )?;
+        }
+        snapshot
+    };
+    let orig_total_hyperedges = net.hyperedge_ids().len();
+
+    // Ensure output directory
+    fs::create_dir_all(&cmd.out).with_context(|| format!("creating {}", cmd.out))?;
+
+    // Determine hyperedges to export with optional head filter
+    let mut allowed_ids: Vec<u32> = net.hyperedge_ids().into_iter().map(|h| h.raw()).collect();
+    allowed_ids.sort_unstable();
+    if let Some(head_filter) = cmd.filter_head {
+        retain_head(&net, &mut allowed_ids, head_filter);
+    }
+    // Truncate before orphans are computed so dropped hyperedges' vertices count as orphans
+    if let Some(cap) = cmd.max_edges.filter(|&cap| cap < allowed_ids.len()) {
+        eprintln!(
+            "warning: export truncated to {} of {} hyperedges (--max-edges)",
+            cap,
+            allowed_ids.len()
+        );
+        allowed_ids.truncate(cap);
+    }
+
+    let orphans: Vec<u32> = if cmd.include_orphans {
+        orphan_vertices(&net, &allowed_ids, &known_vertices)
+    } else {
+        Vec::new()
+    };
+
+    Ok(ExportContext {
+        mf,
+        dataset_root,
+        net,
+        allowed_ids,
+        orphans,
+        orig_total_hyperedges,
+        decision,
+        snapshot_source,
+        cache_hit,
+        snapshot_latency_ms,
+        out_dir: PathBuf::from(&cmd.out),
+        stem,
+        started,
+    })
+}
+
+/// Materialize the AS OF snapshot from storage-backed shards, recording its latency
+/// (in milliseconds, also returned) through the snapshot metrics hook. With `max_arity`,
+/// oversized hyperedges are handled per `policy` (see [`limit_arity`]).
+fn materialize_snapshot(
+    mem_log: &ndfh_core::MembershipLog,
+    cat: &ndfh_core::HyperedgeCatalog,
+    as_of: i64,
+    max_arity: Option<usize>,
+    policy: ArityPolicy,
+) -> Result<(ndfh_core::HypergraphNetwork, f64)> {
+    let t_snapshot = Instant::now();
+    let mut snapshot = AsOfEngine::snapshot_with_catalog(mem_log, cat, as_of);
+    let latency_ms = t_snapshot.elapsed().as_secs_f64() * 1000.0;
+    ndfh_api::observability::record_snapshot_metrics(
+        as_of,
+        snapshot.hyperedge_ids().len(),
+        "storage",
+        latency_ms,
+    );
+    if let Some(max_arity) = max_arity {
+        limit_arity(&mut snapshot, cat, max_arity, policy)?;
+    }
+    Ok((snapshot, latency_ms))
+}
+
+/// Snapshot cache file for `mf` at `as_of`: `<cache_dir>/<content hash>_asof<AS_OF>.bin`.
+/// Any change to the manifest (e.g. a new shard checksum) changes the hash, so stale
+/// entries are never read.
+fn snapshot_cache_path(cache_dir: &Path, mf: &DatasetManifest, as_of: i64) -> Result<PathBuf> {
+    let hash = mf
+        .content_hash()
+        .context("failed to hash manifest for the snapshot cache")?;
+    Ok(cache_dir.join(format!("{}_asof{}.bin", hash, as_of)))
+}
+
+/// Read the bincode snapshot at `cache_path`, None on a miss. Unreadable entries
+/// are ignored with a warning so the caller rebuilds them.
+fn read_cached_snapshot(cache_path: &Path) -> Option<ndfh_core::HypergraphNetwork> {
+    let bytes = fs::read(cache_path).ok()?;
+    match ndfh_core::HypergraphNetwork::from_bincode(&bytes) {
+        Ok(net) => {
+            println!("Snapshot cache hit -> {}", cache_path.display());
+            Some(net)
+        }
+        Err(e) => {
+            eprintln!(
+                "warning: ignoring unreadable snapshot cache {}: {}",
+                cache_path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Write `net` as the bincode snapshot cache entry at `cache_path`
+fn write_cached_snapshot(cache_path: &Path, net: &ndfh_core::HypergraphNetwork) -> Result<()> {
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs::write(cache_path, net.to_bincode())
+        .with_context(|| format!("writing {}", cache_path.display()))
+}
+
+/// Every vertex the ledger mentions: membership tails (open or closed) and catalog heads
+fn ledger_vertices(
+    mem_log: &ndfh_core::MembershipLog,
+    cat: &ndfh_core::HyperedgeCatalog,
+) -> impl Iterator<Item = u32> {
+    mem_log
+        .tail_vertices()
+        .into_iter()
+        .chain(cat.head_vertices())
+        .map(|v| v as u32)
+}
+
+/// Keep only hyperedges whose head is `head`: the target of a ManyToOne edge or
+/// the source of a OneToMany edge
+fn retain_head(net: &ndfh_core::HypergraphNetwork, ids: &mut Vec<u32>, head: u64) {
+    ids.retain(|hid| {
+        net.get_hyperedge(ndfh_core::HyperedgeId::from(*hid))
+            .is_some_and(|edge| edge.heads().iter().any(|v| v.raw() as u64 == head))
+    });
+}
+
+/// Enforce `max_arity` tails per hyperedge, warning with the number of hyperedges
+/// split or dropped; the error policy fails listing the oversized hyperedge ids.
+/// Split-off hyperedges are numbered above every id in `cat`.
+fn limit_arity(
+    net: &mut ndfh_core::HypergraphNetwork,
+    cat: &ndfh_core::HyperedgeCatalog,
+    max_arity: usize,
+    policy: ArityPolicy,
+) -> Result<()> {
+    let report = match net.enforce_max_arity(max_arity, policy, cat) {
+        Ok(report) => report,
+        Err(e) => {
+            let ids: Vec<u32> = net
+                .oversized_hyperedges(max_arity.max(1))
+                .iter()
+                .map(|h| h.raw())
+                .collect();
+            bail!(
+                "{}: hyperedges {:?} exceed --max-arity {}",
+                e,
+                ids,
+                max_arity
+            );
+        }
+    };
+    if report.oversized.is_empty() {
+        return Ok(());
+    }
+    match policy {
+        ArityPolicy::Drop => eprintln!(
+            "warning: dropped {} hyperedge(s) exceeding max arity {}",
+            report.oversized.len(),
+            max_arity
+        ),
+        _ => eprintln!(
+            "warning: split {} hyperedge(s) exceeding max arity {} into {} additional hyperedge(s)",
+            report.oversized.len(),
+            max_arity,
+            report.added.len()
+        ),
+    }
+    Ok(())
+}
+
+/// Best-effort copy of a LICENSE/COPYING file from the dataset root into the export
+/// bundle as LICENSE.txt; returns the name of the copied file
+fn copy_license_file(ctx: &ExportContext) -> Option<String> {
+    let license_candidates = [
+        "LICENSE",
+        "LICENSE.txt",
+        "LICENSE.md",
+        "COPYING",
+        "COPYING.txt",
+        "COPYING.md",
+    ];
+    for cand in &license_candidates {
+        let src = ctx.dataset_root.join(cand);
+        // Do not fail export if copy fails
+        if src.exists() && fs::copy(&src, ctx.out_dir.join("LICENSE.txt")).is_ok() {
+            return Some(cand.to_string());
+        }
+    }
+    None
+}
+
+/// Write NOTICE.txt (metadata with latency is written by `write_export_meta`)
+fn write_export_notice(
+    ctx: &ExportContext,
+    cmd: &ExportCmd,
+    copied_license: Option<&str>,
+) -> Result<()> {
+    let notice = format!(
+        "NDF-H Export NOTICE\n\
+         Dataset: {name} v{ver} (NDF {ndf})\n\
+         License: {lic}\n\
+         Purpose: {purpose}\n\
+         AS OF: {asof}\n\
+         Generated: {ts}\n\
+         LicenseFileCopied: {copied}\n",
+        name = ctx.mf.dataset_name,
+        ver = ctx.mf.dataset_version,
+        ndf = ctx.mf.ndf_version,
+        lic = ctx.mf.license,
+        purpose = cmd.purpose.as_deref().unwrap_or("unspecified"),
+        asof = cmd.as_of,
+        ts = chrono::Utc::now().to_rfc3339(),
+        copied = copied_license.unwrap_or("none"),
+    );
+    let notice_path = ctx.out_dir.join("NOTICE.txt");
+    fs::write(&notice_path, notice).with_context(|| format!("writing {}", notice_path.display()))
+}
+
+/// Deterministically encode the selected hyperedges in `cmd.format` and return the
+/// written path
+fn write_export_output(ctx: &ExportContext, cmd: &ExportCmd) -> Result<PathBuf> {
+    let pretty_json = !cmd.compact;
+    let out = match cmd.format {
+        ExportFormat::LpgGraphml => {
+            let out = ctx.out_dir.join(format!("{}.graphml", ctx.stem));
+            let file =
+                fs::File::create(&out).with_context(|| format!("creating {}", out.display()))?;
+            let mut w = std::io::BufWriter::new(file);
+            write_graphml(
+                &mut w,
+                &ctx.net,
+                cmd.include_labels,
+                Some(&ctx.allowed_ids),
+                &ctx.orphans,
+                None,
+            )
+            .and_then(|_| std::io::Write::flush(&mut w))
+            .with_context(|| format!("writing {}", out.display()))?;
+            println!("GraphML export -> {}", out.display());
+            out
+        }
+        ExportFormat::LpgJson => {
+            let out = ctx.out_dir.join(format!("{}.lpg.json", ctx.stem));
+            let file =
+                fs::File::create(&out).with_context(|| format!("creating {}", out.display()))?;
+            let mut w = std::io::BufWriter::new(file);
+            write_lpg_json(
+                &mut w,
+                &ctx.net,
+                cmd.include_labels,
+                Some(&ctx.allowed_ids),
+                &ctx.orphans,
+                pretty_json,
+            )
+            .map_err(std::io::Error::from)
+            .and_then(|_| std::io::Write::flush(&mut w))
+            .with_context(|| format!("writing {}", out.display()))?;
+            println!("LPG JSON export -> {}", out.display());
+            out
+        }
+        ExportFormat::RdfNquads => {
+            let s = encode_rdf_nquads(&ctx.net, Some(&ctx.allowed_ids), &ctx.orphans);
+            let out = ctx.out_dir.join(format!("{}.nq", ctx.stem));
+            fs::write(&out, s).with_context(|| format!("writing {}", out.display()))?;
+            println!("RDF N-Quads export -> {}", out.display());
+            out
+        }
+        ExportFormat::Dot => {
+            let s = encode_dot(
+                &ctx.net,
+                cmd.include_labels,
+                Some(&ctx.allowed_ids),
+                &ctx.orphans,
+            );
+            let out = ctx.out_dir.join(format!("{}.dot", ctx.stem));
+            fs::write(&out, s).with_context(|| format!("writing {}", out.display()))?;
+            println!("DOT export -> {}", out.display());
+            out
+        }
+    };
+    Ok(out)
+}
+
+/// export.meta.json contents: dataset identity, export options and metrics
+fn export_meta_json(ctx: &ExportContext, cmd: &ExportCmd) -> serde_json::Value {
+    let exported_hyperedges = ctx.allowed_ids.len();
+    let filtered_count = ctx
+        .orig_total_hyperedges
+        .saturating_sub(exported_hyperedges) as u64;
+    let latency_ms: u64 = ctx
+        .started
+        .elapsed()
+        .as_millis()
+        .try_into()
+        .unwrap_or(u64::MAX);
+
+    serde_json::json!({
+        "dataset_name": ctx.mf.dataset_name,
+        "dataset_version": ctx.mf.dataset_version,
+        "ndf_version": ctx.mf.ndf_version,
+        "license": ctx.mf.license,
+        "purpose": cmd.purpose,
+        "as_of": cmd.as_of,
+        "format": cmd.format.resource_table(),
+        "filter_head": cmd.filter_head,
+        "include_orphans": cmd.include_orphans,
+        "max_edges": cmd.max_edges,
+        "max_arity": cmd.max_arity,
+        "output_stem": ctx.stem,
+        "snapshot_source": ctx.snapshot_source.name(),
+        "snapshot_params": ctx.snapshot_source.params(),
+        "snapshot_cache": ctx.cache_hit.map(|hit| if hit { "hit" } else { "miss" }),
+        "policy_decision": match ctx.decision {
+            Decision::Allow => "allow",
+            Decision::Deny(_) => "deny",
+        },
+        "metrics": {
+            "hyperedges_total": ctx.orig_total_hyperedges as u64,
+            "hyperedges_exported": exported_hyperedges as u64,
+            "filtered_count": filtered_count,
+            "orphan_vertices": ctx.orphans.len() as u64,
+            "latency_ms": latency_ms,
+            "snapshot_latency_ms": ctx.snapshot_latency_ms
+        }
+    })
+}
+
+/// Write export.meta.json and emit the export metrics via the observability hook
+fn write_export_meta(ctx: &ExportContext, cmd: &ExportCmd) -> Result<()> {
+    let export_meta = export_meta_json(ctx, cmd);
+    let meta_path = ctx.out_dir.join("export.meta.json");
+    fs::write(&meta_path, to_json_string(&export_meta, !cmd.compact))
+        .with_context(|| format!("writing {}", meta_path.display()))?;
+
+    // Also emit metrics via observability hook (tracing; OTLP-ready)
+    let metric = |key: &str| export_meta["metrics"][key].as_u64().unwrap_or(0);
+    ndfh_api::observability::record_export_metrics(
+        metric("hyperedges_total"),
+        metric("hyperedges_exported"),
+        metric("filtered_count"),
+        metric("latency_ms"),
+    );
+    Ok(())
+}
+
+/// Print the `NetworkSummary` of the hyperedges actually exported (after head
+/// filtering and --max-edges truncation)
+fn print_export_summary(ctx: &ExportContext) {
+    let mut exported = ndfh_core::HypergraphNetwork::new();
+    for &h in &ctx.allowed_ids {
+        if let Some(edge) = ctx.net.get_hyperedge(ndfh_core::HyperedgeId::from(h)) {
+            // ids in the source network are unique, so this cannot collide
+            let _ = exported.add_hyperedge(edge.clone());
+        }
+    }
+    let summary = exported.summary();
+    println!(
+        "Summary: {} vertices, {} hyperedges; arity min {} / mean {:.2} / max {}; max degree {}",
+        summary.vertices,
+        summary.hyperedges,
+        summary.min_arity,
+        summary.mean_arity,
+        summary.max_arity,
+        summary.max_degree
+    );
+}
+
+/// Name of the export bundle checksum list, written after every other bundle file
+const BUNDLE_CHECKSUMS_FILE: &str = "checksums.txt";
+
+/// Write `checksums.txt` into `out_dir` with one `<blake3 hex>  <file name>` line per
+/// bundle file (b3sum format), sorted by file name
+fn write_bundle_checksums(out_dir: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut entries = Vec::with_capacity(files.len());
+    for path in files {
+        let data = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .with_context(|| format!("bundle file without a name: {}", path.display()))?;
+        entries.push((name.to_string(), blake3::hash(&data).to_hex().to_string()));
+    }
+    entries.sort();
+    let listing: String = entries
+        .iter()
+        .map(|(name, hex)| format!("{}  {}\n", hex, name))
+        .collect();
+    let path = out_dir.join(BUNDLE_CHECKSUMS_FILE);
+    fs::write(&path, listing).with_context(|| format!("writing {}", path.display()))
+}
+
+/// Pack the bundle files into `<basename>.tar.<ext>` beside `out_dir` with the system
+/// `tar`; entries are bare file names. Returns the archive path, None when uncompressed.
+fn pack_bundle(
+    out_dir: &Path,
+    basename: &str,
+    files: &[PathBuf],
+    compression: BundleCompression,
+) -> Result<Option<PathBuf>> {
+    let Some((ext, flag)) = compression.extension_and_flag() else {
+        return Ok(None);
+    };
+    let out_dir = out_dir
+        .canonicalize()
+        .with_context(|| format!("resolving {}", out_dir.display()))?;
+    let Some(parent) = out_dir.parent() else {
+        bail!("cannot place a bundle archive beside {}", out_dir.display());
+    };
+    let archive = parent.join(format!("{}.tar.{}", basename, ext));
+
+    let mut tar = std::process::Command::new("tar");
+    tar.arg(flag)
+        .arg("-cf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&out_dir);
+    for path in files {
+        tar.arg(
+            path.file_name()
+                .with_context(|| format!("bundle file without a name: {}", path.display()))?,
+        );
+    }
+    let status = tar
+        .status()
+        .context("failed to run `tar` to pack the export bundle")?;
+    if !status.success() {
+        bail!("`tar` failed ({}) packing {}", status, archive.display());
+    }
+    Ok(Some(archive))
+}
+
+/// Initialize tracing/logging once at process start using ndfh-api helper.
+/// This is done at the earliest entry to main to allow downstream crates to emit spans if enabled.
+#[doc(hidden)]
+fn __ndfh_cli_init_tracing() {
+    // Safe to call multiple times; tracing-subscriber handles global set only once.
+    // We avoid depending on tracing macros in this crate by just initializing.
+    ndfh_api::observability::init_tracer();
+}
+
+/// Minimal ABAC enforcement for exporters:
+/// - Require subject role "exporter"
+/// - If dataset-level PII classification is "moderate" or "high" and purpose == "demo", deny.
+/// - Also deny demo if any shard pii_class is "moderate" or "high".
+fn enforce_export_policy(
+    mf: &DatasetManifest,
+    licenses: &LicenseTable,
+    subject_roles: &[String],
+    purpose: Option<&str>,
+) -> Result<()> {
+    // role check
     let has_exporter = subject_roles.iter().any(|r| r == "exporter");
     if !has_exporter {
         bail!("subject lacks required role 'exporter'");
@@ -807,16 +1660,7 @@ fn enforce_export_policy(
     // Minimal license gating when no external policy is provided:
     // - If license does NOT permit derivatives, deny export for outward-facing purposes.
     //   Allow only if explicitly marked internal/audit.
-    let spdx = mf.license.trim().to_uppercase();
-    let permits_derivatives = if spdx.starts_with("CC-BY-ND") {
-        false
-    } else {
-        matches!(
-            spdx.as_str(),
-            "CC-BY-4.0" | "CC0-1.0" | "MIT" | "APACHE-2.0" | "BSD-3-CLAUSE" | "BSD-2-CLAUSE"
-        )
-    };
-    if !permits_derivatives {
+    if !licenses.permits_derivatives(&mf.license) {
         let p = purpose.unwrap_or("unspecified").to_lowercase();
         let allowed_internal = p == "internal" || p == "audit";
         if !allowed_internal {
@@ -831,61 +1675,459 @@ fn enforce_export_policy(
     Ok(())
 }
 
-/// Compute the maximum pii_class across shards (none < low < moderate < high)
-fn pii_max_class(mf: &DatasetManifest) -> Option<String> {
-    fn score(s: &str) -> i32 {
-        match s {
-            "none" => 0,
-            "low" => 1,
-            "moderate" => 2,
-            "high" => 3,
-            _ => -1,
+/// Security policy structures (minimal evaluator)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SecurityPolicy {
+    #[serde(default)]
+    rules: Vec<PolicyRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PolicyRule {
+    id: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    r#match: BTreeMap<String, serde_yaml::Value>,
+    effect: String, // "allow" | "deny"
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Decision {
+    Allow,
+    Deny(String),
+}
+
+fn load_security_policy(path: &Path) -> Result<SecurityPolicy> {
+    let s = std::fs::read_to_string(path)?;
+    let p: SecurityPolicy = serde_yaml::from_str(&s)?;
+    Ok(p)
+}
+
+/// Outcome of a single `match` constraint within a rule.
+#[derive(Debug, Clone)]
+struct ConstraintCheck {
+    field: String,
+    expected: String,
+    actual: String,
+    matched: bool,
+}
+
+/// Per-rule trace: every constraint is evaluated (no short-circuit) so failures are all visible.
+#[derive(Debug, Clone)]
+struct RuleExplanation {
+    id: String,
+    effect: String,
+    constraints: Vec<ConstraintCheck>,
+    matched: bool,
+}
+
+/// Full policy trace as produced by `explain_policy`.
+#[derive(Debug, Clone)]
+struct PolicyExplanation {
+    rules: Vec<RuleExplanation>,
+    decision: Decision,
+}
+
+impl PolicyExplanation {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for rule in &self.rules {
+            out.push_str(&format!(
+                "rule {} ({}): {}\n",
+                rule.id,
+                rule.effect,
+                if rule.matched {
+                    "matched"
+                } else {
+                    "not matched"
+                }
+            ));
+            for c in &rule.constraints {
+                out.push_str(&format!(
+                    "  [{}] {}: expected {}, got {}\n",
+                    if c.matched { "ok" } else { "FAIL" },
+                    c.field,
+                    c.expected,
+                    c.actual
+                ));
+            }
+        }
+        match &self.decision {
+            Decision::Allow => out.push_str("decision: allow\n"),
+            Decision::Deny(reason) => out.push_str(&format!("decision: deny ({})\n", reason)),
+        }
+        out
+    }
+}
+
+/// Match a string-or-sequence constraint value against an actual value.
+fn yaml_matches_str(v: &serde_yaml::Value, actual: Option<&str>) -> bool {
+    if let Some(exp) = v.as_str() {
+        Some(exp) == actual
+    } else if let Some(arr) = v.as_sequence() {
+        arr.iter()
+            .any(|item| item.as_str().is_some() && item.as_str() == actual)
+    } else {
+        false
+    }
+}
+
+fn yaml_to_display(v: &serde_yaml::Value) -> String {
+    serde_json::to_string(v).unwrap_or_else(|_| format!("{:?}", v))
+}
+
+/// Evaluate minimal policy by exact/contains matching on a small vocabulary:
+/// - subject.roles: [..]
+/// - action: "export"
+/// - context.purpose: string
+/// - resource.license.permits_derivatives: bool
+/// - resource.pii_max_class: "none"|"low"|"moderate"|"high"
+/// - resource.pii_class: per-export sensitivity (here equal to pii_max_class)
+/// - resource.table: export target ("lpg-graphml"|"lpg-json"|"rdf-nquads")
+///
+/// Every constraint of every rule is recorded as matched/failed. The first matching
+/// deny rule wins; otherwise any matching allow rule allows, and no match denies by default.
+fn explain_policy(
+    policy: &SecurityPolicy,
+    mf: &DatasetManifest,
+    licenses: &LicenseTable,
+    subject_roles: &[String],
+    purpose: Option<&str>,
+    action: &str,
+    resource_table: &str,
+) -> PolicyExplanation {
+    // Build context
+    let derivatives = licenses.permits_derivatives(&mf.license);
+    let pii_max = pii_max_class(mf).unwrap_or_else(|| "none".to_string());
+    // Expose a single-class view aligned to the most sensitive shard class
+    let resource_pii_class = pii_max.clone();
+
+    let mut rules = Vec::with_capacity(policy.rules.len());
+    let mut denied_by = None::<String>;
+    let mut matched_allow = None::<String>;
+    for rule in &policy.rules {
+        let mut constraints = Vec::with_capacity(rule.r#match.len());
+        for (k, v) in &rule.r#match {
+            let (actual, matched) = match k.as_str() {
+                "action" => (action.to_string(), yaml_matches_str(v, Some(action))),
+                "context.purpose" => (
+                    purpose.unwrap_or("<none>").to_string(),
+                    yaml_matches_str(v, purpose),
+                ),
+                "subject.roles" => {
+                    // Expect sequence of strings; require all present in subject_roles (subset)
+                    let ok = v.as_sequence().is_some_and(|arr| {
+                        arr.iter().all(|item| {
+                            item.as_str()
+                                .is_some_and(|role| subject_roles.iter().any(|r| r == role))
+                        })
+                    });
+                    (format!("{:?}", subject_roles), ok)
+                }
+                "resource.license.permits_derivatives" => (
+                    derivatives.to_string(),
+                    v.as_bool().is_some_and(|b| b == derivatives),
+                ),
+                "resource.pii_max_class" => (pii_max.clone(), yaml_matches_str(v, Some(&pii_max))),
+                "resource.pii_class" => (
+                    resource_pii_class.clone(),
+                    yaml_matches_str(v, Some(&resource_pii_class)),
+                ),
+                "resource.table" => (
+                    resource_table.to_string(),
+                    yaml_matches_str(v, Some(resource_table)),
+                ),
+                // Unknown field: treat as non-match
+                _ => ("<unknown field>".to_string(), false),
+            };
+            constraints.push(ConstraintCheck {
+                field: k.clone(),
+                expected: yaml_to_display(v),
+                actual,
+                matched,
+            });
+        }
+
+        let matched = constraints.iter().all(|c| c.matched);
+        let rid = rule.id.clone().unwrap_or_else(|| "<unnamed>".into());
+        if matched {
+            if rule.effect.eq_ignore_ascii_case("deny") {
+                denied_by.get_or_insert_with(|| rid.clone());
+            } else if rule.effect.eq_ignore_ascii_case("allow") {
+                // keep scanning in case a later deny should take precedence
+                matched_allow.get_or_insert_with(|| rid.clone());
+            }
+        }
+        rules.push(RuleExplanation {
+            id: rid,
+            effect: rule.effect.clone(),
+            constraints,
+            matched,
+        });
+    }
+
+    // Default decision: if a policy is provided but no allow matched, deny by default.
+    let decision = match (denied_by, matched_allow) {
+        (Some(rid), _) => Decision::Deny(rid),
+        (None, Some(_)) => Decision::Allow,
+        (None, None) => Decision::Deny("no-allowing-rule-matched".into()),
+    };
+    PolicyExplanation { rules, decision }
+}
+
+/// Resolve a dataset directory (containing dataset.yaml) or manifest path, then load and validate it
+fn load_eval_dataset(dataset: &str) -> Result<(DatasetManifest, PathBuf)> {
+    let ds_path = PathBuf::from(dataset);
+    let manifest_path: PathBuf = if ds_path.is_dir() {
+        ds_path.join("dataset.yaml")
+    } else {
+        ds_path.clone()
+    };
+    if !manifest_path.exists() {
+        bail!("dataset manifest not found at {}", manifest_path.display());
+    }
+    let mf = DatasetManifest::from_path(&manifest_path)
+        .with_context(|| format!("failed to read manifest: {}", manifest_path.display()))?;
+    mf.validate_basic()
+        .context("manifest basic validation failed")?;
+    if let Err(e) = check_pii_classes(&mf) {
+        eprintln!("warning: {} (ignored for Max PII)", e);
+    }
+    Ok((mf, manifest_path))
+}
+
+/// Per-dataset statistics reported by `eval`
+#[derive(Debug, Clone)]
+struct EvalStats {
+    name: String,
+    rows: u64,
+    shards: usize,
+    time_range: Option<(i64, i64)>,
+    conformance: String,
+    pii_max: Option<String>,
+}
+
+impl EvalStats {
+    fn from_manifest(mf: &DatasetManifest) -> Self {
+        let mut rows = 0u64;
+        let mut time_range: Option<(i64, i64)> = None;
+        for shard in mf.shards.values() {
+            // Consolidated fields (post-schema normalization)
+            rows += shard.num_rows;
+            let (start, end) = shard.time_range;
+            time_range = Some(match time_range {
+                Some((lo, hi)) => (lo.min(start), hi.max(end)),
+                None => (start, end),
+            });
+        }
+        Self {
+            name: format!("{} v{}", mf.dataset_name, mf.dataset_version),
+            rows,
+            shards: mf.shards.len(),
+            time_range,
+            conformance: format!("{:?}", mf.detect_conformance()),
+            pii_max: pii_max_class(mf),
+        }
+    }
+}
+
+/// Fixed-width comparison table with one row per dataset and a TOTAL row
+fn render_eval_table(stats: &[EvalStats]) -> String {
+    use std::fmt::Write;
+
+    let span = |r: Option<(i64, i64)>| {
+        r.map(|(lo, hi)| (hi - lo).to_string())
+            .unwrap_or_else(|| "-".into())
+    };
+    let name_w = stats
+        .iter()
+        .map(|s| s.name.len())
+        .chain(std::iter::once("Dataset".len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<name_w$}  {:>12}  {:>6}  {:>16}  {:<12}  {:<8}",
+        "Dataset", "Rows", "Shards", "Span (ns)", "Conformance", "Max PII"
+    );
+    for s in stats {
+        let _ = writeln!(
+            out,
+            "{:<name_w$}  {:>12}  {:>6}  {:>16}  {:<12}  {:<8}",
+            s.name,
+            s.rows,
+            s.shards,
+            span(s.time_range),
+            s.conformance,
+            s.pii_max.as_deref().unwrap_or("-")
+        );
+    }
+
+    let total_range = stats
+        .iter()
+        .filter_map(|s| s.time_range)
+        .reduce(|(a_lo, a_hi), (b_lo, b_hi)| (a_lo.min(b_lo), a_hi.max(b_hi)));
+    let total_pii = stats
+        .iter()
+        .filter_map(|s| s.pii_max.as_deref())
+        .max_by_key(|c| pii_class_score(c))
+        .unwrap_or("-");
+    let _ = writeln!(
+        out,
+        "{:<name_w$}  {:>12}  {:>6}  {:>16}  {:<12}  {:<8}",
+        "TOTAL",
+        stats.iter().map(|s| s.rows).sum::<u64>(),
+        stats.iter().map(|s| s.shards).sum::<usize>(),
+        span(total_range),
+        "-",
+        total_pii
+    );
+    out
+}
+
+/// Rank of a pii_class in `PII_CLASSES` after trimming and lowercasing (as
+/// `add_shard` and `canonicalize` store it), or -1 when unrecognized
+fn pii_class_score(s: &str) -> i32 {
+    let class = s.trim().to_lowercase();
+    PII_CLASSES
+        .iter()
+        .position(|c| *c == class)
+        .map_or(-1, |rank| rank as i32)
+}
+
+/// Errors on the first shard whose non-empty pii_class is outside none|low|moderate|high;
+/// such classes are skipped by `pii_max_class` and would otherwise weaken export gating.
+fn check_pii_classes(mf: &DatasetManifest) -> Result<()> {
+    for (id, shard) in &mf.shards {
+        if let Some(class) = shard.pii_class.as_deref() {
+            if !class.trim().is_empty() && pii_class_score(class) < 0 {
+                bail!(
+                    "shard '{}' has unrecognized pii_class '{}' (expected {})",
+                    id,
+                    class,
+                    PII_CLASSES.join("|")
+                );
+            }
         }
     }
+    Ok(())
+}
+
+/// Compute the maximum pii_class across shards (none < low < moderate < high)
+fn pii_max_class(mf: &DatasetManifest) -> Option<String> {
     let mut max_s: Option<(&str, i32)> = None;
     for shard in mf.shards.values() {
         if let Some(class) = shard.pii_class.as_deref() {
-            let sc = score(class);
+            let sc = pii_class_score(class);
             if sc >= 0 && max_s.map(|(_, x)| sc > x).unwrap_or(true) {
-                max_s = Some((class, sc));
+                max_s = Some((PII_CLASSES[sc as usize], sc));
             }
         }
     }
     max_s.map(|(c, _)| c.to_string())
 }
 
-/// Simple license mapping to derived property "permits_derivatives"
-fn license_permits_derivatives(spdx: &str) -> bool {
-    // Conservative mapping for common cases
-    // - CC-BY-4.0, CC0-1.0, MIT, Apache-2.0 permit derivatives
-    // - CC-BY-ND* do not permit derivatives
-    // - Unknown licenses default to false
-    let s = spdx.trim().to_uppercase();
-    if s.starts_with("CC-BY-ND") {
-        return false;
+/// License -> "permits_derivatives" table.
+/// Built-in defaults cover common SPDX ids; operators extend or override them via `licenses.yaml`:
+///
+/// ```yaml
+/// licenses:
+///   Unlicense: true
+///   CC-BY-SA-4.0: true
+/// ```
+#[derive(Debug, Clone)]
+struct LicenseTable {
+    /// Keys are normalized (trimmed, uppercased) SPDX identifiers
+    entries: BTreeMap<String, bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LicenseConfig {
+    #[serde(default)]
+    licenses: BTreeMap<String, bool>,
+}
+
+impl LicenseTable {
+    /// Conservative built-in mapping: permissive licenses permit derivatives, everything else does not
+    fn builtin() -> Self {
+        let entries = [
+            "CC-BY-4.0",
+            "CC0-1.0",
+            "MIT",
+            "APACHE-2.0",
+            "BSD-3-CLAUSE",
+            "BSD-2-CLAUSE",
+        ]
+        .into_iter()
+        .map(|id| (id.to_string(), true))
+        .collect();
+        Self { entries }
+    }
+
+    /// Built-in defaults merged with an optional config file (config entries win)
+    fn load_merged(path: Option<&Path>) -> Result<Self> {
+        let mut table = Self::builtin();
+        if let Some(path) = path {
+            let s = fs::read_to_string(path)
+                .with_context(|| format!("failed to read license table: {}", path.display()))?;
+            let cfg: LicenseConfig = serde_yaml::from_str(&s)
+                .with_context(|| format!("failed to parse license table: {}", path.display()))?;
+            for (id, permits) in cfg.licenses {
+                table.entries.insert(id.trim().to_uppercase(), permits);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Derived property "permits_derivatives" for an SPDX id.
+    /// Unlisted licenses (including CC-BY-ND*) default to false.
+    fn permits_derivatives(&self, spdx: &str) -> bool {
+        let s = spdx.trim().to_uppercase();
+        self.entries.get(&s).copied().unwrap_or(false)
     }
-    matches!(
-        s.as_str(),
-        "CC-BY-4.0" | "CC0-1.0" | "MIT" | "APACHE-2.0" | "BSD-3-CLAUSE" | "BSD-2-CLAUSE"
-    )
 }
 
-/// Deterministic GraphML encoder (ManyToOne edges; hyperedge node reification)
-fn encode_graphml(
+/// Vertices in `known` that no exported hyperedge references (sorted ascending)
+fn orphan_vertices(
     net: &ndfh_core::HypergraphNetwork,
-    _include_labels: bool,
-    allowed_hids: Option<&[u32]>,
-) -> String {
-    use std::collections::BTreeSet;
-    use std::fmt::Write;
+    allowed_hids: &[u32],
+    known: &BTreeSet<u32>,
+) -> Vec<u32> {
+    let mut referenced: BTreeSet<u32> = BTreeSet::new();
+    for h in allowed_hids {
+        if let Some(edge) = net.get_hyperedge(ndfh_core::HyperedgeId::from(*h)) {
+            referenced.extend(
+                edge.sources
+                    .iter()
+                    .chain(edge.targets.iter())
+                    .map(|v| v.raw()),
+            );
+        }
+    }
+    known.difference(&referenced).copied().collect()
+}
 
-    let mut buf = String::new();
-    buf.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
-    buf.push('\n');
-    buf.push_str(r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#);
-    buf.push('\n');
-    buf.push_str(r#"<graph edgedefault="directed">"#);
-    buf.push('\n');
+/// Deterministic streaming GraphML writer (ManyToOne edges; hyperedge node reification).
+/// Writes directly to `w` so large snapshots are never materialized as one string.
+/// `orphans` are emitted as standalone vertex nodes. `max_edges` caps the number of
+/// hyperedges written (lowest ids first); returns how many hyperedges were written.
+/// With `include_labels`, edges of weighted hyperedges carry `weight` and `polarity` data.
+fn write_graphml<W: std::io::Write>(
+    w: &mut W,
+    net: &ndfh_core::HypergraphNetwork,
+    include_labels: bool,
+    allowed_hids: Option<&[u32]>,
+    orphans: &[u32],
+    max_edges: Option<usize>,
+) -> std::io::Result<usize> {
+    writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        w,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    writeln!(w, r#"<graph edgedefault="directed">"#)?;
 
     // Determine hyperedges to emit
     let mut hedge_ids: Vec<u32> = match allowed_hids {
@@ -893,6 +2135,9 @@ fn encode_graphml(
         None => net.hyperedge_ids().into_iter().map(|h| h.raw()).collect(),
     };
     hedge_ids.sort_unstable();
+    if let Some(cap) = max_edges {
+        hedge_ids.truncate(cap);
+    }
 
     // Collect vertex ids from edges
     let mut vertex_ids: BTreeSet<u32> = BTreeSet::new();
@@ -906,71 +2151,259 @@ fn encode_graphml(
             }
         }
     }
+    vertex_ids.extend(orphans.iter().copied());
+
+    let weighted = include_labels
+        && hedge_ids.iter().any(|h| {
+            net.get_hyperedge(ndfh_core::HyperedgeId::from(*h))
+                .and_then(weight_polarity)
+                .is_some()
+        });
+    if weighted {
+        writeln!(
+            w,
+            r#"<key id="weight" for="edge" attr.name="weight" attr.type="double"/>"#
+        )?;
+        writeln!(
+            w,
+            r#"<key id="polarity" for="edge" attr.name="polarity" attr.type="string"/>"#
+        )?;
+    }
 
     // Emit vertex nodes
     for v in vertex_ids {
-        let _ = write!(buf, r#"<node id="v{}"/>"#, v);
-        buf.push('\n');
+        writeln!(w, r#"<node id="v{}"/>"#, v)?;
     }
     // Reify each hyperedge as node "h{ID}", connect sources->h and h->target
-    for h in hedge_ids {
-        let _ = write!(buf, r#"<node id="h{}"/>"#, h);
-        buf.push('\n');
+    for &h in &hedge_ids {
+        writeln!(w, r#"<node id="h{}"/>"#, h)?;
         if let Some(edge) = net.get_hyperedge(ndfh_core::HyperedgeId::from(h)) {
+            let close = match weight_polarity(edge).filter(|_| include_labels) {
+                Some((weight, polarity)) => format!(
+                    r#"><data key="weight">{}</data><data key="polarity">{}</data></edge>"#,
+                    weight, polarity
+                ),
+                None => "/>".to_string(),
+            };
             for s in &edge.sources {
-                let _ = write!(buf, r#"<edge source="v{}" target="h{}"/>"#, s.raw(), h);
-                buf.push('\n');
+                writeln!(w, r#"<edge source="v{}" target="h{}"{}"#, s.raw(), h, close)?;
             }
             for t in &edge.targets {
-                let _ = write!(buf, r#"<edge source="h{}" target="v{}"/>"#, h, t.raw());
-                buf.push('\n');
+                writeln!(w, r#"<edge source="h{}" target="v{}"{}"#, h, t.raw(), close)?;
             }
         }
     }
 
-    buf.push_str("</graph>\n</graphml>\n");
-    buf
+    writeln!(w, "</graph>\n</graphml>")?;
+    Ok(hedge_ids.len())
 }
 
-/// Deterministic LPG JSON encoder: { "nodes": [ {id: "vX"}...], "edges": [ {src, dst, kind}... ] }
+/// Serialize JSON either pretty-printed (human use) or compact (storage)
+fn to_json_string(value: &serde_json::Value, pretty: bool) -> String {
+    let res = if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    };
+    res.unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Format identifier written at the top of every LPG JSON export
+const LPG_JSON_FORMAT: &str = "ndfh-lpg";
+/// Version of the LPG JSON layout written by `write_lpg_json`
+const LPG_JSON_VERSION: &str = "1.0";
+
+/// Deterministic LPG JSON encoder returning the same bytes `write_lpg_json` streams
 fn encode_lpg_json(
     net: &ndfh_core::HypergraphNetwork,
-    _include_labels: bool,
+    include_labels: bool,
     allowed_hids: Option<&[u32]>,
+    orphans: &[u32],
+    pretty: bool,
 ) -> String {
-    use serde_json::json;
-    let mut node_set: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
-    let mut edges: Vec<serde_json::Value> = Vec::new();
+    let mut buf = Vec::new();
+    match write_lpg_json(&mut buf, net, include_labels, allowed_hids, orphans, pretty) {
+        Ok(()) => String::from_utf8(buf).unwrap_or_else(|_| "{}".to_string()),
+        Err(_) => "{}".to_string(),
+    }
+}
 
+/// Deterministic LPG JSON writer:
+/// { "format": "ndfh-lpg", "version", "nodes": [ {id: "vX"}...], "edges": [ {src, dst, kind}... ] }.
+/// Edges are serialized straight to `w` as hyperedges are visited; only the sorted node
+/// ids are buffered. `orphans` are emitted as nodes without edges. With `include_labels`,
+/// edges of weighted hyperedges also carry the signed `weight` and its `polarity`.
+fn write_lpg_json<W: std::io::Write>(
+    w: W,
+    net: &ndfh_core::HypergraphNetwork,
+    include_labels: bool,
+    allowed_hids: Option<&[u32]>,
+    orphans: &[u32],
+    pretty: bool,
+) -> serde_json::Result<()> {
     let mut hedge_ids: Vec<u32> = match allowed_hids {
         Some(slice) => slice.to_vec(),
         None => net.hyperedge_ids().into_iter().map(|h| h.raw()).collect(),
     };
     hedge_ids.sort_unstable();
 
-    for h in hedge_ids {
-        node_set.insert(format!("h{}", h));
+    let mut nodes: BTreeSet<String> = orphans.iter().map(|v| format!("v{}", v)).collect();
+    for &h in &hedge_ids {
+        nodes.insert(format!("h{}", h));
+        if let Some(edge) = net.get_hyperedge(ndfh_core::HyperedgeId::from(h)) {
+            for v in edge.sources.iter().chain(&edge.targets) {
+                nodes.insert(format!("v{}", v.raw()));
+            }
+        }
+    }
+
+    let doc = LpgJsonDocument {
+        net,
+        hedge_ids: &hedge_ids,
+        nodes: &nodes,
+        include_labels,
+    };
+    if pretty {
+        serde_json::to_writer_pretty(w, &doc)
+    } else {
+        serde_json::to_writer(w, &doc)
+    }
+}
+
+/// Serializes the LPG JSON envelope, generating edge records lazily while writing
+struct LpgJsonDocument<'a> {
+    net: &'a ndfh_core::HypergraphNetwork,
+    hedge_ids: &'a [u32],
+    nodes: &'a BTreeSet<String>,
+    include_labels: bool,
+}
+
+#[derive(Serialize)]
+struct LpgJsonNode<'a> {
+    id: &'a str,
+}
+
+#[derive(Serialize)]
+struct LpgJsonEdge {
+    src: String,
+    dst: String,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    polarity: Option<&'static str>,
+}
+
+/// Signed weight of a weighted hyperedge and its polarity label:
+/// "inhibitory" when negative, "excitatory" otherwise
+fn weight_polarity(edge: &ndfh_core::Hyperedge) -> Option<(f64, &'static str)> {
+    edge.weight()
+        .map(|w| (w, if w < 0.0 { "inhibitory" } else { "excitatory" }))
+}
+
+/// Edge list of an `LpgJsonDocument`: tail edges v->h, then head edges h->v, per hyperedge
+struct LpgJsonEdges<'a>(&'a LpgJsonDocument<'a>);
+
+impl Serialize for LpgJsonEdges<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let net = self.0.net;
+        let include_labels = self.0.include_labels;
+        serializer.collect_seq(self.0.hedge_ids.iter().flat_map(|&h| {
+            let edge = net.get_hyperedge(ndfh_core::HyperedgeId::from(h));
+            let labels = edge.and_then(weight_polarity).filter(|_| include_labels);
+            let (weight, polarity) = (labels.map(|l| l.0), labels.map(|l| l.1));
+            let tails = edge
+                .into_iter()
+                .flat_map(|e| &e.sources)
+                .map(move |s| LpgJsonEdge {
+                    src: format!("v{}", s.raw()),
+                    dst: format!("h{}", h),
+                    kind: "tail",
+                    weight,
+                    polarity,
+                });
+            let heads = edge
+                .into_iter()
+                .flat_map(|e| &e.targets)
+                .map(move |t| LpgJsonEdge {
+                    src: format!("h{}", h),
+                    dst: format!("v{}", t.raw()),
+                    kind: "head",
+                    weight,
+                    polarity,
+                });
+            tails.chain(heads)
+        }))
+    }
+}
+
+impl Serialize for LpgJsonDocument<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let nodes: Vec<LpgJsonNode> = self.nodes.iter().map(|id| LpgJsonNode { id }).collect();
+        let mut doc = serializer.serialize_struct("LpgJsonDocument", 4)?;
+        doc.serialize_field("format", LPG_JSON_FORMAT)?;
+        doc.serialize_field("version", LPG_JSON_VERSION)?;
+        doc.serialize_field("nodes", &nodes)?;
+        doc.serialize_field("edges", &LpgJsonEdges(self))?;
+        doc.end()
+    }
+}
+
+/// Deterministic Graphviz DOT encoder: vertices "v{V}" as ellipses, reified hyperedges
+/// "h{H}" as boxes, tail edges v->h and head edges h->v. `orphans` are emitted as
+/// vertices without edges.
+fn encode_dot(
+    net: &ndfh_core::HypergraphNetwork,
+    _include_labels: bool,
+    allowed_hids: Option<&[u32]>,
+    orphans: &[u32],
+) -> String {
+    let mut hedge_ids: Vec<u32> = match allowed_hids {
+        Some(slice) => slice.to_vec(),
+        None => net.hyperedge_ids().into_iter().map(|h| h.raw()).collect(),
+    };
+    hedge_ids.sort_unstable();
+
+    let mut vertex_ids: BTreeSet<u32> = orphans.iter().copied().collect();
+    let mut edges: Vec<String> = Vec::new();
+    for &h in &hedge_ids {
         if let Some(edge) = net.get_hyperedge(ndfh_core::HyperedgeId::from(h)) {
             for s in &edge.sources {
-                node_set.insert(format!("v{}", s.raw()));
-                edges.push(json!({"src": format!("v{}", s.raw()), "dst": format!("h{}", h), "kind": "tail"}));
+                vertex_ids.insert(s.raw());
+                edges.push(format!("  v{} -> h{};", s.raw(), h));
             }
             for t in &edge.targets {
-                node_set.insert(format!("v{}", t.raw()));
-                edges.push(json!({"src": format!("h{}", h), "dst": format!("v{}", t.raw()), "kind": "head"}));
+                vertex_ids.insert(t.raw());
+                edges.push(format!("  h{} -> v{};", h, t.raw()));
             }
         }
     }
 
-    let nodes: Vec<serde_json::Value> = node_set.into_iter().map(|id| json!({"id": id})).collect();
-    serde_json::to_string_pretty(&json!({"nodes": nodes, "edges": edges}))
-        .unwrap_or_else(|_| "{}".to_string())
+    let mut out = String::from("digraph ndfh {\n");
+    for v in vertex_ids {
+        out.push_str(&format!("  v{} [shape=ellipse];\n", v));
+    }
+    for h in &hedge_ids {
+        out.push_str(&format!("  h{} [shape=box];\n", h));
+    }
+    for e in edges {
+        out.push_str(&e);
+        out.push('\n');
+    }
+    out.push_str("}\n");
+    out
 }
 
 /// Deterministic RDF N-Quads encoder using simple vocabulary:
 /// <hedge:h{H}> <ndfh:hasTail> <vertex:v{V}> .
 /// <hedge:h{H}> <ndfh:hasHead> <vertex:v{V}> .
-fn encode_rdf_nquads(net: &ndfh_core::HypergraphNetwork, allowed_hids: Option<&[u32]>) -> String {
+/// <vertex:v{V}> <rdf:type> <ndfh:Vertex> .   (orphan vertices only)
+fn encode_rdf_nquads(
+    net: &ndfh_core::HypergraphNetwork,
+    allowed_hids: Option<&[u32]>,
+    orphans: &[u32],
+) -> String {
     let base = "https://ndfh.example.org/vocab/";
     let mut lines: Vec<String> = Vec::new();
 
@@ -1005,6 +2438,13 @@ fn encode_rdf_nquads(net: &ndfh_core::HypergraphNetwork, allowed_hids: Option<&[
         }
     }
 
+    for v in orphans {
+        lines.push(format!(
+            "<{}vertex/v{}> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <{}Vertex> .",
+            base, v, base
+        ));
+    }
+
     lines.join("\n") + "\n"
 }
 
@@ -1013,6 +2453,18 @@ mod tests {
     use super::*;
     use ndfh_api::{HeCreate, InMemoryTxn};
 
+    fn encode_graphml(
+        net: &ndfh_core::HypergraphNetwork,
+        include_labels: bool,
+        allowed_hids: Option<&[u32]>,
+        orphans: &[u32],
+    ) -> String {
+        let mut buf = Vec::new();
+        write_graphml(&mut buf, net, include_labels, allowed_hids, orphans, None)
+            .expect("write to Vec");
+        String::from_utf8(buf).expect("utf8")
+    }
+
     fn build_demo_snapshot(as_of: i64) -> ndfh_core::HypergraphNetwork {
         let mut txn = InMemoryTxn::default();
         let h_id = txn
@@ -1020,6 +2472,7 @@ mod tests {
                 head_v: 99,
                 fe_spec_json: "{}".to_string(),
                 state_schema_json: None,
+                idempotency_key: None,
             })
             .expect("he_create");
         txn.mem_add(h_id, 10, as_of - 50).expect("mem_add 10");
@@ -1037,40 +2490,180 @@ mod tests {
             ids.sort_unstable();
             ids
         };
-        let s1 = encode_graphml(&net, false, Some(&allowed_ids));
-        let s2 = encode_graphml(&net, false, Some(&allowed_ids));
+        let s1 = encode_graphml(&net, false, Some(&allowed_ids), &[]);
+        let s2 = encode_graphml(&net, false, Some(&allowed_ids), &[]);
         assert_eq!(
             s1, s2,
             "GraphML encoder output must be byte-stable for same snapshot"
         );
     }
 
+    #[test]
+    fn streaming_graphml_writer_handles_large_snapshots() {
+        let mut txn = InMemoryTxn::default();
+        let n_hedges = 5_000u64;
+        for i in 0..n_hedges {
+            let h_id = txn
+                .he_create(HeCreate {
+                    head_v: 100_000 + i,
+                    fe_spec_json: "{}".to_string(),
+                    state_schema_json: None,
+                    idempotency_key: None,
+                })
+                .expect("he_create");
+            for k in 0..4 {
+                txn.mem_add(h_id, i * 4 + k, 0).expect("mem_add");
+            }
+        }
+        let net = txn.snapshot_as_of(10);
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("snapshot.graphml");
+        let mut w = std::io::BufWriter::new(fs::File::create(&path).expect("create"));
+        let written = write_graphml(&mut w, &net, false, None, &[], None).expect("write");
+        std::io::Write::flush(&mut w).expect("flush");
+        assert_eq!(written as u64, n_hedges);
+
+        // Structural parse: header, one self-closing element per line, closing tags
+        let text = fs::read_to_string(&path).expect("read back");
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("<?xml"));
+        assert_eq!(&lines[lines.len() - 2..], &["</graph>", "</graphml>"]);
+        let body = &lines[3..lines.len() - 2];
+        assert!(body
+            .iter()
+            .all(|l| (l.starts_with("<node ") || l.starts_with("<edge ")) && l.ends_with("/>")));
+        let nodes = body.iter().filter(|l| l.starts_with("<node ")).count() as u64;
+        let edges = body.iter().filter(|l| l.starts_with("<edge ")).count() as u64;
+        assert_eq!(nodes, n_hedges * 4 + n_hedges + n_hedges);
+        assert_eq!(edges, n_hedges * 5);
+
+        let mut capped = Vec::new();
+        let written = write_graphml(&mut capped, &net, false, None, &[], Some(10)).expect("write");
+        assert_eq!(written, 10);
+        let capped = String::from_utf8(capped).unwrap();
+        assert_eq!(capped.matches(r#"<node id="h"#).count(), 10);
+    }
+
     #[test]
     fn lpg_json_encoder_is_deterministic() {
         let net = build_demo_snapshot(150);
         let mut allowed_ids: Vec<u32> = net.hyperedge_ids().into_iter().map(|h| h.raw()).collect();
         allowed_ids.sort_unstable();
-        let s1 = encode_lpg_json(&net, false, Some(&allowed_ids));
-        let s2 = encode_lpg_json(&net, false, Some(&allowed_ids));
+        for pretty in [true, false] {
+            let s1 = encode_lpg_json(&net, false, Some(&allowed_ids), &[], pretty);
+            let s2 = encode_lpg_json(&net, false, Some(&allowed_ids), &[], pretty);
+            assert_eq!(
+                s1, s2,
+                "LPG JSON encoder output must be byte-stable for same snapshot"
+            );
+        }
+        let pretty = encode_lpg_json(&net, false, Some(&allowed_ids), &[], true);
+        let compact = encode_lpg_json(&net, false, Some(&allowed_ids), &[], false);
+        assert!(compact.len() < pretty.len() && !compact.contains('\n'));
         assert_eq!(
-            s1, s2,
-            "LPG JSON encoder output must be byte-stable for same snapshot"
+            serde_json::from_str::<serde_json::Value>(&pretty).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&compact).unwrap()
         );
     }
 
+    #[test]
+    fn lpg_json_has_envelope_and_streams_identical_bytes() {
+        let net = build_demo_snapshot(150);
+        for pretty in [true, false] {
+            let encoded = encode_lpg_json(&net, false, None, &[500], pretty);
+            let doc: serde_json::Value = serde_json::from_str(&encoded).unwrap();
+            assert_eq!(doc["format"], LPG_JSON_FORMAT);
+            assert_eq!(doc["version"], LPG_JSON_VERSION);
+            assert_eq!(doc["edges"].as_array().unwrap().len(), 3);
+            assert!(doc["nodes"].as_array().unwrap().len() >= 4);
+
+            let mut streamed = Vec::new();
+            write_lpg_json(&mut streamed, &net, false, None, &[500], pretty).unwrap();
+            assert_eq!(streamed, encoded.as_bytes());
+        }
+    }
+
+    #[test]
+    fn weighted_exports_carry_signed_weight_and_polarity() {
+        let mut net = ndfh_core::HypergraphNetwork::new();
+        for (id, weight) in [(1, -0.75), (2, 0.5)] {
+            let edge = ndfh_core::Hyperedge::new(
+                ndfh_core::HyperedgeId::from(id),
+                ndfh_core::NeuronId::from_slice(&[10 + id]),
+                ndfh_core::NeuronId::from_slice(&[99]),
+                ndfh_core::HyperedgeType::ManyToOne,
+            )
+            .unwrap()
+            .with_weight(weight)
+            .unwrap();
+            net.add_hyperedge(edge).unwrap();
+        }
+
+        let doc: serde_json::Value =
+            serde_json::from_str(&encode_lpg_json(&net, true, None, &[], false)).unwrap();
+        let edges = doc["edges"].as_array().unwrap();
+        let inhibitory: Vec<_> = edges
+            .iter()
+            .filter(|e| e["polarity"] == "inhibitory")
+            .collect();
+        assert_eq!(inhibitory.len(), 2);
+        assert!(inhibitory.iter().all(|e| e["weight"] == -0.75));
+        assert!(inhibitory
+            .iter()
+            .any(|e| e["src"] == "v11" && e["dst"] == "h1"));
+        assert!(edges
+            .iter()
+            .filter(|e| e["src"] == "h2" || e["dst"] == "h2")
+            .all(|e| e["polarity"] == "excitatory" && e["weight"] == 0.5));
+
+        // Labels are opt-in
+        let plain: serde_json::Value =
+            serde_json::from_str(&encode_lpg_json(&net, false, None, &[], false)).unwrap();
+        assert!(plain["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|e| e.get("polarity").is_none()));
+
+        let mut graphml = Vec::new();
+        write_graphml(&mut graphml, &net, true, None, &[], None).unwrap();
+        let graphml = String::from_utf8(graphml).unwrap();
+        assert!(graphml.contains(r#"<key id="polarity" for="edge""#));
+        assert!(graphml.contains(
+            r#"<edge source="v11" target="h1"><data key="weight">-0.75</data><data key="polarity">inhibitory</data></edge>"#
+        ));
+    }
+
     #[test]
     fn rdf_nquads_encoder_is_deterministic() {
         let net = build_demo_snapshot(150);
         let mut allowed_ids: Vec<u32> = net.hyperedge_ids().into_iter().map(|h| h.raw()).collect();
         allowed_ids.sort_unstable();
-        let s1 = encode_rdf_nquads(&net, Some(&allowed_ids));
-        let s2 = encode_rdf_nquads(&net, Some(&allowed_ids));
+        let s1 = encode_rdf_nquads(&net, Some(&allowed_ids), &[]);
+        let s2 = encode_rdf_nquads(&net, Some(&allowed_ids), &[]);
         assert_eq!(
             s1, s2,
             "RDF N-Quads encoder output must be byte-stable for same snapshot"
         );
     }
 
+    #[test]
+    fn dot_encoder_is_deterministic() {
+        let net = build_demo_snapshot(150);
+        let mut allowed_ids: Vec<u32> = net.hyperedge_ids().into_iter().map(|h| h.raw()).collect();
+        allowed_ids.sort_unstable();
+        let s1 = encode_dot(&net, false, Some(&allowed_ids), &[]);
+        let s2 = encode_dot(&net, false, Some(&allowed_ids), &[]);
+        assert_eq!(
+            s1, s2,
+            "DOT encoder output must be byte-stable for same snapshot"
+        );
+        assert!(s1.starts_with("digraph"));
+        assert!(s1.contains("[shape=box]") && s1.contains("[shape=ellipse]"));
+        assert!(s1.trim_end().ends_with('}'));
+    }
+
     #[test]
     fn head_filter_effect_is_consistent() {
         let net = build_demo_snapshot(150);
@@ -1084,12 +2677,391 @@ mod tests {
                 false
             }
         });
-        let s = encode_lpg_json(&net, false, Some(&filtered));
+        let s = encode_lpg_json(&net, false, Some(&filtered), &[], true);
         // Ensure that when we pass the already filtered list again, we get the same bytes (idempotent filtering)
-        let s_again = encode_lpg_json(&net, false, Some(&filtered));
+        let s_again = encode_lpg_json(&net, false, Some(&filtered), &[], true);
         assert_eq!(
             s, s_again,
             "Filtering by head and re-encoding should be stable and idempotent"
         );
     }
+
+    #[test]
+    fn orphan_vertices_are_emitted_as_standalone_nodes() {
+        let net = build_demo_snapshot(150);
+        let allowed_ids: Vec<u32> = net.hyperedge_ids().into_iter().map(|h| h.raw()).collect();
+        // Tail 12 was removed before as_of; 500 is only known to the ledger
+        let known: BTreeSet<u32> = [10, 11, 12, 99, 500].into_iter().collect();
+        let orphans = orphan_vertices(&net, &allowed_ids, &known);
+        assert_eq!(orphans, vec![12, 500]);
+
+        let with = encode_lpg_json(&net, false, Some(&allowed_ids), &orphans, true);
+        let v: serde_json::Value = serde_json::from_str(&with).unwrap();
+        let ids: Vec<&str> = v["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["id"].as_str().unwrap())
+            .collect();
+        assert!(ids.contains(&"v12") && ids.contains(&"v500"));
+
+        let without = encode_lpg_json(&net, false, Some(&allowed_ids), &[], true);
+        assert!(!without.contains("\"v500\""));
+        assert!(encode_graphml(&net, false, Some(&allowed_ids), &orphans)
+            .contains(r#"<node id="v500"/>"#));
+        assert!(encode_rdf_nquads(&net, Some(&allowed_ids), &orphans).contains("vertex/v500>"));
+    }
+
+    #[test]
+    fn eval_table_aggregates_multiple_datasets() {
+        let root = tempfile::tempdir().unwrap();
+        let mut stats = Vec::new();
+        for (name, rows) in [("alpha", 2), ("beta", 3)] {
+            let dir = root.path().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            let body: String = (0..rows)
+                .map(|i| format!("{{\"t_ns\": {}}}\n", 100 * (i + 1)))
+                .collect();
+            fs::write(dir.join("events.jsonl"), body).unwrap();
+            let mf = DatasetManifest::build_from_dir(&dir, name, "0.1.0", "NDF-H 1.0").unwrap();
+            mf.write_to_path(&dir.join("dataset.yaml")).unwrap();
+
+            let (loaded, _) = load_eval_dataset(dir.to_str().unwrap()).unwrap();
+            stats.push(EvalStats::from_manifest(&loaded));
+        }
+
+        let table = render_eval_table(&stats);
+        assert!(table.contains("alpha v0.1.0"));
+        assert!(table.contains("beta v0.1.0"));
+        let totals = table.lines().last().unwrap();
+        assert!(totals.starts_with("TOTAL"));
+        assert!(totals.split_whitespace().any(|c| c == "5"));
+    }
+
+    #[test]
+    fn license_table_config_extends_builtin_defaults() {
+        let builtin = LicenseTable::builtin();
+        assert!(builtin.permits_derivatives("MIT"));
+        assert!(!builtin.permits_derivatives("Unlicense"));
+        assert!(!builtin.permits_derivatives("CC-BY-ND-4.0"));
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("licenses.yaml");
+        fs::write(&path, "licenses:\n  Unlicense: true\n  MIT: false\n").expect("write");
+        let table = LicenseTable::load_merged(Some(&path)).expect("load licenses.yaml");
+        assert!(table.permits_derivatives("Unlicense"));
+        assert!(table.permits_derivatives(" unlicense "));
+        // Config entries override built-in defaults
+        assert!(!table.permits_derivatives("MIT"));
+        // Untouched defaults survive the merge
+        assert!(table.permits_derivatives("Apache-2.0"));
+    }
+
+    /// In-memory manifest without membership shards -> deterministic demo snapshot
+    /// One-row shard of `table` stored at `<table>.jsonl`
+    fn shard(table: &str, pii_class: Option<&str>) -> ndfh_hdx::ShardMeta {
+        ndfh_hdx::ShardMeta {
+            path: format!("{}.jsonl", table),
+            table: table.into(),
+            checksum: String::new(),
+            time_range: (0, 1),
+            num_rows: 1,
+            pii_class: pii_class.map(Into::into),
+            size_bytes: None,
+            compression: None,
+            schema_version: None,
+        }
+    }
+
+    fn demo_export_manifest() -> DatasetManifest {
+        DatasetManifest {
+            dataset_name: "ctx".into(),
+            ndf_version: "NDF-H 1.0".into(),
+            license: "MIT".into(),
+            schema_versions: BTreeMap::from([("events".to_string(), "1.0".to_string())]),
+            shards: BTreeMap::from([("events".to_string(), shard("events", None))]),
+            ..DatasetManifest::default()
+        }
+    }
+
+    #[test]
+    fn max_edges_truncates_every_format_before_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let cli = Cli::try_parse_from([
+            "ndfh",
+            "export",
+            "--dataset",
+            "unused",
+            "--out",
+            &out.to_string_lossy(),
+            "--purpose",
+            "research",
+            "--format",
+            "lpg-json",
+            "--include-orphans",
+            "--max-edges",
+            "0",
+        ])
+        .unwrap();
+        let Commands::Export(cmd) = cli.command else {
+            panic!("expected export command");
+        };
+
+        let ctx = build_export_context(
+            demo_export_manifest(),
+            &dir.path().join("dataset.yaml"),
+            &cmd,
+        )
+        .unwrap();
+        assert!(ctx.orig_total_hyperedges > 0);
+        assert!(ctx.allowed_ids.is_empty());
+        // The dropped hyperedge's endpoints are now orphans
+        assert!(ctx.orphans.contains(&99));
+        assert!(ctx.orphans.contains(&10));
+
+        let path = write_export_output(&ctx, &cmd).unwrap();
+        let doc: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(doc["edges"].as_array().unwrap().is_empty());
+        assert_eq!(doc["nodes"].as_array().unwrap().len(), ctx.orphans.len());
+    }
+
+    #[test]
+    fn export_context_runs_graphml_step_without_cli() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let cli = Cli::try_parse_from([
+            "ndfh",
+            "export",
+            "--dataset",
+            "unused",
+            "--out",
+            &out.to_string_lossy(),
+            "--purpose",
+            "research",
+        ])
+        .unwrap();
+        let Commands::Export(cmd) = cli.command else {
+            panic!("expected export command");
+        };
+
+        let mf = demo_export_manifest();
+        let ctx = build_export_context(mf, &dir.path().join("dataset.yaml"), &cmd).unwrap();
+        assert_eq!(ctx.decision, Decision::Allow);
+        assert_eq!(ctx.allowed_ids.len(), ctx.orig_total_hyperedges);
+        assert!(!ctx.allowed_ids.is_empty());
+
+        let path = write_export_output(&ctx, &cmd).unwrap();
+        assert_eq!(path, out.join("snapshot.graphml"));
+        let xml = fs::read_to_string(&path).unwrap();
+        assert!(xml.contains(&format!(r#"<node id="h{}"/>"#, ctx.allowed_ids[0])));
+
+        let meta = export_meta_json(&ctx, &cmd);
+        assert_eq!(meta["format"], "lpg-graphml");
+        assert_eq!(meta["snapshot_source"], "demo");
+        assert_eq!(meta["snapshot_params"]["as_of"], 150);
+        assert_eq!(meta["snapshot_params"]["head_v"], 99);
+        assert!(meta["snapshot_params"]["seed"].is_null());
+        assert_eq!(
+            meta["snapshot_params"]["tails"][2],
+            serde_json::json!({"tail_v": 12, "t_start": 90, "t_end": 110})
+        );
+        assert_eq!(meta["policy_decision"], "allow");
+        assert_eq!(
+            meta["metrics"]["hyperedges_exported"],
+            ctx.allowed_ids.len() as u64
+        );
+    }
+
+    #[test]
+    fn snapshot_materialization_latency_is_recorded() {
+        let mut log = ndfh_core::MembershipLog::new();
+        let mut cat = ndfh_core::HyperedgeCatalog::new();
+        for h in 0..2_000u64 {
+            cat.register_head(h, 100_000 + h);
+            for tail in 0..5u64 {
+                log.add(h, h * 10 + tail, 100);
+            }
+        }
+        let (net, latency_ms) =
+            materialize_snapshot(&log, &cat, 150, None, ArityPolicy::Split).expect("snapshot");
+        assert_eq!(net.hyperedge_ids().len(), 2_000);
+        assert!(latency_ms > 0.0);
+    }
+
+    #[test]
+    fn max_arity_splits_drops_or_rejects_wide_hyperedges() {
+        let mut log = ndfh_core::MembershipLog::new();
+        let mut cat = ndfh_core::HyperedgeCatalog::new();
+        cat.register_head(1, 99_999);
+        for tail in 0..1_000u64 {
+            log.add(1, tail, 100);
+        }
+        cat.register_head(2, 99_998);
+        log.add(2, 5_000, 100);
+
+        let (split, _) =
+            materialize_snapshot(&log, &cat, 150, Some(100), ArityPolicy::Split).expect("split");
+        let ids = split.hyperedge_ids();
+        assert_eq!(ids.len(), 11);
+        let mut tails = 0;
+        for id in &ids {
+            let edge = split.get_hyperedge(*id).expect("edge");
+            assert!(edge.sources.len() <= 100);
+            tails += edge.sources.len();
+        }
+        assert_eq!(tails, 1_001);
+        let kept = split
+            .get_hyperedge(ndfh_core::HyperedgeId::from(1))
+            .expect("edge 1");
+        assert_eq!(kept.targets, vec![ndfh_core::NeuronId::from(99_999)]);
+
+        let (dropped, _) =
+            materialize_snapshot(&log, &cat, 150, Some(100), ArityPolicy::Drop).expect("drop");
+        assert_eq!(
+            dropped.hyperedge_ids(),
+            vec![ndfh_core::HyperedgeId::from(2)]
+        );
+
+        let err = materialize_snapshot(&log, &cat, 150, Some(100), ArityPolicy::Error)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("hyperedges [1] exceed --max-arity 100"),
+            "{}",
+            err
+        );
+
+        // OneToMany fans out from its head: the targets are split, the head is kept
+        cat.register_head(3, 99_997);
+        cat.register_kind(3, ndfh_core::HyperedgeType::OneToMany);
+        for tail in 0..250u64 {
+            log.add(3, 10_000 + tail, 100);
+        }
+        let (split, _) =
+            materialize_snapshot(&log, &cat, 150, Some(100), ArityPolicy::Split).expect("split");
+        let fan_out: Vec<_> = split
+            .hyperedge_ids()
+            .into_iter()
+            .filter_map(|id| split.get_hyperedge(id))
+            .filter(|e| e.kind() == ndfh_core::HyperedgeType::OneToMany)
+            .collect();
+        assert_eq!(fan_out.len(), 3);
+        for edge in &fan_out {
+            assert_eq!(edge.sources, vec![ndfh_core::NeuronId::from(99_997)]);
+            assert!(edge.targets.len() <= 100);
+        }
+        assert_eq!(fan_out.iter().map(|e| e.targets.len()).sum::<usize>(), 250);
+
+        let err = materialize_snapshot(&log, &cat, 150, Some(100), ArityPolicy::Error)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("hyperedges [1, 3]"), "{}", err);
+    }
+
+    #[test]
+    fn filter_head_matches_head_of_either_kind() {
+        let mut net = ndfh_core::HypergraphNetwork::new();
+        for (id, sources, targets, kind) in [
+            (
+                1,
+                vec![10, 11],
+                vec![99],
+                ndfh_core::HyperedgeType::ManyToOne,
+            ),
+            (
+                2,
+                vec![99],
+                vec![20, 21],
+                ndfh_core::HyperedgeType::OneToMany,
+            ),
+            (3, vec![30], vec![31], ndfh_core::HyperedgeType::ManyToOne),
+            // 99 is a tail here, not the head
+            (4, vec![40], vec![99], ndfh_core::HyperedgeType::OneToMany),
+        ] {
+            net.add_hyperedge(
+                ndfh_core::Hyperedge::new(
+                    ndfh_core::HyperedgeId::from(id),
+                    ndfh_core::NeuronId::from_slice(&sources),
+                    ndfh_core::NeuronId::from_slice(&targets),
+                    kind,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        }
+        let mut ids = vec![1, 2, 3, 4];
+        retain_head(&net, &mut ids, 99);
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn unrecognized_pii_class_is_reported() {
+        let mut mf = DatasetManifest::default();
+        mf.shards.insert("a".into(), shard("events", Some("low")));
+        assert!(check_pii_classes(&mf).is_ok());
+
+        mf.shards
+            .insert("b".into(), shard("events", Some("medium")));
+        let err = check_pii_classes(&mf).unwrap_err().to_string();
+        assert!(
+            err.contains("shard 'b'") && err.contains("'medium'"),
+            "{}",
+            err
+        );
+        // The typo'd class is still skipped by the max computation
+        assert_eq!(pii_max_class(&mf).as_deref(), Some("low"));
+
+        // Padding and case are normalized the same way as in ndfh-hdx
+        mf.shards.remove("b");
+        mf.shards.insert("c".into(), shard("events", Some(" High")));
+        assert!(check_pii_classes(&mf).is_ok());
+        assert_eq!(pii_max_class(&mf).as_deref(), Some("high"));
+    }
+
+    #[test]
+    fn explain_policy_pinpoints_failed_pii_constraint() {
+        let mut mf = DatasetManifest::default();
+        mf.shards
+            .insert("events".into(), shard("events", Some("high")));
+        let policy: SecurityPolicy = serde_yaml::from_str(
+            r#"
+rules:
+  - id: allow-low-pii-research
+    effect: allow
+    match:
+      action: export
+      subject.roles: [exporter]
+      context.purpose: research
+      resource.pii_max_class: [none, low]
+"#,
+        )
+        .unwrap();
+        let roles = vec!["exporter".to_string()];
+        let explanation = explain_policy(
+            &policy,
+            &mf,
+            &LicenseTable::builtin(),
+            &roles,
+            Some("research"),
+            "export",
+            "lpg-json",
+        );
+
+        assert_eq!(
+            explanation.decision,
+            Decision::Deny("no-allowing-rule-matched".into())
+        );
+        let rule = &explanation.rules[0];
+        assert!(!rule.matched);
+        let failed: Vec<_> = rule.constraints.iter().filter(|c| !c.matched).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].field, "resource.pii_max_class");
+        assert_eq!(failed[0].actual, "high");
+        assert_eq!(rule.constraints.len(), 4);
+        assert!(explanation
+            .render()
+            .contains("[FAIL] resource.pii_max_class"));
+    }
 }
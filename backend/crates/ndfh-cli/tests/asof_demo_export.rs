@@ -0,0 +1,51 @@
+use std::fs;
+
+use tempfile::tempdir;
+
+#[test]
+fn asof_demo_writes_lpg_json_snapshot() {
+    let out = tempdir().expect("tempdir");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "as-of-demo",
+        "--format",
+        "lpg-json",
+        "--out",
+        &out.path().to_string_lossy(),
+    ]);
+    cmd.assert().success();
+
+    let text = fs::read_to_string(out.path().join("snapshot.lpg.json")).expect("read lpg json");
+    let doc: serde_json::Value = serde_json::from_str(&text).expect("valid JSON");
+    assert!(!doc["nodes"].as_array().expect("nodes array").is_empty());
+    assert!(!doc["edges"].as_array().expect("edges array").is_empty());
+}
+
+#[test]
+fn asof_demo_builds_requested_number_of_hyperedges() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "as-of-demo",
+        "--edges",
+        "3",
+        "--tails",
+        "4",
+        "--t-ns",
+        "1000",
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).expect("utf8 stdout");
+
+    assert!(
+        stdout.contains("AS OF t_ns=1000 -> hyperedges: 3"),
+        "{}",
+        stdout
+    );
+    // every hyperedge keeps its 4 tails; the early-leaving tail is gone by t=1000
+    assert_eq!(
+        stdout.matches("sources=4, targets=1").count(),
+        3,
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use tempfile::tempdir;
+
+const EVENTS: &str =
+    "{\"t_ns\": 100, \"v\": 1}\n{\"t_ns\": 110, \"v\": 2}\n{\"t_ns\": 120, \"v\": 3}\n";
+
+#[test]
+fn cat_prints_limited_rows_of_a_shard() {
+    let data = tempdir().expect("tempdir");
+    let manifest = common::write_dataset(data.path(), "cat", &[("events.jsonl", EVENTS)]);
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "cat",
+        "--manifest",
+        &manifest.to_string_lossy(),
+        "--shard",
+        "events",
+        "--limit",
+        "2",
+    ]);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(out).expect("utf8 stdout");
+    let rows: Vec<&str> = stdout.lines().filter(|l| l.starts_with('{')).collect();
+    assert_eq!(rows.len(), 2);
+    assert!(stdout.contains("\"t_ns\": 110"));
+    assert!(!stdout.contains("\"t_ns\": 120"));
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "cat",
+        "--manifest",
+        &manifest.to_string_lossy(),
+        "--shard",
+        "nope",
+    ]);
+    let err = cmd.assert().failure().get_output().stderr.clone();
+    assert!(String::from_utf8_lossy(&err).contains("unknown shard 'nope'"));
+}
@@ -0,0 +1,39 @@
+// Dataset fixtures shared by the CLI integration tests; each test crate uses a subset.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ndfh_hdx::DatasetManifest;
+
+/// Events shard with a single row at t=100
+pub const EVENTS_JSONL: &str = "{\"t_ns\": 100}\n";
+
+/// Memberships of hyperedge 1: tail 12 leaves at t=110, tails 10 and 11 stay open
+pub const MEMBERSHIP_JSONL: &str =
+    "{\"h_id\": 1, \"tail_v\": 12, \"t_start\": 90, \"t_end\": 110}\n\
+     {\"h_id\": 1, \"tail_v\": 10, \"t_start\": 100, \"t_end\": null}\n\
+     {\"h_id\": 1, \"tail_v\": 11, \"t_start\": 120, \"t_end\": null}\n";
+
+/// Head catalog giving hyperedge 1 head 99
+pub const HYPEREDGES_JSONL: &str = "{\"h_id\": 1, \"head_v\": 99}\n";
+
+/// Write each `(file name, rows)` shard into `dir`, creating it if needed
+pub fn write_shards(dir: &Path, shards: &[(&str, &str)]) {
+    fs::create_dir_all(dir).expect("create dataset dir");
+    for (file, rows) in shards {
+        fs::write(dir.join(file), rows).expect("write shard");
+    }
+}
+
+/// Write `shards` plus an MIT-licensed `dataset.yaml` built from them (real blake3
+/// checksums, time ranges and row counts); returns the manifest path
+pub fn write_dataset(dir: &Path, name: &str, shards: &[(&str, &str)]) -> PathBuf {
+    write_shards(dir, shards);
+    let mut mf =
+        DatasetManifest::build_from_dir(dir, name, "0.1.0", "NDF-H 1.0").expect("build manifest");
+    mf.license = "MIT".to_string();
+    let manifest = dir.join("dataset.yaml");
+    mf.write_to_path(&manifest).expect("write manifest");
+    manifest
+}
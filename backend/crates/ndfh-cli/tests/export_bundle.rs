@@ -0,0 +1,113 @@
+mod common;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use tempfile::tempdir;
+
+// Dataset with a LICENSE file so the bundle includes LICENSE.txt
+fn write_dataset(dir: &Path) {
+    fs::write(dir.join("LICENSE"), "MIT License\n").expect("write license");
+    common::write_dataset(dir, "bundle", &[("events.jsonl", common::EVENTS_JSONL)]);
+}
+
+#[test]
+fn export_bundle_checksums_match_written_files() {
+    let data = tempdir().expect("tempdir");
+    write_dataset(data.path());
+    let out = tempdir().expect("tempdir");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "export",
+        "--dataset",
+        &data.path().to_string_lossy(),
+        "--out",
+        &out.path().to_string_lossy(),
+        "--purpose",
+        "research",
+    ]);
+    cmd.assert().success();
+
+    let listing = fs::read_to_string(out.path().join("checksums.txt")).expect("read checksums");
+    let recorded: BTreeMap<&str, &str> = listing
+        .lines()
+        .map(|line| {
+            let (hex, name) = line.split_once("  ").expect("<hash>  <name>");
+            (name, hex)
+        })
+        .collect();
+    assert_eq!(
+        recorded.keys().copied().collect::<Vec<_>>(),
+        vec![
+            "LICENSE.txt",
+            "NOTICE.txt",
+            "export.meta.json",
+            "snapshot.graphml"
+        ]
+    );
+    for (name, hex) in &recorded {
+        let bytes = fs::read(out.path().join(name)).expect("read bundle file");
+        assert_eq!(blake3::hash(&bytes).to_hex().as_str(), *hex, "{}", name);
+    }
+
+    // Tampering with a bundle file is detectable
+    fs::write(out.path().join("NOTICE.txt"), "edited\n").expect("tamper");
+    let bytes = fs::read(out.path().join("NOTICE.txt")).expect("read notice");
+    assert_ne!(
+        blake3::hash(&bytes).to_hex().as_str(),
+        recorded["NOTICE.txt"]
+    );
+}
+
+#[test]
+fn export_with_compress_zstd_packs_bundle_into_tarball() {
+    let data = tempdir().expect("tempdir");
+    write_dataset(data.path());
+    let parent = tempdir().expect("tempdir");
+    let out = parent.path().join("bundle");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "export",
+        "--dataset",
+        &data.path().to_string_lossy(),
+        "--out",
+        &out.to_string_lossy(),
+        "--purpose",
+        "research",
+        "--compress",
+        "zstd",
+        "--basename",
+        "run1",
+    ]);
+    cmd.assert().success();
+
+    // The archive is named after --basename, not the output directory
+    assert!(!parent.path().join("bundle.tar.zst").exists());
+    let archive = parent.path().join("run1.tar.zst");
+    assert!(archive.is_file(), "missing {}", archive.display());
+    let listing = std::process::Command::new("tar")
+        .arg("--zstd")
+        .arg("-tf")
+        .arg(&archive)
+        .output()
+        .expect("run tar");
+    assert!(listing.status.success());
+    let mut entries: Vec<String> = String::from_utf8_lossy(&listing.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            "LICENSE.txt",
+            "NOTICE.txt",
+            "checksums.txt",
+            "export.meta.json",
+            "run1.graphml"
+        ]
+    );
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::path::Path;
+
+use tempfile::tempdir;
+
+fn export(dataset: &Path, out: &Path, extra: &[&str]) {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "export",
+        "--dataset",
+        &dataset.to_string_lossy(),
+        "--as-of",
+        "150",
+        "--out",
+        &out.to_string_lossy(),
+        "--purpose",
+        "research",
+    ]);
+    cmd.args(extra);
+    cmd.assert().success();
+}
+
+#[test]
+fn export_basename_controls_output_file_name() {
+    let data = tempdir().expect("tempdir");
+    common::write_dataset(
+        data.path(),
+        "naming",
+        &[("events.jsonl", common::EVENTS_JSONL)],
+    );
+    let out = tempdir().expect("tempdir");
+
+    export(data.path(), out.path(), &["--basename", "foo"]);
+    assert!(out.path().join("foo.graphml").exists());
+    assert!(!out.path().join("snapshot.graphml").exists());
+
+    export(
+        data.path(),
+        out.path(),
+        &[
+            "--basename",
+            "foo",
+            "--include-timestamp",
+            "--format",
+            "rdf-nquads",
+        ],
+    );
+    assert!(out.path().join("foo_asof150.nq").exists());
+    // The earlier export is left untouched
+    assert!(out.path().join("foo.graphml").exists());
+}
+
+#[test]
+fn export_summary_reports_demo_snapshot() {
+    let data = tempdir().expect("tempdir");
+    common::write_dataset(
+        data.path(),
+        "naming",
+        &[("events.jsonl", common::EVENTS_JSONL)],
+    );
+    let out = data.path().join("out");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "export",
+        "--dataset",
+        &data.path().to_string_lossy(),
+        "--as-of",
+        "150",
+        "--out",
+        &out.to_string_lossy(),
+        "--purpose",
+        "research",
+        "--summary",
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).expect("utf-8 stdout");
+    // Demo snapshot: head 99 with tails 10 and 11 (tail 12 has left)
+    assert!(
+        stdout.contains("Summary: 3 vertices, 1 hyperedges; arity min 2 / mean 2.00 / max 2"),
+        "{}",
+        stdout
+    );
+}
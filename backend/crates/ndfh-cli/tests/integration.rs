@@ -41,4 +41,4 @@ fn test_cli_invalid_command() {
 fn test_cli_experiment_run() {
     // Placeholder: test running an experiment via CLI
     // Would create temp config, run command, check output files
-}
\ No newline at end of file
+}
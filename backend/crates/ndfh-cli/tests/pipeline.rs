@@ -0,0 +1,75 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use tempfile::tempdir;
+
+// Raw shards only: one hyperedge (head 99) whose tail 12 has left by t=150
+fn write_shards(dir: &Path) {
+    common::write_shards(
+        dir,
+        &[
+            (
+                "events.jsonl",
+                "{\"t_ns\": 100, \"v\": 10}\n{\"t_ns\": 130, \"v\": 11}\n",
+            ),
+            ("membership.jsonl", common::MEMBERSHIP_JSONL),
+            ("hyperedges.jsonl", common::HYPEREDGES_JSONL),
+        ],
+    );
+}
+
+fn run(args: &[&str]) {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args(args);
+    cmd.assert().success();
+}
+
+#[test]
+fn convert_verify_export_produces_parseable_graphml() {
+    let data = tempdir().expect("tempdir");
+    write_shards(data.path());
+    let data_dir = data.path().to_string_lossy().to_string();
+    let manifest = data.path().join("dataset.yaml");
+    let manifest = manifest.to_string_lossy().to_string();
+
+    run(&["convert", "--input", &data_dir, "--output", &data_dir]);
+    let mf = ndfh_hdx::DatasetManifest::from_path(&manifest).expect("converted manifest");
+    let tables: Vec<&str> = mf.shards.values().map(|s| s.table.as_str()).collect();
+    assert_eq!(tables, vec!["events", "hyperedges", "membership"]);
+
+    run(&[
+        "verify",
+        "--manifest",
+        &manifest,
+        "--check-checksums",
+        "--check-sorted",
+    ]);
+
+    let out = tempdir().expect("tempdir");
+    run(&[
+        "export",
+        "--dataset",
+        &data_dir,
+        "--as-of",
+        "150",
+        "--out",
+        &out.path().to_string_lossy(),
+        "--purpose",
+        "internal",
+    ]);
+
+    let graphml_path = out.path().join("snapshot.graphml");
+    let text = fs::read_to_string(&graphml_path).expect("read graphml");
+    let doc = roxmltree::Document::parse(&text).expect("well-formed GraphML");
+    let mut nodes: Vec<&str> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("node"))
+        .filter_map(|n| n.attribute("id"))
+        .collect();
+    nodes.sort_unstable();
+    assert_eq!(nodes, vec!["h1", "v10", "v11", "v99"]);
+    let edges = doc.descendants().filter(|n| n.has_tag_name("edge")).count();
+    assert_eq!(edges, 3);
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use ndfh_core::{HyperedgeId, HypergraphNetwork, NeuronId};
+use tempfile::tempdir;
+
+// One hyperedge (head 99) whose tail 12 has left by t=150
+fn write_dataset(dir: &Path) {
+    common::write_dataset(
+        dir,
+        "snap",
+        &[
+            ("hyperedges.jsonl", common::HYPEREDGES_JSONL),
+            ("membership.jsonl", common::MEMBERSHIP_JSONL),
+        ],
+    );
+}
+
+#[test]
+fn snapshot_writes_deserializable_network() {
+    let data = tempdir().expect("tempdir");
+    write_dataset(data.path());
+    let out = data.path().join("s.json");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "snapshot",
+        "--dataset",
+        &data.path().to_string_lossy(),
+        "--as-of",
+        "150",
+        "--out",
+        &out.to_string_lossy(),
+    ]);
+    cmd.assert().success();
+
+    let text = fs::read_to_string(&out).expect("read snapshot");
+    let net: HypergraphNetwork = serde_json::from_str(&text).expect("HypergraphNetwork JSON");
+    let edge = net.get_hyperedge(HyperedgeId::from(1)).expect("edge 1");
+    assert_eq!(edge.sources, vec![NeuronId::from(10), NeuronId::from(11)]);
+    assert_eq!(edge.targets, vec![NeuronId::from(99)]);
+}
+
+#[test]
+fn snapshot_as_of_is_scaled_by_time_unit() {
+    let data = tempdir().expect("tempdir");
+    write_dataset(data.path());
+
+    let snapshot_at = |extra: &[&str]| -> HypergraphNetwork {
+        let out = data.path().join("s.json");
+        let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+        cmd.args([
+            "snapshot",
+            "--dataset",
+            &data.path().to_string_lossy(),
+            "--as-of",
+            "1",
+            "--out",
+            &out.to_string_lossy(),
+        ]);
+        cmd.args(extra);
+        cmd.assert().success();
+        let text = fs::read_to_string(&out).expect("read snapshot");
+        serde_json::from_str(&text).expect("HypergraphNetwork JSON")
+    };
+
+    // 1 ns precedes every membership
+    assert!(snapshot_at(&[]).hyperedge_ids().is_empty());
+
+    // 1 ms = 1_000_000 ns, after tails 10 and 11 joined and tail 12 left
+    let net = snapshot_at(&["--time-unit", "ms"]);
+    let edge = net.get_hyperedge(HyperedgeId::from(1)).expect("edge 1");
+    assert_eq!(edge.sources, vec![NeuronId::from(10), NeuronId::from(11)]);
+}
+
+fn export_with_cache(data: &Path, out: &Path, cache: &Path) -> serde_json::Value {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "export",
+        "--dataset",
+        &data.to_string_lossy(),
+        "--as-of",
+        "150",
+        "--out",
+        &out.to_string_lossy(),
+        "--purpose",
+        "research",
+        "--cache-dir",
+        &cache.to_string_lossy(),
+    ]);
+    cmd.assert().success();
+    let meta = fs::read_to_string(out.join("export.meta.json")).expect("read meta");
+    serde_json::from_str(&meta).expect("meta JSON")
+}
+
+#[test]
+fn repeated_export_reads_snapshot_from_cache() {
+    let data = tempdir().expect("tempdir");
+    write_dataset(data.path());
+    let cache = data.path().join("cache");
+    let out = data.path().join("out");
+
+    let first = export_with_cache(data.path(), &out, &cache);
+    assert_eq!(first["snapshot_cache"], "miss");
+    assert!(first["metrics"]["snapshot_latency_ms"].is_f64());
+    let graphml = fs::read_to_string(out.join("snapshot.graphml")).expect("read graphml");
+    assert_eq!(fs::read_dir(&cache).expect("cache dir").count(), 1);
+
+    let second = export_with_cache(data.path(), &out, &cache);
+    assert_eq!(second["snapshot_cache"], "hit");
+    // Nothing was materialized on a hit
+    assert!(second["metrics"]["snapshot_latency_ms"].is_null());
+    assert_eq!(
+        fs::read_to_string(out.join("snapshot.graphml")).expect("read graphml"),
+        graphml
+    );
+
+    // Editing the manifest changes its content hash, so the old entry is not reused
+    let manifest = data.path().join("dataset.yaml");
+    let edited = fs::read_to_string(&manifest)
+        .expect("read manifest")
+        .replace("0.1.0", "0.2.0");
+    fs::write(&manifest, edited).expect("write manifest");
+    let third = export_with_cache(data.path(), &out, &cache);
+    assert_eq!(third["snapshot_cache"], "miss");
+    assert_eq!(fs::read_dir(&cache).expect("cache dir").count(), 2);
+}
+
+#[test]
+fn export_records_snapshot_materialization_latency() {
+    let data = tempdir().expect("tempdir");
+    write_dataset(data.path());
+    let out = data.path().join("out");
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "export",
+        "--dataset",
+        &data.path().to_string_lossy(),
+        "--as-of",
+        "150",
+        "--out",
+        &out.to_string_lossy(),
+        "--purpose",
+        "research",
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    // The snapshot metrics hook emitted the histogram field
+    let stdout = String::from_utf8_lossy(&output);
+    let event = stdout
+        .lines()
+        .find(|line| line.contains("snapshot built"))
+        .expect("snapshot metrics event");
+    // (field names and values may be wrapped in ANSI styling)
+    assert!(event.contains("histogram.snapshot_latency_ms"), "{}", event);
+    assert!(event.contains("\"storage\""), "{}", event);
+
+    // ... and the same latency is recorded in export.meta.json
+    let meta: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out.join("export.meta.json")).expect("read meta"))
+            .expect("meta JSON");
+    let latency_ms = meta["metrics"]["snapshot_latency_ms"]
+        .as_f64()
+        .expect("snapshot_latency_ms");
+    assert!(latency_ms > 0.0);
+}
+
+#[test]
+fn cache_hit_skips_parsing_shards() {
+    let data = tempdir().expect("tempdir");
+    write_dataset(data.path());
+    let cache = data.path().join("cache");
+    let out = data.path().join("out");
+
+    let first = export_with_cache(data.path(), &out, &cache);
+    assert_eq!(first["snapshot_cache"], "miss");
+
+    // An unreadable membership shard fails the loader, but a hit never opens it
+    let shard = data.path().join("membership.jsonl");
+    fs::remove_file(&shard).expect("remove shard");
+    fs::create_dir(&shard).expect("replace shard with a directory");
+    let second = export_with_cache(data.path(), &out, &cache);
+    assert_eq!(second["snapshot_cache"], "hit");
+}
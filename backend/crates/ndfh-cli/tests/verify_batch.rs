@@ -0,0 +1,81 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use tempfile::tempdir;
+
+// One-shard dataset whose manifest carries the real blake3 checksum of its shard
+fn write_dataset(dir: &Path) {
+    common::write_dataset(
+        dir,
+        "batch",
+        &[("events.jsonl", "{\"t_ns\": 100}\n{\"t_ns\": 200}\n")],
+    );
+}
+
+#[test]
+fn batch_verify_reports_each_failing_manifest() {
+    let root = tempdir().expect("tempdir");
+    write_dataset(&root.path().join("good"));
+    write_dataset(&root.path().join("bad"));
+    // Same size, different bytes: only the checksum comparison catches it
+    fs::write(
+        root.path().join("bad").join("events.jsonl"),
+        "{\"t_ns\": 100}\n{\"t_ns\": 300}\n",
+    )
+    .expect("corrupt shard");
+
+    let pattern = root.path().join("*").join("dataset.yaml");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "verify",
+        "--manifest-glob",
+        &pattern.to_string_lossy(),
+        "--jobs",
+        "2",
+    ]);
+    let out = cmd.assert().failure().get_output().stdout.clone();
+    let stdout = String::from_utf8(out).expect("utf8 stdout");
+
+    assert!(
+        stdout.contains("Verified 2 manifests: 1 passed, 1 failed"),
+        "{}",
+        stdout
+    );
+    let fail = stdout
+        .lines()
+        .find(|l| l.starts_with("FAIL"))
+        .expect("FAIL line");
+    assert!(
+        fail.contains("bad") && fail.contains("checksum mismatches"),
+        "{}",
+        fail
+    );
+    assert!(stdout
+        .lines()
+        .any(|l| l.starts_with("PASS") && l.contains("good")));
+}
+
+#[test]
+fn inspect_explain_names_tables_missing_for_next_level() {
+    let root = tempdir().expect("tempdir");
+    write_dataset(root.path());
+
+    let manifest = root.path().join("dataset.yaml");
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("ndfh-cli");
+    cmd.args([
+        "inspect",
+        "--manifest",
+        &manifest.to_string_lossy(),
+        "--explain",
+    ]);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(out).expect("utf8 stdout");
+
+    assert!(
+        stdout.contains("Detected conformance: Unknown (present: events; missing: labels)"),
+        "{}",
+        stdout
+    );
+}
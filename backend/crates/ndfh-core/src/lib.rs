@@ -2,7 +2,9 @@
 //! NDF-H Core: membership ledger and minimal hypergraph snapshot types.
 
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "concurrent")]
+use shnn_lockfree::{BoundedMPMCQueue, LockFreeError};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Minimal vertex identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -16,6 +18,16 @@ impl NeuronId {
     pub fn raw(&self) -> u32 {
         self.0
     }
+
+    /// Ids in the half-open range `[start, end)`
+    pub fn range(start: u32, end: u32) -> impl Iterator<Item = NeuronId> {
+        (start..end).map(Self)
+    }
+
+    /// Wrap a slice of raw ids
+    pub fn from_slice(raw: &[u32]) -> Vec<NeuronId> {
+        raw.iter().copied().map(Self).collect()
+    }
 }
 
 /// Minimal hyperedge identifier
@@ -30,22 +42,55 @@ impl HyperedgeId {
     pub fn raw(&self) -> u32 {
         self.0
     }
+
+    /// Ids in the half-open range `[start, end)`
+    pub fn range(start: u32, end: u32) -> impl Iterator<Item = HyperedgeId> {
+        (start..end).map(Self)
+    }
+
+    /// Wrap a slice of raw ids
+    pub fn from_slice(raw: &[u32]) -> Vec<HyperedgeId> {
+        raw.iter().copied().map(Self).collect()
+    }
 }
 
-/// Hyperedge arity semantics (kept for compatibility)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Hyperedge arity semantics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum HyperedgeType {
+    /// Member tails are the sources, the head is the single target
+    #[default]
     ManyToOne,
+    /// The head is the single source, member tails are the targets
+    OneToMany,
     // Future variants could be added if needed
 }
 
 /// Minimal hyperedge structure: sources (tails) -> targets (heads)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Hyperedge {
     id: HyperedgeId,
     pub sources: Vec<NeuronId>,
     pub targets: Vec<NeuronId>,
     _kind: HyperedgeType,
+    /// Synaptic weight: positive is excitatory, negative inhibitory (None when unweighted)
+    #[serde(default, deserialize_with = "finite_weight")]
+    weight: Option<f64>,
+}
+
+// `with_weight` and deserialization only admit finite weights, so equality is reflexive
+impl Eq for Hyperedge {}
+
+/// Deserialize a hyperedge weight, rejecting NaN and infinities like `with_weight`
+fn finite_weight<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    let weight = Option::<f64>::deserialize(deserializer)?;
+    match weight {
+        Some(w) if !w.is_finite() => {
+            Err(serde::de::Error::custom("hyperedge weight must be finite"))
+        }
+        _ => Ok(weight),
+    }
 }
 
 impl Hyperedge {
@@ -63,16 +108,126 @@ impl Hyperedge {
             sources,
             targets,
             _kind: kind,
+            weight: None,
         })
     }
 
+    /// Attach a signed synaptic weight; errors if it is NaN or infinite
+    pub fn with_weight(mut self, weight: f64) -> Result<Self, &'static str> {
+        if !weight.is_finite() {
+            return Err("hyperedge weight must be finite");
+        }
+        self.weight = Some(weight);
+        Ok(self)
+    }
+
+    pub fn weight(&self) -> Option<f64> {
+        self.weight
+    }
+
     pub fn id(&self) -> HyperedgeId {
         self.id
     }
+
+    pub fn kind(&self) -> HyperedgeType {
+        self._kind
+    }
+
+    /// The head side: `targets` for ManyToOne, `sources` for OneToMany
+    pub fn heads(&self) -> &[NeuronId] {
+        match self._kind {
+            HyperedgeType::ManyToOne => &self.targets,
+            HyperedgeType::OneToMany => &self.sources,
+        }
+    }
+
+    /// The member tails fanning in to or out of the head; their count is the arity
+    pub fn tails(&self) -> &[NeuronId] {
+        match self._kind {
+            HyperedgeType::ManyToOne => &self.sources,
+            HyperedgeType::OneToMany => &self.targets,
+        }
+    }
+
+    fn tails_mut(&mut self) -> &mut Vec<NeuronId> {
+        match self._kind {
+            HyperedgeType::ManyToOne => &mut self.sources,
+            HyperedgeType::OneToMany => &mut self.targets,
+        }
+    }
+
+    /// Same sorted sources, sorted targets and kind; the id and weight are ignored.
+    pub fn structurally_eq(&self, other: &Hyperedge) -> bool {
+        self._kind == other._kind
+            && sorted_ids(&self.sources) == sorted_ids(&other.sources)
+            && sorted_ids(&self.targets) == sorted_ids(&other.targets)
+    }
+
+    /// Id-free key for deduplicating structurally equal hyperedges.
+    /// Two hyperedges have equal keys exactly when they are `structurally_eq`.
+    pub fn structure_key(&self) -> impl std::hash::Hash + Eq {
+        (
+            sorted_ids(&self.sources),
+            sorted_ids(&self.targets),
+            self._kind,
+        )
+    }
+}
+
+fn sorted_ids(ids: &[NeuronId]) -> Vec<NeuronId> {
+    let mut out = ids.to_vec();
+    out.sort_unstable();
+    out
+}
+
+/// What `HypergraphNetwork::enforce_max_arity` does with an oversized hyperedge
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ArityPolicy {
+    /// Split the tails into chunks of at most `max_arity`, sharing the head and weight;
+    /// the first chunk keeps the original id, the rest get fresh ids
+    #[default]
+    Split,
+    /// Remove the hyperedge
+    Drop,
+    /// Reject the network, leaving it unchanged
+    Error,
+}
+
+/// Outcome of `HypergraphNetwork::enforce_max_arity`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArityReport {
+    /// Hyperedges whose arity exceeded the limit, in ascending id order
+    pub oversized: Vec<HyperedgeId>,
+    /// Fresh ids assigned to split-off chunks (empty unless splitting)
+    pub added: Vec<HyperedgeId>,
+}
+
+/// Which hyperedges `HypergraphNetwork::subgraph` keeps for a vertex set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SubgraphMode {
+    /// Every source and target lies in the set
+    #[default]
+    Induced,
+    /// At least one source or target lies in the set
+    Incident,
+}
+
+/// Size and degree statistics of a `HypergraphNetwork` (see `HypergraphNetwork::summary`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkSummary {
+    /// Distinct vertices appearing as a source or target
+    pub vertices: usize,
+    pub hyperedges: usize,
+    /// Smallest, mean and largest arity (`tails().len()`); all 0 for an empty network
+    pub min_arity: usize,
+    pub mean_arity: f64,
+    pub max_arity: usize,
+    /// Largest number of hyperedges any single vertex belongs to
+    pub max_degree: usize,
 }
 
 /// Minimal in-memory hypergraph network used by exporters and tests
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HypergraphNetwork {
     edges: BTreeMap<HyperedgeId, Hyperedge>,
 }
@@ -99,10 +254,154 @@ impl HypergraphNetwork {
     pub fn hyperedge_ids(&self) -> Vec<HyperedgeId> {
         self.edges.keys().copied().collect()
     }
+
+    /// Hyperedges in which `v` is a source or a target, in ascending id order.
+    /// Linear scan: O(E * k) where k is the mean endpoint count per hyperedge.
+    pub fn hyperedges_of(&self, v: NeuronId) -> Vec<HyperedgeId> {
+        self.edges
+            .values()
+            .filter(|e| e.sources.contains(&v) || e.targets.contains(&v))
+            .map(|e| e.id)
+            .collect()
+    }
+
+    /// All vertices sharing at least one hyperedge with `v` (excluding `v` itself).
+    /// Linear scan: O(E * k) where k is the mean endpoint count per hyperedge.
+    pub fn neighbors(&self, v: NeuronId) -> BTreeSet<NeuronId> {
+        let mut out = BTreeSet::new();
+        for e in self.edges.values() {
+            if e.sources.contains(&v) || e.targets.contains(&v) {
+                out.extend(e.sources.iter().chain(e.targets.iter()).copied());
+            }
+        }
+        out.remove(&v);
+        out
+    }
+
+    /// Sub-hypergraph of the hyperedges selected by `mode` for `vertices`.
+    /// Kept hyperedges are copied whole, with their ids and endpoints unchanged.
+    pub fn subgraph(&self, vertices: &BTreeSet<NeuronId>, mode: SubgraphMode) -> HypergraphNetwork {
+        let edges = self
+            .edges
+            .values()
+            .filter(|e| {
+                let mut endpoints = e.sources.iter().chain(e.targets.iter());
+                match mode {
+                    SubgraphMode::Induced => endpoints.all(|v| vertices.contains(v)),
+                    SubgraphMode::Incident => endpoints.any(|v| vertices.contains(v)),
+                }
+            })
+            .map(|e| (e.id, e.clone()))
+            .collect();
+        HypergraphNetwork { edges }
+    }
+
+    /// Vertex and hyperedge counts with arity and degree statistics
+    pub fn summary(&self) -> NetworkSummary {
+        let mut degrees: BTreeMap<NeuronId, usize> = BTreeMap::new();
+        for e in self.edges.values() {
+            let endpoints: BTreeSet<NeuronId> =
+                e.sources.iter().chain(e.targets.iter()).copied().collect();
+            for v in endpoints {
+                *degrees.entry(v).or_insert(0) += 1;
+            }
+        }
+        let arities: Vec<usize> = self.edges.values().map(|e| e.tails().len()).collect();
+        NetworkSummary {
+            vertices: degrees.len(),
+            hyperedges: arities.len(),
+            min_arity: arities.iter().copied().min().unwrap_or(0),
+            mean_arity: if arities.is_empty() {
+                0.0
+            } else {
+                arities.iter().sum::<usize>() as f64 / arities.len() as f64
+            },
+            max_arity: arities.iter().copied().max().unwrap_or(0),
+            max_degree: degrees.values().copied().max().unwrap_or(0),
+        }
+    }
+
+    /// Hyperedges whose arity (`tails().len()`) exceeds `max_arity`, in ascending id order
+    pub fn oversized_hyperedges(&self, max_arity: usize) -> Vec<HyperedgeId> {
+        self.edges
+            .values()
+            .filter(|e| e.tails().len() > max_arity)
+            .map(|e| e.id)
+            .collect()
+    }
+
+    /// Bring every hyperedge down to at most `max_arity` tails (a limit of 0 is
+    /// treated as 1) according to `policy`. Split-off chunks keep the head and
+    /// are numbered after the largest id in this snapshot or in `catalog`, so they
+    /// never reuse the id of a catalog hyperedge that is not live in the snapshot.
+    pub fn enforce_max_arity(
+        &mut self,
+        max_arity: usize,
+        policy: ArityPolicy,
+        catalog: &HyperedgeCatalog,
+    ) -> Result<ArityReport, &'static str> {
+        let max_arity = max_arity.max(1);
+        let oversized = self.oversized_hyperedges(max_arity);
+        let mut report = ArityReport {
+            oversized,
+            added: Vec::new(),
+        };
+        if report.oversized.is_empty() {
+            return Ok(report);
+        }
+        match policy {
+            ArityPolicy::Error => return Err("hyperedge exceeds max arity"),
+            ArityPolicy::Drop => {
+                for id in &report.oversized {
+                    self.edges.remove(id);
+                }
+            }
+            ArityPolicy::Split => {
+                let max_id = self.edges.keys().next_back().map(|h| h.0 as u64);
+                let mut next_id = max_id.max(catalog.max_h_id()).map_or(0, |h| h + 1);
+                for id in &report.oversized {
+                    let Some(edge) = self.edges.get_mut(id) else {
+                        continue;
+                    };
+                    let rest = edge.tails_mut().split_off(max_arity);
+                    let (heads, kind, weight) = (edge.heads().to_vec(), edge._kind, edge.weight);
+                    for chunk in rest.chunks(max_arity) {
+                        let new_id = u32::try_from(next_id)
+                            .map(HyperedgeId)
+                            .map_err(|_| "hyperedge id space exhausted")?;
+                        next_id += 1;
+                        let (sources, targets) = match kind {
+                            HyperedgeType::ManyToOne => (chunk.to_vec(), heads.clone()),
+                            HyperedgeType::OneToMany => (heads.clone(), chunk.to_vec()),
+                        };
+                        let mut split = Hyperedge::new(new_id, sources, targets, kind)?;
+                        split.weight = weight;
+                        self.edges.insert(new_id, split);
+                        report.added.push(new_id);
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Encode the snapshot in a compact binary form for on-disk caching
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("in-memory hypergraph serialization cannot fail")
+    }
+
+    /// Decode a snapshot previously produced by `to_bincode`
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
 }
 
-/// Valid-time membership row (tail membership into hyperedge h_id)
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// Valid-time membership row (tail membership into hyperedge h_id).
+/// Construct via `new`/`closed` so the `t_start <= t_end` invariant holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct MembershipRow {
     pub h_id: u64,
     pub tail_v: u64,
@@ -110,8 +409,80 @@ pub struct MembershipRow {
     pub t_end: Option<i64>,
 }
 
+impl MembershipRow {
+    /// Open-ended membership starting at t_start
+    pub fn new(h_id: u64, tail_v: u64, t_start: i64) -> Self {
+        Self {
+            h_id,
+            tail_v,
+            t_start,
+            t_end: None,
+        }
+    }
+
+    /// Membership valid over [t_start, t_end); errors if t_end precedes t_start
+    pub fn closed(h_id: u64, tail_v: u64, t_start: i64, t_end: i64) -> Result<Self, &'static str> {
+        if t_end < t_start {
+            return Err("t_end precedes t_start");
+        }
+        Ok(Self {
+            h_id,
+            tail_v,
+            t_start,
+            t_end: Some(t_end),
+        })
+    }
+}
+
+/// Membership change as logged by `mem_add`/`mem_rem`, the input of `MembershipLog::replay`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MembershipEvent {
+    Add {
+        h_id: u64,
+        tail_v: u64,
+        t_start: i64,
+    },
+    Rem {
+        h_id: u64,
+        tail_v: u64,
+        t_end: i64,
+    },
+}
+
+impl MembershipEvent {
+    /// Event time: `t_start` of an add, `t_end` of a remove
+    pub fn time(&self) -> i64 {
+        match *self {
+            MembershipEvent::Add { t_start, .. } => t_start,
+            MembershipEvent::Rem { t_end, .. } => t_end,
+        }
+    }
+}
+
+/// Why `MembershipLog::replay` rejected an event stream; `index` is the
+/// zero-based position of the offending event
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReplayError {
+    #[error("event {index} at t={t} precedes the previous event at t={previous}")]
+    OutOfOrder { index: usize, t: i64, previous: i64 },
+    #[error("event {index} adds tail {tail_v} to hyperedge {h_id}, which is already open")]
+    AlreadyOpen {
+        index: usize,
+        h_id: u64,
+        tail_v: u64,
+    },
+    #[error(
+        "event {index} removes tail {tail_v} from hyperedge {h_id} without an open membership"
+    )]
+    NotOpen {
+        index: usize,
+        h_id: u64,
+        tail_v: u64,
+    },
+}
+
 /// Append-only membership log
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq, Eq)]
 pub struct MembershipLog {
     rows: Vec<MembershipRow>,
 }
@@ -121,14 +492,84 @@ impl MembershipLog {
         Self { rows: Vec::new() }
     }
 
+    /// Build a log directly from rows, e.g. read back from a shard or a test fixture.
+    /// Errors if a row closes before it opens, or if two rows for the same
+    /// (h_id, tail_v) have overlapping `[t_start, t_end)` intervals.
+    pub fn from_rows(rows: Vec<MembershipRow>) -> Result<Self, &'static str> {
+        if rows
+            .iter()
+            .any(|r| r.t_end.map(|e| e < r.t_start).unwrap_or(false))
+        {
+            return Err("t_end precedes t_start");
+        }
+        let mut spans: Vec<(u64, u64, i64, Option<i64>)> = rows
+            .iter()
+            .map(|r| (r.h_id, r.tail_v, r.t_start, r.t_end))
+            .collect();
+        spans.sort_unstable();
+        for w in spans.windows(2) {
+            let (prev, next) = (w[0], w[1]);
+            let same_member = prev.0 == next.0 && prev.1 == next.1;
+            if same_member && prev.3.map(|e| e > next.2).unwrap_or(true) {
+                return Err("overlapping membership intervals");
+            }
+        }
+        Ok(Self { rows })
+    }
+
+    /// Rebuild a log from a chronological stream of add/remove events, one row per
+    /// add closed by its matching remove. Errors on decreasing timestamps, an add
+    /// for an already-open membership, or a remove without an open membership.
+    pub fn replay(events: impl Iterator<Item = MembershipEvent>) -> Result<Self, ReplayError> {
+        let mut log = Self::new();
+        let mut open: HashMap<(u64, u64), usize> = HashMap::new();
+        let mut previous: Option<i64> = None;
+        for (index, event) in events.enumerate() {
+            let t = event.time();
+            if let Some(previous) = previous.filter(|&p| t < p) {
+                return Err(ReplayError::OutOfOrder { index, t, previous });
+            }
+            previous = Some(t);
+            match event {
+                MembershipEvent::Add {
+                    h_id,
+                    tail_v,
+                    t_start,
+                } => {
+                    if open.insert((h_id, tail_v), log.rows.len()).is_some() {
+                        return Err(ReplayError::AlreadyOpen {
+                            index,
+                            h_id,
+                            tail_v,
+                        });
+                    }
+                    log.add(h_id, tail_v, t_start);
+                }
+                MembershipEvent::Rem {
+                    h_id,
+                    tail_v,
+                    t_end,
+                } => {
+                    let row = open.remove(&(h_id, tail_v)).ok_or(ReplayError::NotOpen {
+                        index,
+                        h_id,
+                        tail_v,
+                    })?;
+                    log.rows[row].t_end = Some(t_end);
+                }
+            }
+        }
+        Ok(log)
+    }
+
+    /// Consume the log, returning its rows in log order
+    pub fn into_rows(self) -> Vec<MembershipRow> {
+        self.rows
+    }
+
     /// Append a new membership (open-ended)
     pub fn add(&mut self, h_id: u64, tail_v: u64, t_start: i64) {
-        self.rows.push(MembershipRow {
-            h_id,
-            tail_v,
-            t_start,
-            t_end: None,
-        });
+        self.rows.push(MembershipRow::new(h_id, tail_v, t_start));
     }
 
     /// Close an existing membership by setting its t_end
@@ -145,6 +586,22 @@ impl MembershipLog {
         }
     }
 
+    /// Close every open membership of hyperedge `h_id` at `t_end`, returning how many
+    /// rows were closed. Errors without closing anything if an open row starts after
+    /// `t_end`.
+    pub fn close_all(&mut self, h_id: u64, t_end: i64) -> Result<usize, &'static str> {
+        let open = |r: &MembershipRow| r.h_id == h_id && r.t_end.is_none();
+        if self.rows.iter().any(|r| open(r) && r.t_start > t_end) {
+            return Err("t_end precedes t_start");
+        }
+        let mut closed = 0;
+        for row in self.rows.iter_mut().filter(|r| open(r)) {
+            row.t_end = Some(t_end);
+            closed += 1;
+        }
+        Ok(closed)
+    }
+
     /// Build a snapshot hypergraph "AS OF" event time t_ns (empty baseline).
     /// Prefer `snapshot_as_of_with_catalog` to materialize directed hyperedges.
     pub fn snapshot_as_of(&self, _t_ns: i64) -> HypergraphNetwork {
@@ -168,49 +625,348 @@ impl MembershipLog {
         }
 
         // 2) Materialize hyperedges
-        let mut net = HypergraphNetwork::new();
-        for (h_id_u64, tails) in tails_by_h.into_iter() {
-            if let Some(&head_v_u64) = catalog.head_map.get(&h_id_u64) {
-                let hed_id = HyperedgeId::from(h_id_u64 as u32);
-                let head = NeuronId::from(head_v_u64 as u32);
-                let sources: Vec<NeuronId> = tails
-                    .into_iter()
-                    .map(|v| NeuronId::from(v as u32))
-                    .collect();
-
-                if sources.is_empty() {
-                    continue;
-                }
+        materialize_hyperedges(tails_by_h, catalog)
+    }
 
-                if let Ok(edge) =
-                    Hyperedge::new(hed_id, sources, vec![head], HyperedgeType::ManyToOne)
-                {
-                    let _ = net.add_hyperedge(edge);
-                }
+    /// Build an interval index over all rows for repeated `AS OF` queries.
+    /// The index is a point-in-time copy; rebuild it after appending to the log.
+    pub fn build_index(&self) -> MembershipIndex {
+        MembershipIndex::build(&self.rows)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MembershipRow> {
+        self.rows.iter()
+    }
+
+    /// Rows whose active interval overlaps the window [start, end], i.e.
+    /// `t_start <= end` and `t_end` is open or `>= start`, in log order
+    pub fn iter_in_range(&self, start: i64, end: i64) -> impl Iterator<Item = &MembershipRow> {
+        self.rows
+            .iter()
+            .filter(move |r| r.t_start <= end && r.t_end.map(|e| e >= start).unwrap_or(true))
+    }
+
+    /// Times at which the active set can change: every distinct `t_start` and
+    /// `t_end`, sorted ascending. Snapshots between two consecutive change points
+    /// are identical.
+    pub fn change_points(&self) -> Vec<i64> {
+        self.rows
+            .iter()
+            .flat_map(|r| std::iter::once(r.t_start).chain(r.t_end))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Distinct tail vertices referenced by any row, active or not
+    pub fn tail_vertices(&self) -> BTreeSet<u64> {
+        self.rows.iter().map(|r| r.tail_v).collect()
+    }
+
+    /// Row counts and time span of the log, for diagnostics
+    pub fn stats(&self) -> MembershipStats {
+        let mut stats = MembershipStats {
+            rows: self.rows.len() as u64,
+            ..MembershipStats::default()
+        };
+        for r in &self.rows {
+            if r.t_end.is_some() {
+                stats.closed += 1;
+            } else {
+                stats.open += 1;
             }
+            *stats.rows_per_hyperedge.entry(r.h_id).or_insert(0) += 1;
+            let last = r.t_end.unwrap_or(r.t_start);
+            stats.time_range = Some(match stats.time_range {
+                Some((lo, hi)) => (lo.min(r.t_start), hi.max(last)),
+                None => (r.t_start, last),
+            });
         }
+        stats
+    }
+}
 
-        net
+/// Summary of a `MembershipLog` (see `MembershipLog::stats`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MembershipStats {
+    /// Total rows
+    pub rows: u64,
+    /// Rows without a `t_end`
+    pub open: u64,
+    /// Rows with a `t_end`
+    pub closed: u64,
+    /// Row count per hyperedge id
+    pub rows_per_hyperedge: BTreeMap<u64, u64>,
+    /// Earliest `t_start` and latest `t_start`/`t_end`; `None` for an empty log
+    pub time_range: Option<(i64, i64)>,
+}
+
+impl MembershipStats {
+    /// Number of distinct hyperedges referenced by the log
+    pub fn hyperedges(&self) -> usize {
+        self.rows_per_hyperedge.len()
     }
+}
 
-    pub fn iter(&self) -> impl Iterator<Item = &MembershipRow> {
-        self.rows.iter()
+/// Rows a `ConcurrentMembershipLog::new` log holds between drains
+#[cfg(feature = "concurrent")]
+pub const DEFAULT_CONCURRENT_LOG_CAPACITY: usize = 1 << 16;
+
+/// Append-only membership log shared by multiple ingest threads without a mutex.
+/// Producers queue rows on a bounded lock-free ring; `drain_into` consolidates everything
+/// appended so far into a `MembershipLog` for snapshotting. While the ring is full,
+/// producers spin until a drain frees slots.
+#[cfg(feature = "concurrent")]
+pub struct ConcurrentMembershipLog {
+    queue: BoundedMPMCQueue<MembershipRow>,
+}
+
+#[cfg(feature = "concurrent")]
+impl Default for ConcurrentMembershipLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "concurrent")]
+impl ConcurrentMembershipLog {
+    /// Log holding up to `DEFAULT_CONCURRENT_LOG_CAPACITY` undrained rows
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CONCURRENT_LOG_CAPACITY).expect("power of two capacity")
+    }
+
+    /// Log holding up to `capacity` undrained rows; `capacity` must be a power of two (>= 2)
+    pub fn with_capacity(capacity: usize) -> Result<Self, LockFreeError> {
+        Ok(Self {
+            queue: BoundedMPMCQueue::with_capacity(capacity)?,
+        })
+    }
+
+    /// Append a row; spins while the log is full
+    pub fn append(&self, row: MembershipRow) {
+        while self.queue.push(row).is_err() {
+            std::hint::spin_loop();
+        }
+    }
+
+    /// Append a new membership (open-ended)
+    pub fn add(&self, h_id: u64, tail_v: u64, t_start: i64) {
+        self.append(MembershipRow::new(h_id, tail_v, t_start));
+    }
+
+    /// True when no rows are waiting to be drained
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Move every queued row into a new `MembershipLog`, in queue order (each
+    /// producer's rows keep their relative order). Safe while producers append, the
+    /// ring never frees a slot a producer may still touch; rows appended while
+    /// draining may land in this or the next drain.
+    pub fn drain_into(&self) -> MembershipLog {
+        let mut rows = Vec::new();
+        while let Ok(row) = self.queue.pop() {
+            rows.push(row);
+        }
+        MembershipLog { rows }
+    }
+}
+
+/// Materialize hyperedges from active tails grouped by h_id, oriented by each
+/// hyperedge's catalog kind (tails -> head for ManyToOne, head -> tails for OneToMany).
+/// Hyperedges without a catalog head are dropped.
+pub fn materialize_hyperedges(
+    tails_by_h: HashMap<u64, Vec<u64>>,
+    catalog: &HyperedgeCatalog,
+) -> HypergraphNetwork {
+    let mut net = HypergraphNetwork::new();
+    for (h_id_u64, tails) in tails_by_h.into_iter() {
+        if let Some(&head_v_u64) = catalog.head_map.get(&h_id_u64) {
+            let hed_id = HyperedgeId::from(h_id_u64 as u32);
+            let head = NeuronId::from(head_v_u64 as u32);
+            let members: Vec<NeuronId> = tails
+                .into_iter()
+                .map(|v| NeuronId::from(v as u32))
+                .collect();
+
+            if members.is_empty() {
+                continue;
+            }
+
+            let kind = catalog.kind_of(h_id_u64);
+            let (sources, targets) = match kind {
+                HyperedgeType::ManyToOne => (members, vec![head]),
+                HyperedgeType::OneToMany => (vec![head], members),
+            };
+            if let Ok(edge) = Hyperedge::new(hed_id, sources, targets, kind) {
+                let _ = net.add_hyperedge(edge);
+            }
+        }
+    }
+
+    net
+}
+
+/// Centered interval tree over membership intervals `[t_start, t_end)`.
+///
+/// Built once via `MembershipLog::build_index`; each stabbing query costs
+/// O(log n + active) instead of a full scan of the log.
+#[derive(Debug, Default)]
+pub struct MembershipIndex {
+    /// (h_id, tail_v) per indexed row, in original log order
+    entries: Vec<(u64, u64)>,
+    root: Option<Box<IntervalNode>>,
+}
+
+#[derive(Debug)]
+struct IntervalNode {
+    center: i64,
+    /// Intervals containing `center`, sorted by start ascending: (t_start, entry)
+    by_start: Vec<(i64, usize)>,
+    /// Same intervals sorted by end descending: (t_end, entry)
+    by_end: Vec<(i64, usize)>,
+    left: Option<Box<IntervalNode>>,
+    right: Option<Box<IntervalNode>>,
+}
+
+impl IntervalNode {
+    /// `intervals` holds (t_start, t_end, entry) with t_start < t_end (open end = i64::MAX)
+    fn build(mut intervals: Vec<(i64, i64, usize)>) -> Option<Box<Self>> {
+        if intervals.is_empty() {
+            return None;
+        }
+        // Median start guarantees at least one interval stays at this node
+        intervals.sort_unstable_by_key(|&(s, _, _)| s);
+        let center = intervals[intervals.len() / 2].0;
+
+        let mut here = Vec::new();
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        for iv in intervals {
+            if iv.1 <= center {
+                left.push(iv);
+            } else if iv.0 > center {
+                right.push(iv);
+            } else {
+                here.push(iv);
+            }
+        }
+
+        let by_start: Vec<(i64, usize)> = here.iter().map(|&(s, _, i)| (s, i)).collect();
+        let mut by_end: Vec<(i64, usize)> = here.iter().map(|&(_, e, i)| (e, i)).collect();
+        by_end.sort_unstable_by_key(|&(e, _)| std::cmp::Reverse(e));
+
+        Some(Box::new(Self {
+            center,
+            by_start,
+            by_end,
+            left: Self::build(left),
+            right: Self::build(right),
+        }))
+    }
+
+    fn stab(&self, t: i64, out: &mut Vec<usize>) {
+        if t < self.center {
+            // Every interval here ends after center > t; only the start bound matters
+            out.extend(
+                self.by_start
+                    .iter()
+                    .take_while(|&&(s, _)| s <= t)
+                    .map(|&(_, i)| i),
+            );
+            if let Some(left) = &self.left {
+                left.stab(t, out);
+            }
+        } else {
+            // Every interval here starts at or before center <= t; only the end bound matters
+            out.extend(
+                self.by_end
+                    .iter()
+                    .take_while(|&&(e, _)| e > t)
+                    .map(|&(_, i)| i),
+            );
+            if let Some(right) = &self.right {
+                right.stab(t, out);
+            }
+        }
+    }
+}
+
+impl MembershipIndex {
+    fn build(rows: &[MembershipRow]) -> Self {
+        let entries: Vec<(u64, u64)> = rows.iter().map(|r| (r.h_id, r.tail_v)).collect();
+        // Empty or inverted intervals can never be active; leave them out of the tree
+        let intervals: Vec<(i64, i64, usize)> = rows
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.t_start, r.t_end.unwrap_or(i64::MAX), i))
+            .filter(|&(s, e, _)| s < e)
+            .collect();
+        Self {
+            entries,
+            root: IntervalNode::build(intervals),
+        }
+    }
+
+    /// Number of rows covered by the index
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
     }
+
+    /// (h_id, tail_v) pairs active at t_ns, in original log order
+    pub fn active_at(&self, t_ns: i64) -> Vec<(u64, u64)> {
+        let mut hits = Vec::new();
+        if let Some(root) = &self.root {
+            root.stab(t_ns, &mut hits);
+        }
+        hits.sort_unstable();
+        hits.into_iter().map(|i| self.entries[i]).collect()
+    }
+}
+
+/// Indexed equivalent of `MembershipLog::snapshot_as_of_with_catalog`.
+pub fn snapshot_as_of_indexed(
+    index: &MembershipIndex,
+    catalog: &HyperedgeCatalog,
+    t_ns: i64,
+) -> HypergraphNetwork {
+    let mut tails_by_h: HashMap<u64, Vec<u64>> = HashMap::new();
+    for (h_id, tail_v) in index.active_at(t_ns) {
+        tails_by_h.entry(h_id).or_default().push(tail_v);
+    }
+    materialize_hyperedges(tails_by_h, catalog)
 }
 
 /// Catalog of hyperedges providing head vertex mapping (h_id -> head_v)
+/// and the kind of each hyperedge (ManyToOne unless registered otherwise)
 #[derive(Debug, Default)]
 pub struct HyperedgeCatalog {
     pub(crate) head_map: HashMap<u64, u64>,
+    pub(crate) kind_map: HashMap<u64, HyperedgeType>,
 }
 
 impl HyperedgeCatalog {
     pub fn new() -> Self {
         Self {
             head_map: HashMap::new(),
+            kind_map: HashMap::new(),
         }
     }
 
+    /// Record the kind of a hyperedge; unregistered hyperedges are ManyToOne
+    pub fn register_kind(&mut self, h_id: u64, kind: HyperedgeType) {
+        self.kind_map.insert(h_id, kind);
+    }
+
+    /// Lookup kind
+    pub fn kind_of(&self, h_id: u64) -> HyperedgeType {
+        self.kind_map.get(&h_id).copied().unwrap_or_default()
+    }
+
     /// Register a hyperedge head mapping
     pub fn register_head(&mut self, h_id: u64, head_v: u64) {
         self.head_map.insert(h_id, head_v);
@@ -225,12 +981,243 @@ impl HyperedgeCatalog {
     pub fn head_of(&self, h_id: u64) -> Option<u64> {
         self.head_map.get(&h_id).copied()
     }
+
+    /// Largest h_id with a registered head or kind
+    pub fn max_h_id(&self) -> Option<u64> {
+        self.head_map
+            .keys()
+            .chain(self.kind_map.keys())
+            .copied()
+            .max()
+    }
+
+    /// Distinct head vertices across all registered hyperedges
+    pub fn head_vertices(&self) -> BTreeSet<u64> {
+        self.head_map.values().copied().collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn id_range_and_slice_helpers() {
+        let ids: Vec<u32> = NeuronId::range(0, 3).map(|v| v.raw()).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+        assert_eq!(NeuronId::range(5, 5).count(), 0);
+        assert_eq!(
+            NeuronId::from_slice(&[7, 3]),
+            vec![NeuronId::from(7), NeuronId::from(3)]
+        );
+
+        let hids: Vec<HyperedgeId> = HyperedgeId::range(1, 3).collect();
+        assert_eq!(hids, vec![HyperedgeId::from(1), HyperedgeId::from(2)]);
+        assert_eq!(HyperedgeId::from_slice(&[4]), vec![HyperedgeId::from(4)]);
+    }
+
+    #[test]
+    fn structurally_equal_hyperedges_ignore_id_and_order() {
+        use std::collections::HashSet;
+
+        let a = Hyperedge::new(
+            HyperedgeId::from(1),
+            NeuronId::from_slice(&[10, 11]),
+            NeuronId::from_slice(&[99]),
+            HyperedgeType::ManyToOne,
+        )
+        .unwrap();
+        let b = Hyperedge::new(
+            HyperedgeId::from(2),
+            NeuronId::from_slice(&[11, 10]),
+            NeuronId::from_slice(&[99]),
+            HyperedgeType::ManyToOne,
+        )
+        .unwrap();
+        let c = Hyperedge::new(
+            HyperedgeId::from(3),
+            NeuronId::from_slice(&[10, 12]),
+            NeuronId::from_slice(&[99]),
+            HyperedgeType::ManyToOne,
+        )
+        .unwrap();
+
+        assert!(a.structurally_eq(&b));
+        assert_ne!(a, b);
+        assert!(!a.structurally_eq(&c));
+
+        let keys: HashSet<_> = [&a, &b, &c].iter().map(|e| e.structure_key()).collect();
+        assert_eq!(keys.len(), 2);
+    }
+
+    #[test]
+    fn membership_row_constructors_enforce_ordering() {
+        let open = MembershipRow::new(1, 10, 100);
+        assert_eq!(open.t_end, None);
+        let closed = MembershipRow::closed(1, 10, 100, 150).unwrap();
+        assert_eq!((closed.t_start, closed.t_end), (100, Some(150)));
+        assert!(MembershipRow::closed(1, 10, 100, 100).is_ok());
+        assert!(MembershipRow::closed(1, 10, 100, 99).is_err());
+    }
+
+    #[test]
+    fn stats_count_open_and_closed_rows() {
+        let log = MembershipLog::from_rows(vec![
+            MembershipRow::new(1, 10, 100),
+            MembershipRow::closed(1, 11, 90, 140).unwrap(),
+            MembershipRow::new(2, 10, 120),
+        ])
+        .unwrap();
+
+        let stats = log.stats();
+        assert_eq!(stats.rows, 3);
+        assert_eq!((stats.open, stats.closed), (2, 1));
+        assert_eq!(stats.hyperedges(), 2);
+        assert_eq!(stats.rows_per_hyperedge[&1], 2);
+        assert_eq!(stats.time_range, Some((90, 140)));
+        assert_eq!(MembershipLog::new().stats(), MembershipStats::default());
+    }
+
+    #[test]
+    fn change_points_are_sorted_and_distinct() {
+        let log = MembershipLog::from_rows(vec![
+            MembershipRow::closed(1, 10, 50, 80).unwrap(),
+            MembershipRow::new(1, 11, 20),
+            MembershipRow::closed(2, 10, 20, 50).unwrap(),
+            MembershipRow::closed(2, 11, 30, 80).unwrap(),
+        ])
+        .expect("log");
+        assert_eq!(log.change_points(), vec![20, 30, 50, 80]);
+        assert!(MembershipLog::new().change_points().is_empty());
+    }
+
+    #[test]
+    fn replay_rebuilds_log_from_event_stream() {
+        use MembershipEvent::{Add, Rem};
+        let events = [
+            Add {
+                h_id: 1,
+                tail_v: 12,
+                t_start: 90,
+            },
+            Add {
+                h_id: 1,
+                tail_v: 10,
+                t_start: 100,
+            },
+            Rem {
+                h_id: 1,
+                tail_v: 12,
+                t_end: 110,
+            },
+            Add {
+                h_id: 1,
+                tail_v: 11,
+                t_start: 120,
+            },
+            Add {
+                h_id: 1,
+                tail_v: 12,
+                t_start: 160,
+            },
+        ];
+        let log = MembershipLog::replay(events.into_iter()).unwrap();
+        assert_eq!(
+            log.iter().copied().collect::<Vec<_>>(),
+            vec![
+                MembershipRow::closed(1, 12, 90, 110).unwrap(),
+                MembershipRow::new(1, 10, 100),
+                MembershipRow::new(1, 11, 120),
+                MembershipRow::new(1, 12, 160),
+            ]
+        );
+
+        let mut cat = HyperedgeCatalog::new();
+        cat.register_head(1, 99);
+        let net = log.snapshot_as_of_with_catalog(150, &cat);
+        let mut sources = net
+            .get_hyperedge(HyperedgeId::from(1))
+            .unwrap()
+            .sources
+            .clone();
+        sources.sort_unstable();
+        assert_eq!(sources, NeuronId::from_slice(&[10, 11]));
+
+        let late = [
+            Add {
+                h_id: 1,
+                tail_v: 10,
+                t_start: 100,
+            },
+            Rem {
+                h_id: 1,
+                tail_v: 10,
+                t_end: 50,
+            },
+        ];
+        assert_eq!(
+            MembershipLog::replay(late.into_iter()),
+            Err(ReplayError::OutOfOrder {
+                index: 1,
+                t: 50,
+                previous: 100
+            })
+        );
+        let unopened = [Rem {
+            h_id: 2,
+            tail_v: 10,
+            t_end: 50,
+        }];
+        assert_eq!(
+            MembershipLog::replay(unopened.into_iter()),
+            Err(ReplayError::NotOpen {
+                index: 0,
+                h_id: 2,
+                tail_v: 10
+            })
+        );
+        let twice = [
+            Add {
+                h_id: 1,
+                tail_v: 10,
+                t_start: 100,
+            },
+            Add {
+                h_id: 1,
+                tail_v: 10,
+                t_start: 100,
+            },
+        ];
+        assert!(matches!(
+            MembershipLog::replay(twice.into_iter()),
+            Err(ReplayError::AlreadyOpen { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn iter_in_range_yields_only_overlapping_rows() {
+        let mut log = MembershipLog::new();
+        log.add(1, 10, 50); // closed before the window
+        log.remove(1, 10, 90);
+        log.add(1, 11, 80); // straddles the window start
+        log.remove(1, 11, 120);
+        log.add(1, 12, 110); // inside the window
+        log.remove(1, 12, 140);
+        log.add(1, 13, 190); // open-ended, straddles the window end
+        log.add(1, 14, 250); // starts after the window
+        log.add(1, 15, 60); // closes exactly at the window start
+        log.remove(1, 15, 100);
+
+        let tails: Vec<u64> = log.iter_in_range(100, 200).map(|r| r.tail_v).collect();
+        assert_eq!(tails, vec![11, 12, 13, 15]);
+        assert_eq!(
+            log.iter_in_range(300, 400)
+                .map(|r| r.tail_v)
+                .collect::<Vec<_>>(),
+            vec![13, 14]
+        );
+    }
+
     #[test]
     fn append_only_add_rem() {
         let mut log = MembershipLog::new();
@@ -262,4 +1249,360 @@ mod tests {
         assert_eq!(edge.targets.len(), 1);
         assert!(edge.sources.len() >= 2); // 10 and 11 present
     }
+
+    #[test]
+    fn from_rows_builds_log_with_preset_intervals() {
+        let rows = vec![
+            MembershipRow::closed(1, 10, 100, 200).unwrap(),
+            MembershipRow::new(1, 11, 120),
+            MembershipRow::closed(1, 12, 90, 110).unwrap(),
+            MembershipRow::new(1, 12, 110), // re-joins exactly when the earlier row closes
+        ];
+        let log = MembershipLog::from_rows(rows).unwrap();
+
+        let mut cat = HyperedgeCatalog::new();
+        cat.register_head(1, 99);
+        let net = log.snapshot_as_of_with_catalog(150, &cat);
+        let mut sources = net
+            .get_hyperedge(HyperedgeId::from(1))
+            .unwrap()
+            .sources
+            .clone();
+        sources.sort();
+        assert_eq!(sources, NeuronId::from_slice(&[10, 11, 12]));
+        assert!(log
+            .snapshot_as_of_with_catalog(250, &cat)
+            .get_hyperedge(HyperedgeId::from(1))
+            .is_some());
+
+        let rows = log.into_rows();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].t_end, Some(200));
+
+        let overlapping = vec![
+            MembershipRow::new(1, 10, 100),
+            MembershipRow::closed(1, 10, 150, 160).unwrap(),
+        ];
+        assert!(MembershipLog::from_rows(overlapping).is_err());
+        let mut inverted = MembershipRow::new(1, 10, 100);
+        inverted.t_end = Some(50);
+        assert!(MembershipLog::from_rows(vec![inverted]).is_err());
+    }
+
+    #[test]
+    fn indexed_snapshot_matches_linear_scan() {
+        let mut log = MembershipLog::new();
+        let mut cat = HyperedgeCatalog::new();
+        for h in 0..8u64 {
+            cat.register_head(h, 1000 + h);
+        }
+        // Deterministic pseudo-random intervals, some open-ended, some zero-length
+        let mut x: u64 = 42;
+        for i in 0..200u64 {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let start = ((x >> 33) % 1000) as i64;
+            let h_id = (x >> 20) % 8;
+            log.add(h_id, i, start);
+            match (x >> 10) % 4 {
+                0 => {}
+                1 => log.remove(h_id, i, start),
+                _ => log.remove(h_id, i, start + ((x >> 40) % 300) as i64),
+            }
+        }
+
+        let index = log.build_index();
+        assert_eq!(index.len(), 200);
+        for t in [-1, 0, 1, 137, 500, 999, 1299, 5000] {
+            let linear = log.snapshot_as_of_with_catalog(t, &cat);
+            let indexed = snapshot_as_of_indexed(&index, &cat, t);
+            assert_eq!(linear.hyperedge_ids(), indexed.hyperedge_ids(), "t={t}");
+            for h in linear.hyperedge_ids() {
+                let a = linear.get_hyperedge(h).unwrap();
+                let b = indexed.get_hyperedge(h).unwrap();
+                assert_eq!(a.sources, b.sources, "t={t} h={h:?}");
+                assert_eq!(a.targets, b.targets, "t={t} h={h:?}");
+            }
+        }
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn decoding_rejects_non_finite_weights() {
+        let mut edge = Hyperedge::new(
+            HyperedgeId::from(1),
+            vec![NeuronId::from(10)],
+            vec![NeuronId::from(99)],
+            HyperedgeType::ManyToOne,
+        )
+        .unwrap()
+        .with_weight(0.5)
+        .unwrap();
+        let mut net = HypergraphNetwork::new();
+        net.add_hyperedge(edge.clone()).unwrap();
+        assert_eq!(
+            HypergraphNetwork::from_bincode(&net.to_bincode()).unwrap(),
+            net
+        );
+
+        for weight in [f64::NAN, f64::INFINITY] {
+            edge.weight = Some(weight);
+            let bytes = bincode::serialize(&edge).unwrap();
+            let err = bincode::deserialize::<Hyperedge>(&bytes).unwrap_err();
+            assert!(err.to_string().contains("finite"), "{}", err);
+        }
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_round_trip_is_compact() {
+        let mut net = HypergraphNetwork::new();
+        for h in 0..50u32 {
+            let sources = NeuronId::range(h * 10, h * 10 + 8).collect();
+            let edge = Hyperedge::new(
+                HyperedgeId::from(h),
+                sources,
+                vec![NeuronId::from(10_000 + h)],
+                HyperedgeType::ManyToOne,
+            )
+            .unwrap();
+            net.add_hyperedge(edge).unwrap();
+        }
+
+        let bytes = net.to_bincode();
+        let decoded = HypergraphNetwork::from_bincode(&bytes).unwrap();
+        assert_eq!(decoded, net);
+
+        let json = serde_json::to_vec(&net).unwrap();
+        assert!(
+            bytes.len() < json.len(),
+            "{} >= {}",
+            bytes.len(),
+            json.len()
+        );
+        assert!(HypergraphNetwork::from_bincode(&bytes[..bytes.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn hyperedge_weight_must_be_finite_and_survives_splitting() {
+        let edge = || {
+            Hyperedge::new(
+                HyperedgeId::from(1),
+                NeuronId::from_slice(&[10, 11, 12]),
+                vec![NeuronId::from(99)],
+                HyperedgeType::ManyToOne,
+            )
+            .unwrap()
+        };
+        assert_eq!(edge().weight(), None);
+        assert!(edge().with_weight(f64::NAN).is_err());
+        assert!(edge().with_weight(f64::INFINITY).is_err());
+
+        let mut net = HypergraphNetwork::new();
+        net.add_hyperedge(edge().with_weight(-0.5).unwrap())
+            .unwrap();
+        let report = net
+            .enforce_max_arity(2, ArityPolicy::Split, &HyperedgeCatalog::new())
+            .unwrap();
+        for id in net.hyperedge_ids() {
+            assert_eq!(net.get_hyperedge(id).unwrap().weight(), Some(-0.5));
+        }
+        assert_eq!(report.added.len(), 1);
+    }
+
+    #[test]
+    fn split_ids_skip_catalog_hyperedges_missing_from_the_snapshot() {
+        let mut net = HypergraphNetwork::new();
+        net.add_hyperedge(
+            Hyperedge::new(
+                HyperedgeId::from(1),
+                NeuronId::from_slice(&[10, 11, 12]),
+                vec![NeuronId::from(99)],
+                HyperedgeType::ManyToOne,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        // Hyperedge 5 exists in the catalog but has no live tails at this AS OF
+        let mut catalog = HyperedgeCatalog::new();
+        catalog.register_head(1, 99);
+        catalog.register_head(5, 98);
+        assert_eq!(catalog.max_h_id(), Some(5));
+
+        let report = net
+            .enforce_max_arity(2, ArityPolicy::Split, &catalog)
+            .unwrap();
+        assert_eq!(report.added, HyperedgeId::from_slice(&[6]));
+        assert_eq!(net.hyperedge_ids(), HyperedgeId::from_slice(&[1, 6]));
+    }
+
+    #[test]
+    fn subgraph_keeps_induced_or_incident_hyperedges() {
+        let mut net = HypergraphNetwork::new();
+        for (id, sources, target) in [(1, vec![10, 11], 99), (2, vec![12], 99), (3, vec![20], 21)] {
+            net.add_hyperedge(
+                Hyperedge::new(
+                    HyperedgeId::from(id),
+                    NeuronId::from_slice(&sources),
+                    vec![NeuronId::from(target)],
+                    HyperedgeType::ManyToOne,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        }
+
+        let pair: BTreeSet<NeuronId> = NeuronId::from_slice(&[12, 99]).into_iter().collect();
+        let induced = net.subgraph(&pair, SubgraphMode::Induced);
+        assert_eq!(induced.hyperedge_ids(), HyperedgeId::from_slice(&[2]));
+        assert_eq!(
+            induced.get_hyperedge(HyperedgeId::from(2)),
+            net.get_hyperedge(HyperedgeId::from(2))
+        );
+
+        let incident = net.subgraph(&pair, SubgraphMode::Incident);
+        assert_eq!(incident.hyperedge_ids(), HyperedgeId::from_slice(&[1, 2]));
+        assert!(net
+            .subgraph(&BTreeSet::new(), SubgraphMode::Induced)
+            .hyperedge_ids()
+            .is_empty());
+    }
+
+    #[test]
+    fn summary_reports_arity_and_degree() {
+        let mut net = HypergraphNetwork::new();
+        assert_eq!(net.summary(), NetworkSummary::default());
+        for (id, sources) in [(1, vec![10, 11, 12]), (2, vec![10])] {
+            net.add_hyperedge(
+                Hyperedge::new(
+                    HyperedgeId::from(id),
+                    NeuronId::from_slice(&sources),
+                    vec![NeuronId::from(99)],
+                    HyperedgeType::ManyToOne,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        }
+
+        let summary = net.summary();
+        assert_eq!((summary.vertices, summary.hyperedges), (4, 2));
+        assert_eq!((summary.min_arity, summary.max_arity), (1, 3));
+        assert_eq!(summary.mean_arity, 2.0);
+        assert_eq!(summary.max_degree, 2);
+    }
+
+    #[test]
+    fn neighbors_of_head_include_all_tails() {
+        let mut net = HypergraphNetwork::new();
+        let e1 = Hyperedge::new(
+            HyperedgeId::from(1),
+            vec![NeuronId::from(10), NeuronId::from(11)],
+            vec![NeuronId::from(99)],
+            HyperedgeType::ManyToOne,
+        )
+        .unwrap();
+        let e2 = Hyperedge::new(
+            HyperedgeId::from(2),
+            vec![NeuronId::from(12)],
+            vec![NeuronId::from(99)],
+            HyperedgeType::ManyToOne,
+        )
+        .unwrap();
+        let e3 = Hyperedge::new(
+            HyperedgeId::from(3),
+            vec![NeuronId::from(20)],
+            vec![NeuronId::from(21)],
+            HyperedgeType::ManyToOne,
+        )
+        .unwrap();
+        net.add_hyperedge(e1).unwrap();
+        net.add_hyperedge(e2).unwrap();
+        net.add_hyperedge(e3).unwrap();
+
+        let head = NeuronId::from(99);
+        assert_eq!(
+            net.hyperedges_of(head),
+            vec![HyperedgeId::from(1), HyperedgeId::from(2)]
+        );
+        let expected: BTreeSet<NeuronId> = [10, 11, 12].into_iter().map(NeuronId::from).collect();
+        assert_eq!(net.neighbors(head), expected);
+        assert_eq!(
+            net.neighbors(NeuronId::from(10)),
+            [11, 99].into_iter().map(NeuronId::from).collect()
+        );
+        assert!(net.neighbors(NeuronId::from(1000)).is_empty());
+    }
+
+    #[cfg(feature = "concurrent")]
+    #[test]
+    fn concurrent_log_collects_rows_from_all_producers() {
+        const THREADS: u64 = 8;
+        const ROWS: u64 = 500;
+        let log = ConcurrentMembershipLog::new();
+        std::thread::scope(|scope| {
+            for h_id in 0..THREADS {
+                let log = &log;
+                scope.spawn(move || {
+                    for tail_v in 0..ROWS {
+                        log.add(h_id, tail_v, tail_v as i64);
+                    }
+                });
+            }
+        });
+
+        let drained = log.drain_into();
+        assert!(log.is_empty());
+        let mut rows: Vec<(u64, u64)> = drained.iter().map(|r| (r.h_id, r.tail_v)).collect();
+        assert_eq!(rows.len(), (THREADS * ROWS) as usize);
+        rows.sort_unstable();
+        rows.dedup();
+        assert_eq!(rows.len(), (THREADS * ROWS) as usize);
+
+        // Each producer's rows stay in append order
+        for h_id in 0..THREADS {
+            let tails: Vec<u64> = drained
+                .iter()
+                .filter(|r| r.h_id == h_id)
+                .map(|r| r.tail_v)
+                .collect();
+            assert_eq!(tails, (0..ROWS).collect::<Vec<_>>());
+        }
+        assert_eq!(log.drain_into(), MembershipLog::new());
+    }
+
+    #[cfg(feature = "concurrent")]
+    #[test]
+    fn concurrent_log_drains_while_producers_append() {
+        const THREADS: u64 = 4;
+        const ROWS: u64 = 200;
+        // Far fewer slots than rows, so producers wait on the concurrent drains
+        let log = ConcurrentMembershipLog::with_capacity(8).expect("capacity");
+        assert!(ConcurrentMembershipLog::with_capacity(6).is_err());
+        let mut drained = Vec::new();
+        std::thread::scope(|scope| {
+            for h_id in 0..THREADS {
+                let log = &log;
+                scope.spawn(move || {
+                    for tail_v in 0..ROWS {
+                        log.add(h_id, tail_v, tail_v as i64);
+                    }
+                });
+            }
+            while drained.len() < (THREADS * ROWS) as usize {
+                drained.extend(log.drain_into().iter().copied());
+            }
+        });
+
+        assert!(log.is_empty());
+        for h_id in 0..THREADS {
+            let tails: Vec<u64> = drained
+                .iter()
+                .filter(|r| r.h_id == h_id)
+                .map(|r| r.tail_v)
+                .collect();
+            assert_eq!(tails, (0..ROWS).collect::<Vec<_>>());
+        }
+    }
 }
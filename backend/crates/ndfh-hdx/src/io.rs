@@ -4,25 +4,70 @@
 //! Minimal bootstrap to support:
 //! - Loading membership append-only log (valid-time) from JSONL shards
 //! - Loading hyperedge head catalog (h_id -> head_v) from JSONL shards
+//! - Writing membership and event shards in the same row format the loaders read
 //!
 //! These helpers are intentionally lightweight and schema-tolerant for early fixtures.
 
-use crate::{DatasetManifest, HdxResult};
-use ndfh_core::{HyperedgeCatalog, MembershipLog};
+use crate::{DatasetManifest, HdxError, HdxResult};
+use ndfh_core::{HyperedgeCatalog, HyperedgeType, MembershipLog};
+use rayon::prelude::*;
 use serde_json::Value as JsonValue;
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
-/// Resolve shard file paths for a given logical table name (e.g., "membership", "hyperedges")
-pub fn resolve_table_shards(mf: &DatasetManifest, root: &Path, table: &str) -> Vec<PathBuf> {
+/// Resolve shard file paths for a given logical table name (e.g., "membership", "hyperedges").
+/// Errors if any matching shard path escapes the dataset root.
+pub fn resolve_table_shards(
+    mf: &DatasetManifest,
+    root: &Path,
+    table: &str,
+) -> HdxResult<Vec<PathBuf>> {
     let mut files = Vec::new();
     for shard in mf.shards.values() {
         if shard.table.as_str() == table {
-            files.push(root.join(&shard.path));
+            files.push(shard.resolve_path(root)?);
         }
     }
-    files
+    Ok(files)
+}
+
+/// Read up to `limit` parsed JSON rows (all rows when None) from the shard with the given id.
+/// The shard path is resolved relative to `root`; malformed lines are skipped like the other loaders.
+pub fn read_shard_rows(
+    mf: &DatasetManifest,
+    root: &Path,
+    shard_id: &str,
+    limit: Option<usize>,
+) -> HdxResult<Vec<JsonValue>> {
+    let shard = mf.shards.get(shard_id).ok_or_else(|| {
+        HdxError::Validation(format!(
+            "unknown shard '{}' (known: {})",
+            shard_id,
+            mf.shards.keys().cloned().collect::<Vec<_>>().join(", ")
+        ))
+    })?;
+    if let Some(codec) = &shard.compression {
+        return Err(HdxError::Validation(format!(
+            "shard '{}' is {}-compressed; compressed shards are not readable yet",
+            shard_id, codec
+        )));
+    }
+    let reader = BufReader::new(fs::File::open(shard.resolve_path(root)?)?);
+    let mut rows = Vec::new();
+    for line in reader.lines() {
+        if limit.is_some_and(|n| rows.len() >= n) {
+            break;
+        }
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(v) = serde_json::from_str::<JsonValue>(&line) {
+            rows.push(v);
+        }
+    }
+    Ok(rows)
 }
 
 /// Load MembershipLog from all membership shards in the manifest.
@@ -31,7 +76,18 @@ pub fn load_membership_log_from_manifest(
     mf: &DatasetManifest,
     root: &Path,
 ) -> HdxResult<Option<MembershipLog>> {
-    let mut files = resolve_table_shards(mf, root, "membership");
+    load_membership_log_from_manifest_with_threads(mf, root, 1)
+}
+
+/// Like `load_membership_log_from_manifest`, but parses shards on `threads` worker threads
+/// (0 = rayon default pool, 1 = sequential). Parsed rows are replayed into the log in
+/// shard-id order, so the result is identical to the sequential loader.
+pub fn load_membership_log_from_manifest_with_threads(
+    mf: &DatasetManifest,
+    root: &Path,
+    threads: usize,
+) -> HdxResult<Option<MembershipLog>> {
+    let mut files = resolve_table_shards(mf, root, "membership")?;
     // Fallback: conventional fixture filenames when manifest doesn't list membership shards
     if files.is_empty() {
         let fallback_candidates = [
@@ -47,21 +103,62 @@ pub fn load_membership_log_from_manifest(
     if files.is_empty() {
         return Ok(None);
     }
-    let mut log = MembershipLog::new();
-    for file in files {
-        if !file.exists() {
-            continue;
+    files.retain(|file| file.exists());
+
+    let parsed: Vec<Vec<MembershipRecord>> = if threads == 1 || files.len() < 2 {
+        files
+            .iter()
+            .map(|file| parse_membership_jsonl_file(file))
+            .collect::<HdxResult<_>>()?
+    } else {
+        let parse_all = || {
+            files
+                .par_iter()
+                .map(|file| parse_membership_jsonl_file(file))
+                .collect::<HdxResult<Vec<_>>>()
+        };
+        if threads == 0 {
+            parse_all()?
+        } else {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_err(|e| HdxError::Validation(format!("failed to start shard loader: {}", e)))?
+                .install(parse_all)?
         }
-        load_membership_jsonl_file(&file, &mut log)?;
+    };
+
+    let mut log = MembershipLog::new();
+    for record in parsed.into_iter().flatten() {
+        apply_membership_record(&mut log, record);
     }
     Ok(Some(log))
 }
 
+/// One parsed membership row: (h_id, tail_v, t_start, t_end)
+type MembershipRecord = (u64, u64, i64, Option<i64>);
+
+fn apply_membership_record(log: &mut MembershipLog, (h, t, ts, te): MembershipRecord) {
+    log.add(h, t, ts);
+    if let Some(te) = te {
+        log.remove(h, t, te);
+    }
+}
+
 /// Parse one membership JSONL file and append rows to the log.
 /// Expected fields per line: h_id: u64, tail_v: u64, t_start: i64, t_end: Option<i64>
 fn load_membership_jsonl_file(path: &Path, log: &mut MembershipLog) -> HdxResult<()> {
+    for record in parse_membership_jsonl_file(path)? {
+        apply_membership_record(log, record);
+    }
+    Ok(())
+}
+
+/// Parse one membership JSONL file into rows, in file order.
+fn parse_membership_jsonl_file(path: &Path) -> HdxResult<Vec<MembershipRecord>> {
     let f = fs::File::open(path)?;
     let reader = BufReader::new(f);
+    let mut records = Vec::new();
     for line in reader.lines() {
         let line = line?;
         if line.trim().is_empty() {
@@ -81,19 +178,14 @@ fn load_membership_jsonl_file(path: &Path, log: &mut MembershipLog) -> HdxResult
         };
 
         match (h_id, tail_v, t_start) {
-            (Some(h), Some(t), Some(ts)) => {
-                log.add(h, t, ts);
-                if let Some(te) = t_end {
-                    log.remove(h, t, te);
-                }
-            }
+            (Some(h), Some(t), Some(ts)) => records.push((h, t, ts, t_end)),
             _ => {
                 // skip row; could log a warning in a fuller impl
                 continue;
             }
         }
     }
-    Ok(())
+    Ok(records)
 }
 
 /// Load HyperedgeCatalog from all hyperedges shards in the manifest.
@@ -102,7 +194,7 @@ pub fn load_hyperedge_catalog_from_manifest(
     mf: &DatasetManifest,
     root: &Path,
 ) -> HdxResult<Option<HyperedgeCatalog>> {
-    let mut files = resolve_table_shards(mf, root, "hyperedges");
+    let mut files = resolve_table_shards(mf, root, "hyperedges")?;
     // Fallback: conventional fixture filenames when manifest doesn't list hyperedges shards
     if files.is_empty() {
         let fallback_candidates = [
@@ -129,7 +221,8 @@ pub fn load_hyperedge_catalog_from_manifest(
 }
 
 /// Parse one hyperedges JSONL file and register head mappings into the catalog.
-/// Expected minimal fields per line: h_id: u64, head_v: u64
+/// Expected minimal fields per line: h_id: u64, head_v: u64; an optional `kind`
+/// ("ManyToOne" | "OneToMany", default ManyToOne) is registered as the hyperedge kind.
 fn load_hyperedges_jsonl_file(path: &Path, cat: &mut HyperedgeCatalog) -> HdxResult<()> {
     let f = fs::File::open(path)?;
     let reader = BufReader::new(f);
@@ -148,7 +241,67 @@ fn load_hyperedges_jsonl_file(path: &Path, cat: &mut HyperedgeCatalog) -> HdxRes
 
         if let (Some(h), Some(head)) = (h_id, head_v) {
             cat.register_head(h, head);
+            if let Some(kind) = v.get("kind") {
+                // An unknown kind would silently flip edge direction, so reject it
+                let kind: HyperedgeType = serde_json::from_value(kind.clone()).map_err(|_| {
+                    HdxError::Validation(format!(
+                        "hyperedge {} in {} has unknown kind {}",
+                        h,
+                        path.display(),
+                        kind
+                    ))
+                })?;
+                cat.register_kind(h, kind);
+            }
         }
     }
     Ok(())
 }
+
+/// Stream a membership log to a JSONL shard, one row per line in log order.
+/// Rows carry `h_id`, `tail_v`, `t_start` and `t_end` (null when open), so the
+/// membership loader replays them into an equal log. Parent directories are created.
+/// Returns the number of rows written.
+pub fn write_membership_shard(log: &MembershipLog, path: &Path) -> HdxResult<u64> {
+    let mut w = create_shard_writer(path)?;
+    let mut rows = 0u64;
+    for row in log.iter() {
+        let line = serde_json::json!({
+            "h_id": row.h_id,
+            "tail_v": row.tail_v,
+            "t_start": row.t_start,
+            "t_end": row.t_end,
+        });
+        writeln!(w, "{}", line)?;
+        rows += 1;
+    }
+    w.flush()?;
+    Ok(rows)
+}
+
+/// Stream `(t_ns, v)` spike events to a JSONL shard, one `{"t_ns", "v"}` row per line.
+/// Events are written in the given order; callers should pass them time-sorted so the
+/// shard passes `verify_time_sorted`. Parent directories are created.
+/// Returns the number of rows written.
+pub fn write_events_shard<I>(events: I, path: &Path) -> HdxResult<u64>
+where
+    I: IntoIterator<Item = (i64, u64)>,
+{
+    let mut w = create_shard_writer(path)?;
+    let mut rows = 0u64;
+    for (t_ns, v) in events {
+        writeln!(w, "{}", serde_json::json!({ "t_ns": t_ns, "v": v }))?;
+        rows += 1;
+    }
+    w.flush()?;
+    Ok(rows)
+}
+
+fn create_shard_writer(path: &Path) -> HdxResult<BufWriter<fs::File>> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    Ok(BufWriter::new(fs::File::create(path)?))
+}
@@ -3,11 +3,12 @@
 
 pub mod io;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "schema-validate")]
 use serde_json::Value as JsonValue;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fs,
     path::{Path, PathBuf},
 };
@@ -27,6 +28,33 @@ pub struct PiiPolicy {
     #[serde(default)]
     pub notes: Option<String>,
 }
+
+impl PiiPolicy {
+    /// Whole days elapsed between `reviewed_at` and `now`.
+    /// None when `reviewed_at` is unset or not valid RFC3339.
+    pub fn review_age_days(&self, now: DateTime<Utc>) -> Option<i64> {
+        let reviewed = DateTime::parse_from_rfc3339(self.reviewed_at.as_deref()?.trim()).ok()?;
+        Some((now - reviewed.with_timezone(&Utc)).num_days())
+    }
+
+    /// Error when the last review is older than `max_age_days` as of `now`,
+    /// or when there is no parseable `reviewed_at` to check.
+    pub fn validate_review_fresh(&self, max_age_days: i64, now: DateTime<Utc>) -> HdxResult<()> {
+        let age = self.review_age_days(now).ok_or_else(|| {
+            HdxError::Validation(format!(
+                "pii_policy.reviewed_at {:?} is missing or not RFC3339",
+                self.reviewed_at
+            ))
+        })?;
+        if age > max_age_days {
+            return Err(HdxError::Validation(format!(
+                "pii_policy review is stale: last reviewed {} days ago (max {})",
+                age, max_age_days
+            )));
+        }
+        Ok(())
+    }
+}
 /// Path diff helper (minimal dependency via pathdiff)
 mod pathdiff {
     use std::path::{Component, Path, PathBuf};
@@ -66,6 +94,48 @@ pub enum ConformanceLevel {
     Unknown,
 }
 
+impl ConformanceLevel {
+    /// Tables a dataset needs, on top of the previous level, to reach the level after
+    /// this one; empty at the top level
+    fn tables_for_next_level(self) -> &'static [&'static str] {
+        match self {
+            ConformanceLevel::Unknown => &["events", "labels"],
+            ConformanceLevel::L0 => &["fire", "hyperedges"],
+            ConformanceLevel::L1 => &[],
+        }
+    }
+}
+
+/// Conformance level together with why it was reached, see
+/// `DatasetManifest::detect_conformance_explained`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceReport {
+    pub level: ConformanceLevel,
+    /// Distinct tables referenced by the shards, sorted
+    pub present_tables: Vec<String>,
+    /// Tables still missing for the next level up (empty at L1)
+    pub missing_for_next_level: Vec<String>,
+}
+
+impl std::fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} (present: {}",
+            self.level,
+            if self.present_tables.is_empty() {
+                "none".to_string()
+            } else {
+                self.present_tables.join(", ")
+            }
+        )?;
+        if !self.missing_for_next_level.is_empty() {
+            write!(f, "; missing: {}", self.missing_for_next_level.join(", "))?;
+        }
+        write!(f, ")")
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum HdxError {
     #[error("I/O error: {0}")]
@@ -80,6 +150,31 @@ pub enum HdxError {
 
 pub type HdxResult<T> = Result<T, HdxError>;
 
+/// Recognized shard `pii_class` values, from least to most sensitive
+pub const PII_CLASSES: [&str; 4] = ["none", "low", "moderate", "high"];
+
+/// Schema version recorded by `build_from_dir` for each table it discovers
+pub const DEFAULT_SCHEMA_VERSION: &str = "1.0";
+
+/// Options for `DatasetManifest::build_from_dir_with_options`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BuildOptions {
+    /// Fail with a Validation error when too many lines of a shard are malformed JSON
+    /// (default: lenient, malformed lines are skipped)
+    pub strict: bool,
+    /// Maximum tolerated fraction of malformed lines per shard in strict mode (0.0 = none)
+    pub max_invalid_fraction: f64,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            max_invalid_fraction: 0.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShardMeta {
     pub path: String,
@@ -89,6 +184,61 @@ pub struct ShardMeta {
     pub num_rows: u64,
     #[serde(default)]
     pub pii_class: Option<String>,
+    /// File size on disk in bytes (used to estimate cost and fast-fail verification)
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    /// Compression codec of the shard file: "gzip" | "zstd" (None when uncompressed)
+    #[serde(default)]
+    pub compression: Option<String>,
+    /// Schema version of this shard's rows; overrides the table-level entry in
+    /// `schema_versions` while old and new shards of a table coexist mid-migration
+    #[serde(default)]
+    pub schema_version: Option<String>,
+}
+
+impl ShardMeta {
+    /// Resolve the shard file under the dataset `root`. Rejects absolute or `..`
+    /// paths, and existing files whose canonical path (after symlinks) leaves the root.
+    pub fn resolve_path(&self, root: &Path) -> HdxResult<PathBuf> {
+        if shard_path_escapes(&self.path) {
+            return Err(HdxError::Validation(format!(
+                "shard path '{}' must be relative and stay within the dataset root",
+                self.path
+            )));
+        }
+        let joined = root.join(&self.path);
+        if let (Ok(canon_root), Ok(canon_file)) = (root.canonicalize(), joined.canonicalize()) {
+            if !canon_file.starts_with(&canon_root) {
+                return Err(HdxError::Validation(format!(
+                    "shard path '{}' resolves outside the dataset root",
+                    self.path
+                )));
+            }
+        }
+        Ok(joined)
+    }
+}
+
+/// True when a manifest shard path is absolute or climbs out via `..`
+fn shard_path_escapes(path: &str) -> bool {
+    use std::path::Component;
+    Path::new(path)
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Checksum algorithms understood by `DatasetManifest::verify_checksums`
+const CHECKSUM_ALGOS: &[&str] = &["blake3", "sha256", "xxh3"];
+
+/// Lowercase hex digest of `bytes` under `algo`, or None for an unsupported algo
+fn hex_digest(algo: &str, bytes: &[u8]) -> Option<String> {
+    use sha2::{Digest, Sha256};
+    match algo {
+        "blake3" => Some(blake3::hash(bytes).to_hex().to_string()),
+        "sha256" => Some(format!("{:x}", Sha256::digest(bytes))),
+        "xxh3" => Some(format!("{:016x}", xxhash_rust::xxh3::xxh3_64(bytes))),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -110,6 +260,85 @@ impl DatasetManifest {
         serde_yaml::to_string(self)
     }
 
+    /// Normalized copy of the manifest for stable serialization:
+    /// - trims all strings, keeping the SPDX license id's spelling
+    /// - lowercases PII classes and compression codecs; blank optionals become None
+    /// - sorts and dedups split shard lists (maps are already ordered BTreeMaps)
+    pub fn canonicalize(&self) -> Self {
+        fn norm_opt(v: &Option<String>) -> Option<String> {
+            v.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        }
+        fn norm_opt_lower(v: &Option<String>) -> Option<String> {
+            norm_opt(v).map(|s| s.to_lowercase())
+        }
+
+        let schema_versions = self
+            .schema_versions
+            .iter()
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .collect();
+        let pii_policy = self.pii_policy.as_ref().map(|pp| PiiPolicy {
+            classification: norm_opt_lower(&pp.classification),
+            basis: norm_opt(&pp.basis),
+            reviewer: norm_opt(&pp.reviewer),
+            reviewed_at: norm_opt(&pp.reviewed_at),
+            notes: norm_opt(&pp.notes),
+        });
+        let splits = self
+            .splits
+            .iter()
+            .map(|(name, ids)| {
+                let mut ids: Vec<String> = ids.iter().map(|id| id.trim().to_string()).collect();
+                ids.sort();
+                ids.dedup();
+                (name.trim().to_string(), ids)
+            })
+            .collect();
+        let shards = self
+            .shards
+            .iter()
+            .map(|(id, meta)| {
+                (
+                    id.trim().to_string(),
+                    ShardMeta {
+                        path: meta.path.trim().to_string(),
+                        table: meta.table.trim().to_string(),
+                        checksum: meta.checksum.trim().to_string(),
+                        time_range: meta.time_range,
+                        num_rows: meta.num_rows,
+                        pii_class: norm_opt_lower(&meta.pii_class),
+                        size_bytes: meta.size_bytes,
+                        compression: norm_opt_lower(&meta.compression),
+                        schema_version: norm_opt(&meta.schema_version),
+                    },
+                )
+            })
+            .collect();
+
+        DatasetManifest {
+            dataset_name: self.dataset_name.trim().to_string(),
+            dataset_version: self.dataset_version.trim().to_string(),
+            ndf_version: self.ndf_version.trim().to_string(),
+            schema_versions,
+            license: self.license.trim().to_string(),
+            pii_policy,
+            splits,
+            shards,
+        }
+    }
+
+    /// blake3 hex digest of the canonical YAML; equal for logically-equal manifests.
+    /// SPDX ids match case-insensitively, so the license is case-folded for hashing only.
+    pub fn content_hash(&self) -> HdxResult<String> {
+        let mut canon = self.canonicalize();
+        canon.license = canon.license.to_uppercase();
+        let yaml = canon.to_yaml()?;
+        Ok(blake3::hash(yaml.as_bytes()).to_hex().to_string())
+    }
+
     /// Load and parse a dataset.yaml from a path
     pub fn from_path<P: AsRef<Path>>(path: P) -> HdxResult<Self> {
         let data = fs::read_to_string(path)?;
@@ -139,34 +368,156 @@ impl DatasetManifest {
                 )));
             }
         }
+        // Shard paths must not escape the dataset root
+        for (sid, shard) in &self.shards {
+            if shard_path_escapes(&shard.path) {
+                return Err(HdxError::Validation(format!(
+                    "shard {} path '{}' must be relative and stay within the dataset root",
+                    sid, shard.path
+                )));
+            }
+        }
+        // Every shard must have a schema version, either its own or its table's
+        let undeclared: BTreeSet<&str> = self
+            .shards
+            .keys()
+            .filter(|sid| self.shard_schema_version(sid).is_none())
+            .map(|sid| self.shards[sid.as_str()].table.as_str())
+            .collect();
+        if !undeclared.is_empty() {
+            return Err(HdxError::Validation(format!(
+                "shard tables missing from schema_versions: {}",
+                undeclared.into_iter().collect::<Vec<_>>().join(", ")
+            )));
+        }
+        // Splits may only reference declared shards
+        for (split, ids) in &self.splits {
+            if let Some(id) = ids.iter().find(|id| !self.shards.contains_key(id.as_str())) {
+                return Err(HdxError::Validation(format!(
+                    "split {} references unknown shard {}",
+                    split, id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert a shard after validating it: non-empty id and table, ordered
+    /// time_range, a path that stays within the dataset root and a recognized
+    /// pii_class (blank is treated as unset). The pii_class is stored trimmed and
+    /// lowercased, as `canonicalize` would. Rejects ids that already exist.
+    pub fn add_shard(&mut self, id: String, meta: ShardMeta) -> HdxResult<()> {
+        if id.trim().is_empty() {
+            return Err(HdxError::Validation("shard id must not be empty".into()));
+        }
+        if self.shards.contains_key(&id) {
+            return Err(HdxError::Validation(format!("duplicate shard id {}", id)));
+        }
+        if meta.table.trim().is_empty() {
+            return Err(HdxError::Validation(format!(
+                "shard {} table must not be empty",
+                id
+            )));
+        }
+        if meta.time_range.0 > meta.time_range.1 {
+            return Err(HdxError::Validation(format!(
+                "shard {} has inverted time_range",
+                id
+            )));
+        }
+        if shard_path_escapes(&meta.path) {
+            return Err(HdxError::Validation(format!(
+                "shard {} path '{}' must be relative and stay within the dataset root",
+                id, meta.path
+            )));
+        }
+        let mut meta = meta;
+        meta.pii_class = meta
+            .pii_class
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(str::to_lowercase);
+        if let Some(class) = meta.pii_class.as_deref() {
+            if !PII_CLASSES.contains(&class) {
+                return Err(HdxError::Validation(format!(
+                    "shard {} has unrecognized pii_class '{}' (expected {})",
+                    id,
+                    class,
+                    PII_CLASSES.join("|")
+                )));
+            }
+        }
+        self.shards.insert(id, meta);
         Ok(())
     }
 
+    /// Shards listed in more than one split (train/test leakage), with the
+    /// splits that contain them. Empty when splits are disjoint.
+    pub fn split_overlaps(&self) -> BTreeMap<String, Vec<String>> {
+        let mut owners: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (split, ids) in &self.splits {
+            for id in ids.iter().collect::<BTreeSet<_>>() {
+                owners.entry(id.clone()).or_default().push(split.clone());
+            }
+        }
+        owners.retain(|_, splits| splits.len() > 1);
+        owners
+    }
+
+    /// Declared schema version for a table, if any.
+    pub fn schema_version_for(&self, table: &str) -> Option<&str> {
+        self.schema_versions.get(table).map(String::as_str)
+    }
+
+    /// Effective schema version of a shard: its own `schema_version` when set
+    /// (and non-blank), otherwise the declared version of its table.
+    pub fn shard_schema_version(&self, shard_id: &str) -> Option<&str> {
+        let shard = self.shards.get(shard_id)?;
+        shard
+            .schema_version
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .or_else(|| self.schema_version_for(&shard.table))
+    }
+
     /// Validate the manifest YAML against a JSON Schema file (2020-12 compatible)
     #[cfg(feature = "schema-validate")]
     pub fn validate_against_schema<P1: AsRef<Path>, P2: AsRef<Path>>(
         manifest_path: P1,
         schema_path: P2,
     ) -> HdxResult<()> {
+        let compiled = Self::compile_schema(schema_path)?;
         let manifest_str = fs::read_to_string(&manifest_path)?;
-        let schema_str = fs::read_to_string(&schema_path)?;
-
-        // Parse schema and manifest to serde_json::Value
-        let schema_json: JsonValue = serde_json::from_str(&schema_str)
-            .map_err(|e| HdxError::Validation(format!("Invalid JSON schema: {e}")))?;
         // YAML to JSON value
         let manifest_yaml: serde_yaml::Value = serde_yaml::from_str(&manifest_str)
             .map_err(|e| HdxError::Validation(format!("Invalid YAML manifest: {e}")))?;
         let manifest_json: JsonValue = serde_json::to_value(manifest_yaml)
             .map_err(|e| HdxError::Validation(format!("YAML->JSON conversion failed: {e}")))?;
+        Self::validate_value_against_compiled(&manifest_json, &compiled)
+    }
 
-        // Compile and validate (use crate default draft for compatibility with 0.17)
-        let compiled = jsonschema::JSONSchema::options()
+    /// Read and compile a JSON Schema file once, for reuse across many
+    /// `validate_value_against_compiled` calls
+    #[cfg(feature = "schema-validate")]
+    pub fn compile_schema<P: AsRef<Path>>(schema_path: P) -> HdxResult<jsonschema::JSONSchema> {
+        let schema_str = fs::read_to_string(&schema_path)?;
+        let schema_json: JsonValue = serde_json::from_str(&schema_str)
+            .map_err(|e| HdxError::Validation(format!("Invalid JSON schema: {e}")))?;
+        // Use crate default draft for compatibility with 0.17
+        jsonschema::JSONSchema::options()
             .compile(&schema_json)
-            .map_err(|e| HdxError::Validation(format!("Schema compilation failed: {e}")))?;
+            .map_err(|e| HdxError::Validation(format!("Schema compilation failed: {e}")))
+    }
 
-        let result = compiled.validate(&manifest_json);
-        if let Err(errors) = result {
+    /// Validate an already-parsed manifest (or policy) value against a compiled schema
+    #[cfg(feature = "schema-validate")]
+    pub fn validate_value_against_compiled(
+        manifest: &JsonValue,
+        compiled: &jsonschema::JSONSchema,
+    ) -> HdxResult<()> {
+        if let Err(errors) = compiled.validate(manifest) {
             let mut msgs = Vec::new();
             for err in errors {
                 msgs.push(format!("at {}: {}", err.instance_path, err));
@@ -191,15 +542,26 @@ impl DatasetManifest {
 
     /// Compute a summary string for human inspection
     pub fn summary(&self) -> String {
-        let mut by_table: BTreeMap<&str, (usize, u64)> = BTreeMap::new();
-        for shard in self.shards.values() {
-            let entry = by_table.entry(shard.table.as_str()).or_insert((0, 0));
+        let mut by_table: BTreeMap<&str, (usize, u64, BTreeSet<&str>)> = BTreeMap::new();
+        for (sid, shard) in &self.shards {
+            let entry = by_table
+                .entry(shard.table.as_str())
+                .or_insert((0, 0, BTreeSet::new()));
             entry.0 += 1;
             entry.1 += shard.num_rows;
+            entry.2.extend(self.shard_schema_version(sid));
         }
         let mut parts = Vec::new();
-        for (table, (count, rows)) in by_table {
-            parts.push(format!("{}: {} shards, {} rows", table, count, rows));
+        for (table, (count, rows, versions)) in by_table {
+            let mut line = format!("{}: {} shards, {} rows", table, count, rows);
+            // Only call out tables caught mid-migration
+            if versions.len() > 1 {
+                line.push_str(&format!(
+                    " (schema versions {})",
+                    versions.into_iter().collect::<Vec<_>>().join(", ")
+                ));
+            }
+            parts.push(line);
         }
         format!(
             "{} v{} (NDF {}) — {} shards\n{}",
@@ -212,19 +574,23 @@ impl DatasetManifest {
     }
 
     /// Verify shard checksums relative to a dataset root directory.
+    /// Checksums are `<algo>:<hex>` with algo one of `blake3`, `sha256` or `xxh3`;
+    /// shards with any other algo are reported as mismatches.
     /// Returns a list of mismatched shard IDs (empty means all OK).
     pub fn verify_checksums(&self, root: &Path) -> HdxResult<Vec<String>> {
         let mut mismatches = Vec::new();
         for (shard_id, meta) in &self.shards {
-            let file_path = root.join(&meta.path);
-            // Only support blake3:... prefix for now
+            let file_path = meta.resolve_path(root)?;
             let expected = meta.checksum.trim();
             let (algo, exp_hex) = expected.split_once(':').unwrap_or(("unknown", expected));
-            if algo != "blake3" {
-                // Skip unsupported checksum algorithms gracefully
+            if !CHECKSUM_ALGOS.contains(&algo) {
+                // Report rather than skip, so a typo cannot hide a corrupt shard
+                mismatches.push(format!(
+                    "{} (unsupported checksum algo '{}')",
+                    shard_id, algo
+                ));
                 continue;
             }
-            // Read file and compute blake3
             let Ok(bytes) = fs::read(&file_path) else {
                 mismatches.push(format!(
                     "{} (missing file {})",
@@ -233,28 +599,105 @@ impl DatasetManifest {
                 ));
                 continue;
             };
-            let got = blake3::hash(&bytes).to_hex().to_string();
-            if got != exp_hex {
+            // Fast-fail on size mismatch before hashing
+            if let Some(expected_size) = meta.size_bytes {
+                let got_size = bytes.len() as u64;
+                if got_size != expected_size {
+                    mismatches.push(format!(
+                        "{} (expected {} bytes, got {} bytes)",
+                        shard_id, expected_size, got_size
+                    ));
+                    continue;
+                }
+            }
+            let got = hex_digest(algo, &bytes).expect("algo checked above");
+            if !got.eq_ignore_ascii_case(exp_hex) {
                 mismatches.push(format!(
-                    "{} (expected blake3:{}, got blake3:{})",
-                    shard_id, exp_hex, got
+                    "{} (expected {}:{}, got {}:{})",
+                    shard_id, algo, exp_hex, algo, got
                 ));
             }
         }
         Ok(mismatches)
     }
 
+    /// Verify that event and membership shard rows are time-sorted on disk.
+    /// Event shards ("events", "fire") are checked on `t_ns`, membership shards on
+    /// `t_start`; other tables are ignored. Malformed lines and rows without the
+    /// time field are skipped. Returns the IDs of out-of-order shards.
+    pub fn verify_time_sorted(&self, root: &Path) -> HdxResult<Vec<String>> {
+        let mut unsorted = Vec::new();
+        for (shard_id, meta) in &self.shards {
+            let key = match meta.table.as_str() {
+                "events" | "fire" => "t_ns",
+                "membership" => "t_start",
+                _ => continue,
+            };
+            if !Self::is_time_sorted(&meta.resolve_path(root)?, key)? {
+                unsorted.push(shard_id.clone());
+            }
+        }
+        Ok(unsorted)
+    }
+
+    fn is_time_sorted(path: &Path, key: &str) -> HdxResult<bool> {
+        use std::io::{BufRead, BufReader};
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut prev: Option<i64> = None;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Ok(v) = serde_json::from_str::<serde_json::Value>(&line) else {
+                continue;
+            };
+            let Some(t) = v
+                .get(key)
+                .and_then(|t| t.as_i64().or_else(|| t.as_f64().map(|f| f as i64)))
+            else {
+                continue;
+            };
+            if prev.is_some_and(|p| t < p) {
+                return Ok(false);
+            }
+            prev = Some(t);
+        }
+        Ok(true)
+    }
+
     /// Build a DatasetManifest by scanning an input directory for JSONL shards.
     /// Heuristics:
-    /// - Recognizes tables by filename containing "events", "fire", or "labels"
-    /// - Computes time_range from t_ns fields per line and row counts
+    /// - Recognizes tables by filename containing "events", "fire", "labels",
+    ///   "membership", or "hyperedges"
+    /// - Computes time_range from t_ns fields (t_start for membership) per line and row
+    ///   counts; hyperedges shards get a (0, 0) time_range
     /// - Computes blake3 checksum of each file content
     /// - Uses relative paths (relative to input_dir)
+    ///
+    /// Malformed lines are skipped; use `build_from_dir_with_options` for strict parsing.
     pub fn build_from_dir(
         input_dir: &Path,
         dataset_name: &str,
         dataset_version: &str,
         ndf_version: &str,
+    ) -> HdxResult<Self> {
+        Self::build_from_dir_with_options(
+            input_dir,
+            dataset_name,
+            dataset_version,
+            ndf_version,
+            &BuildOptions::default(),
+        )
+    }
+
+    /// Same as `build_from_dir`, with control over malformed-line handling.
+    pub fn build_from_dir_with_options(
+        input_dir: &Path,
+        dataset_name: &str,
+        dataset_version: &str,
+        ndf_version: &str,
+        opts: &BuildOptions,
     ) -> HdxResult<Self> {
         let mut mf = DatasetManifest {
             dataset_name: dataset_name.to_string(),
@@ -287,19 +730,25 @@ impl DatasetManifest {
                 .and_then(|s| s.to_str())
                 .unwrap_or_default()
                 .to_lowercase();
-            let table = if file_name.contains("events") {
-                "events"
+            let (table, time_key) = if file_name.contains("events") {
+                ("events", Some("t_ns"))
             } else if file_name.contains("fire") {
-                "fire"
+                ("fire", Some("t_ns"))
             } else if file_name.contains("labels") {
-                "labels"
+                ("labels", Some("t_ns"))
+            } else if file_name.contains("membership") {
+                ("membership", Some("t_start"))
+            } else if file_name.contains("hyperedges") {
+                // Head catalog rows carry no time field
+                ("hyperedges", None)
             } else {
                 // Skip unrecognized jsonl
                 continue;
             };
 
-            let (tmin, tmax, rows) = Self::compute_time_range_and_rows(path)?;
+            let (tmin, tmax, rows) = Self::compute_time_range_and_rows(path, time_key, opts)?;
             let checksum = Self::blake3_file(path)?;
+            let size_bytes = fs::metadata(path)?.len();
             // Relative path from input_dir
             let rel_path = pathdiff::diff_paths(path, input_dir)
                 .unwrap_or_else(|| PathBuf::from(file_name.clone()));
@@ -321,17 +770,51 @@ impl DatasetManifest {
                     time_range: (tmin, tmax),
                     num_rows: rows,
                     pii_class: None,
+                    size_bytes: Some(size_bytes),
+                    // Builder only ingests plain .jsonl files
+                    compression: None,
+                    schema_version: None,
                 },
             );
+            mf.schema_versions
+                .entry(table.to_string())
+                .or_insert_with(|| DEFAULT_SCHEMA_VERSION.to_string());
         }
 
         if mf.shards.is_empty() {
-            return Err(HdxError::Validation("no recognizable shards found (expected *events*.jsonl, *fire*.jsonl, or *labels*.jsonl)".into()));
+            return Err(HdxError::Validation("no recognizable shards found (expected *events*.jsonl, *fire*.jsonl, *labels*.jsonl, *membership*.jsonl, or *hyperedges*.jsonl)".into()));
         }
 
         Ok(mf)
     }
 
+    /// Drop shard entries (and their split references) whose files no longer exist under `root`.
+    /// Paths that fail [`ShardMeta::resolve_path`] count as missing, even if the file exists
+    /// outside the root. Returns the pruned shard ids; errors (leaving the manifest untouched)
+    /// if every shard would be removed.
+    pub fn prune_missing(&mut self, root: &Path) -> HdxResult<Vec<String>> {
+        let missing: Vec<String> = self
+            .shards
+            .iter()
+            .filter(|(_, meta)| !meta.resolve_path(root).is_ok_and(|path| path.is_file()))
+            .map(|(id, _)| id.clone())
+            .collect();
+        if !missing.is_empty() && missing.len() == self.shards.len() {
+            return Err(HdxError::Validation(format!(
+                "all {} shards are missing under {}; refusing to leave the manifest empty",
+                missing.len(),
+                root.display()
+            )));
+        }
+        for id in &missing {
+            self.shards.remove(id);
+        }
+        for ids in self.splits.values_mut() {
+            ids.retain(|id| !missing.contains(id));
+        }
+        Ok(missing)
+    }
+
     /// Write manifest YAML to a file path; creates parents as needed.
     pub fn write_to_path(&self, out_path: &Path) -> HdxResult<()> {
         if let Some(parent) = out_path.parent() {
@@ -342,7 +825,13 @@ impl DatasetManifest {
         Ok(())
     }
 
-    fn compute_time_range_and_rows(path: &Path) -> HdxResult<(i64, i64, u64)> {
+    /// Row count and `[min, max]` of `time_key` over a JSONL shard; untimed shards
+    /// (`time_key` of `None`) get a `(0, 0)` time range.
+    fn compute_time_range_and_rows(
+        path: &Path,
+        time_key: Option<&str>,
+        opts: &BuildOptions,
+    ) -> HdxResult<(i64, i64, u64)> {
         use std::io::{BufRead, BufReader};
         let f = fs::File::open(path)?;
         let reader = BufReader::new(f);
@@ -350,19 +839,27 @@ impl DatasetManifest {
         let mut tmin: Option<i64> = None;
         let mut tmax: Option<i64> = None;
         let mut rows: u64 = 0;
+        let mut invalid: u64 = 0;
+        let mut first_invalid_line: Option<usize> = None;
 
-        for line in reader.lines() {
+        for (idx, line) in reader.lines().enumerate() {
             let line = line?;
             if line.trim().is_empty() {
                 continue;
             }
             let v: serde_json::Value = match serde_json::from_str(&line) {
                 Ok(v) => v,
-                Err(_) => continue, // skip malformed lines
+                Err(_) => {
+                    // skip malformed lines (strict mode checks the ratio below)
+                    invalid += 1;
+                    first_invalid_line.get_or_insert(idx + 1);
+                    continue;
+                }
             };
-            let t_ns_opt = v
-                .get("t_ns")
-                .and_then(|t| t.as_i64().or_else(|| t.as_f64().map(|f| f as i64)));
+            let t_ns_opt = time_key.and_then(|key| {
+                v.get(key)
+                    .and_then(|t| t.as_i64().or_else(|| t.as_f64().map(|f| f as i64)))
+            });
             if let Some(t) = t_ns_opt {
                 tmin = Some(tmin.map(|x| x.min(t)).unwrap_or(t));
                 tmax = Some(tmax.map(|x| x.max(t)).unwrap_or(t));
@@ -370,6 +867,25 @@ impl DatasetManifest {
             rows += 1;
         }
 
+        if opts.strict {
+            if let Some(line_no) = first_invalid_line {
+                let fraction = invalid as f64 / (rows + invalid) as f64;
+                if fraction > opts.max_invalid_fraction {
+                    return Err(HdxError::Validation(format!(
+                        "{}: {} of {} lines failed to parse (first at line {}); exceeds max_invalid_fraction {}",
+                        path.display(),
+                        invalid,
+                        rows + invalid,
+                        line_no,
+                        opts.max_invalid_fraction
+                    )));
+                }
+            }
+        }
+
+        if time_key.is_none() {
+            return Ok((0, 0, rows));
+        }
         match (tmin, tmax) {
             (Some(a), Some(b)) => Ok((a, b, rows)),
             _ => Err(HdxError::Validation(format!(
@@ -386,28 +902,652 @@ impl DatasetManifest {
 
     /// Heuristic conformance detection
     pub fn detect_conformance(&self) -> ConformanceLevel {
-        let mut has_events = false;
-        let mut has_labels = false;
-        let mut has_fire = false;
-        let mut has_hyperedges = false;
-
-        for shard in self.shards.values() {
-            match shard.table.as_str() {
-                "events" => has_events = true,
-                "labels" => has_labels = true,
-                "fire" => has_fire = true,
-                "hyperedges" => has_hyperedges = true,
-                _ => {}
+        self.detect_conformance_explained().level
+    }
+
+    /// Heuristic conformance detection plus the tables present and the tables
+    /// still missing to reach the next level
+    pub fn detect_conformance_explained(&self) -> ConformanceReport {
+        let present: BTreeSet<&str> = self.shards.values().map(|s| s.table.as_str()).collect();
+        let has = |tables: &[&str]| tables.iter().all(|t| present.contains(t));
+
+        let mut level = ConformanceLevel::Unknown;
+        if has(level.tables_for_next_level()) {
+            level = ConformanceLevel::L0;
+            if has(level.tables_for_next_level()) {
+                level = ConformanceLevel::L1;
             }
         }
 
-        if has_events && has_labels {
-            if has_fire && has_hyperedges {
-                return ConformanceLevel::L1;
+        ConformanceReport {
+            level,
+            present_tables: present.iter().map(|t| t.to_string()).collect(),
+            missing_for_next_level: level
+                .tables_for_next_level()
+                .iter()
+                .filter(|t| !present.contains(*t))
+                .map(|t| t.to_string())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_from_dir_records_size_bytes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let content = "{\"t_ns\": 100}\n{\"t_ns\": 200}\n";
+        fs::write(dir.path().join("events.jsonl"), content).expect("write shard");
+
+        let mf = DatasetManifest::build_from_dir(dir.path(), "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+        let shard = mf.shards.get("events").expect("events shard");
+        assert_eq!(shard.size_bytes, Some(content.len() as u64));
+        assert_eq!(shard.compression, None);
+        assert!(mf.verify_checksums(dir.path()).expect("verify").is_empty());
+    }
+
+    #[test]
+    fn traversing_shard_paths_are_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("events.jsonl"), "{\"t_ns\": 100}\n").expect("write shard");
+        let mut mf = DatasetManifest::build_from_dir(dir.path(), "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+        assert!(mf.validate_basic().is_ok());
+
+        mf.shards.get_mut("events").unwrap().path = "../../etc/passwd".into();
+        let err = mf.validate_basic().unwrap_err().to_string();
+        assert!(
+            err.contains("shard events path '../../etc/passwd'"),
+            "{}",
+            err
+        );
+        assert!(mf.verify_checksums(dir.path()).is_err());
+        assert!(mf.shards["events"].resolve_path(dir.path()).is_err());
+
+        mf.shards.get_mut("events").unwrap().path = "/etc/passwd".into();
+        assert!(mf.validate_basic().is_err());
+
+        mf.shards.get_mut("events").unwrap().path = "./events.jsonl".into();
+        assert!(mf.validate_basic().is_ok());
+        assert_eq!(
+            mf.shards["events"]
+                .resolve_path(dir.path())
+                .expect("resolve"),
+            dir.path().join("./events.jsonl")
+        );
+    }
+
+    #[cfg(feature = "schema-validate")]
+    #[test]
+    fn compiled_schema_validates_many_manifests() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let schema_path = dir.path().join("schema.json");
+        fs::write(
+            &schema_path,
+            r#"{"type": "object", "required": ["dataset_name", "shards"]}"#,
+        )
+        .expect("write schema");
+
+        let compiled = DatasetManifest::compile_schema(&schema_path).expect("compile");
+        let good = serde_json::json!({"dataset_name": "a", "shards": {}});
+        let bad = serde_json::json!({"dataset_name": "b"});
+        assert!(DatasetManifest::validate_value_against_compiled(&good, &compiled).is_ok());
+        let err = DatasetManifest::validate_value_against_compiled(&bad, &compiled)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("shards"), "{}", err);
+    }
+
+    #[test]
+    fn verify_checksums_fails_fast_on_size_mismatch() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("events.jsonl"), "{\"t_ns\": 100}\n").expect("write shard");
+
+        let mut mf = DatasetManifest::build_from_dir(dir.path(), "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+        mf.shards.get_mut("events").unwrap().size_bytes = Some(1);
+        let mismatches = mf.verify_checksums(dir.path()).expect("verify");
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].contains("expected 1 bytes"));
+    }
+
+    #[test]
+    fn conformance_report_names_missing_tables() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("events.jsonl"), "{\"t_ns\": 100}\n").expect("write shard");
+        let mut mf = DatasetManifest::build_from_dir(dir.path(), "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+
+        let report = mf.detect_conformance_explained();
+        assert_eq!(report.level, ConformanceLevel::Unknown);
+        assert_eq!(report.present_tables, vec!["events"]);
+        assert_eq!(report.missing_for_next_level, vec!["labels"]);
+        assert_eq!(
+            report.to_string(),
+            "Unknown (present: events; missing: labels)"
+        );
+
+        let mut labels = mf.shards["events"].clone();
+        labels.table = "labels".into();
+        mf.shards.insert("labels".into(), labels);
+        let report = mf.detect_conformance_explained();
+        assert_eq!(report.level, ConformanceLevel::L0);
+        assert_eq!(report.missing_for_next_level, vec!["fire", "hyperedges"]);
+    }
+
+    #[test]
+    fn verify_checksums_supports_sha256() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("events.jsonl");
+        fs::write(&path, "{\"t_ns\": 100}\n").expect("write shard");
+
+        let mut mf = DatasetManifest::build_from_dir(dir.path(), "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+        let digest = hex_digest("sha256", &fs::read(&path).unwrap()).unwrap();
+        assert_eq!(digest.len(), 64);
+        mf.shards.get_mut("events").unwrap().checksum = format!("sha256:{}", digest);
+        assert!(mf.verify_checksums(dir.path()).expect("verify").is_empty());
+
+        // Same length, one byte flipped
+        fs::write(&path, "{\"t_ns\": 101}\n").expect("rewrite shard");
+        let mismatches = mf.verify_checksums(dir.path()).expect("verify");
+        assert_eq!(mismatches.len(), 1);
+        assert!(
+            mismatches[0].contains("expected sha256:"),
+            "{}",
+            mismatches[0]
+        );
+    }
+
+    #[test]
+    fn verify_checksums_reports_unknown_algo() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("events.jsonl");
+        fs::write(&path, "{\"t_ns\": 100}\n").expect("write shard");
+
+        let mut mf = DatasetManifest::build_from_dir(dir.path(), "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+        let digest = hex_digest("xxh3", &fs::read(&path).unwrap()).unwrap();
+        mf.shards.get_mut("events").unwrap().checksum = format!("xxh3:{}", digest);
+        assert!(mf.verify_checksums(dir.path()).expect("verify").is_empty());
+
+        mf.shards.get_mut("events").unwrap().checksum = format!("foo:{}", digest);
+        assert_eq!(
+            mf.verify_checksums(dir.path()).expect("verify"),
+            vec!["events (unsupported checksum algo 'foo')".to_string()]
+        );
+    }
+
+    #[test]
+    fn prune_missing_drops_only_absent_shards() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().join("ds");
+        fs::create_dir(&root).expect("create root");
+        fs::write(root.join("events.jsonl"), "{\"t_ns\": 1}\n").expect("write shard");
+        fs::write(root.join("fire.jsonl"), "{\"t_ns\": 2}\n").expect("write shard");
+        let mut mf = DatasetManifest::build_from_dir(&root, "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+        fs::remove_file(root.join("fire.jsonl")).expect("remove shard");
+
+        // A shard that exists, but outside the root, is not present
+        fs::write(dir.path().join("other.jsonl"), "{\"t_ns\": 3}\n").expect("write shard");
+        let mut escaping = mf.shards["events"].clone();
+        escaping.path = "../other.jsonl".to_string();
+        mf.shards.insert("other".to_string(), escaping);
+
+        let pruned = mf.prune_missing(&root).expect("prune");
+        assert_eq!(pruned, vec!["fire".to_string(), "other".to_string()]);
+        assert!(mf.shards.contains_key("events"));
+        assert_eq!(mf.shards.len(), 1);
+
+        fs::remove_file(root.join("events.jsonl")).expect("remove shard");
+        assert!(mf.prune_missing(&root).is_err());
+        assert_eq!(mf.shards.len(), 1);
+    }
+
+    #[test]
+    fn verify_time_sorted_reports_unsorted_shards() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join("events.jsonl"),
+            "{\"t_ns\": 5}\n{\"t_ns\": 3}\n{\"t_ns\": 9}\n",
+        )
+        .expect("write shard");
+        fs::write(
+            dir.path().join("fire.jsonl"),
+            "{\"t_ns\": 1}\nnot json\n{\"t_ns\": 1}\n{\"t_ns\": 4}\n",
+        )
+        .expect("write shard");
+        fs::write(
+            dir.path().join("membership.jsonl"),
+            "{\"h_id\": 0, \"tail_v\": 1, \"t_start\": 20}\n{\"h_id\": 0, \"tail_v\": 2, \"t_start\": 10}\n",
+        )
+        .expect("write shard");
+        let mut mf = DatasetManifest::build_from_dir(dir.path(), "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+        mf.shards.insert(
+            "membership".into(),
+            ShardMeta {
+                path: "membership.jsonl".into(),
+                table: "membership".into(),
+                checksum: String::new(),
+                time_range: (10, 20),
+                num_rows: 2,
+                pii_class: None,
+                size_bytes: None,
+                compression: None,
+                schema_version: None,
+            },
+        );
+
+        let unsorted = mf.verify_time_sorted(dir.path()).expect("verify");
+        assert_eq!(
+            unsorted,
+            vec!["events".to_string(), "membership".to_string()]
+        );
+    }
+
+    #[test]
+    fn hyperedge_kind_survives_shard_round_trip() {
+        use ndfh_core::{HyperedgeId, HyperedgeType, NeuronId};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rows = [
+            serde_json::json!({"h_id": 1, "head_v": 99, "kind": HyperedgeType::OneToMany}),
+            serde_json::json!({"h_id": 2, "head_v": 98}),
+        ];
+        let body: String = rows.iter().map(|r| format!("{}\n", r)).collect();
+        fs::write(dir.path().join("hyperedges.jsonl"), body).expect("write shard");
+        fs::write(
+            dir.path().join("membership.jsonl"),
+            "{\"h_id\": 1, \"tail_v\": 10, \"t_start\": 0, \"t_end\": null}\n\
+             {\"h_id\": 1, \"tail_v\": 11, \"t_start\": 0, \"t_end\": null}\n\
+             {\"h_id\": 2, \"tail_v\": 12, \"t_start\": 0, \"t_end\": null}\n",
+        )
+        .expect("write shard");
+        // No shards listed: the loaders fall back to the conventional file names
+        let mf = DatasetManifest::default();
+
+        let cat = io::load_hyperedge_catalog_from_manifest(&mf, dir.path())
+            .expect("load catalog")
+            .expect("catalog");
+        assert_eq!(cat.kind_of(1), HyperedgeType::OneToMany);
+        assert_eq!(cat.kind_of(2), HyperedgeType::ManyToOne);
+
+        let log = io::load_membership_log_from_manifest(&mf, dir.path())
+            .expect("load log")
+            .expect("log");
+        let net = log.snapshot_as_of_with_catalog(10, &cat);
+        let fan_out = net.get_hyperedge(HyperedgeId::from(1)).expect("edge 1");
+        assert_eq!(fan_out.kind(), HyperedgeType::OneToMany);
+        assert_eq!(fan_out.sources, vec![NeuronId::from(99)]);
+        assert_eq!(fan_out.targets.len(), 2);
+        let fan_in = net.get_hyperedge(HyperedgeId::from(2)).expect("edge 2");
+        assert_eq!(fan_in.kind(), HyperedgeType::ManyToOne);
+        assert_eq!(fan_in.targets, vec![NeuronId::from(98)]);
+
+        fs::write(
+            dir.path().join("hyperedges.jsonl"),
+            "{\"h_id\": 1, \"head_v\": 99, \"kind\": \"Sideways\"}\n",
+        )
+        .expect("write shard");
+        assert!(io::load_hyperedge_catalog_from_manifest(&mf, dir.path()).is_err());
+    }
+
+    #[test]
+    fn add_shard_validates_meta() {
+        let meta = ShardMeta {
+            path: "events.jsonl".into(),
+            table: "events".into(),
+            checksum: String::new(),
+            time_range: (100, 200),
+            num_rows: 2,
+            pii_class: Some("low".into()),
+            size_bytes: None,
+            compression: None,
+            schema_version: None,
+        };
+        let mut mf = DatasetManifest::default();
+
+        let inverted = ShardMeta {
+            time_range: (200, 100),
+            ..meta.clone()
+        };
+        let err = mf.add_shard("events".into(), inverted).unwrap_err();
+        assert!(err.to_string().contains("inverted time_range"), "{}", err);
+        assert!(mf.shards.is_empty());
+
+        mf.add_shard("events".into(), meta.clone())
+            .expect("valid shard");
+        assert_eq!(mf.shards["events"].num_rows, 2);
+
+        let dup = mf.add_shard("events".into(), meta.clone()).unwrap_err();
+        assert!(dup.to_string().contains("duplicate shard id"), "{}", dup);
+        let no_table = ShardMeta {
+            table: " ".into(),
+            ..meta.clone()
+        };
+        assert!(mf.add_shard("other".into(), no_table).is_err());
+        let bad_pii = ShardMeta {
+            pii_class: Some("secret".into()),
+            ..meta.clone()
+        };
+        assert!(mf.add_shard("other".into(), bad_pii).is_err());
+        assert_eq!(mf.shards.len(), 1);
+
+        let padded_pii = ShardMeta {
+            pii_class: Some(" LOW".into()),
+            ..meta
+        };
+        mf.add_shard("labels".into(), padded_pii)
+            .expect("padded pii_class");
+        assert_eq!(mf.shards["labels"].pii_class.as_deref(), Some("low"));
+    }
+
+    #[test]
+    fn stale_pii_review_is_rejected() {
+        let now = DateTime::parse_from_rfc3339("2025-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut pp = PiiPolicy {
+            reviewed_at: Some("2024-06-01T00:00:00Z".into()),
+            ..PiiPolicy::default()
+        };
+        assert_eq!(pp.review_age_days(now), Some(365));
+        let err = pp.validate_review_fresh(90, now).unwrap_err().to_string();
+        assert!(
+            err.contains("stale: last reviewed 365 days ago (max 90)"),
+            "{}",
+            err
+        );
+
+        pp.reviewed_at = Some("2025-04-15T12:00:00+02:00".into());
+        assert!(pp.validate_review_fresh(90, now).is_ok());
+
+        pp.reviewed_at = Some("last spring".into());
+        assert_eq!(pp.review_age_days(now), None);
+        assert!(pp.validate_review_fresh(90, now).is_err());
+    }
+
+    #[test]
+    fn written_shards_reload_into_equal_structures() {
+        use ndfh_core::{MembershipLog, MembershipRow};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().join("nested").join("ds");
+        let log = MembershipLog::from_rows(vec![
+            MembershipRow::closed(2, 10, 90, 110).unwrap(),
+            MembershipRow::closed(1, 10, 100, 150).unwrap(),
+            MembershipRow::new(2, 10, 110),
+            MembershipRow::new(1, 11, 120),
+        ])
+        .expect("valid rows");
+
+        let written = io::write_membership_shard(&log, &root.join("membership.jsonl"))
+            .expect("write membership");
+        assert_eq!(written, 4);
+        let reloaded = io::load_membership_log_from_manifest(&DatasetManifest::default(), &root)
+            .expect("load log")
+            .expect("log");
+        assert_eq!(reloaded, log);
+
+        let events = [(100, 1), (110, 2), (120, 1)];
+        io::write_events_shard(events, &root.join("events.jsonl")).expect("write events");
+        let mf = DatasetManifest::build_from_dir(&root, "ds", "0.1.0", "NDF-H 1.0")
+            .expect("build manifest");
+        let shard = &mf.shards["events"];
+        assert_eq!(shard.num_rows, 3);
+        assert_eq!(shard.time_range, (100, 120));
+        assert_eq!(mf.shards["membership"].time_range, (90, 120));
+        assert!(mf.verify_time_sorted(&root).expect("verify").is_empty());
+    }
+
+    #[test]
+    fn parallel_membership_load_matches_sequential() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut mf = DatasetManifest::default();
+        for shard in 0..6u64 {
+            let mut body = String::new();
+            for i in 0..50u64 {
+                let h = (shard * 7 + i) % 5;
+                let t_end = if i % 3 == 0 {
+                    format!("{}", 100 + i * 10 + shard)
+                } else {
+                    "null".to_string()
+                };
+                body.push_str(&format!(
+                    "{{\"h_id\": {}, \"tail_v\": {}, \"t_start\": {}, \"t_end\": {}}}\n",
+                    h,
+                    i % 11,
+                    i * 10 + shard,
+                    t_end
+                ));
             }
-            return ConformanceLevel::L0;
+            let name = format!("membership_{}.jsonl", shard);
+            fs::write(dir.path().join(&name), body).expect("write shard");
+            mf.shards.insert(
+                format!("membership_{}", shard),
+                ShardMeta {
+                    path: name,
+                    table: "membership".into(),
+                    checksum: String::new(),
+                    time_range: (0, 0),
+                    num_rows: 50,
+                    pii_class: None,
+                    size_bytes: None,
+                    compression: None,
+                    schema_version: None,
+                },
+            );
         }
+        let mut cat = ndfh_core::HyperedgeCatalog::new();
+        cat.extend_heads((0..5u64).map(|h| (h, 1000 + h)));
+
+        let seq = io::load_membership_log_from_manifest(&mf, dir.path())
+            .expect("sequential load")
+            .expect("log");
+        let par = io::load_membership_log_from_manifest_with_threads(&mf, dir.path(), 4)
+            .expect("parallel load")
+            .expect("log");
+        let rows = |log: &ndfh_core::MembershipLog| -> Vec<(u64, u64, i64, Option<i64>)> {
+            log.iter()
+                .map(|r| (r.h_id, r.tail_v, r.t_start, r.t_end))
+                .collect()
+        };
+        assert_eq!(rows(&seq), rows(&par));
+
+        let as_of = 300;
+        let a = seq.snapshot_as_of_with_catalog(as_of, &cat);
+        let b = par.snapshot_as_of_with_catalog(as_of, &cat);
+        assert_eq!(a.hyperedge_ids(), b.hyperedge_ids());
+        for h in a.hyperedge_ids() {
+            let (ea, eb) = (a.get_hyperedge(h).unwrap(), b.get_hyperedge(h).unwrap());
+            assert_eq!(ea.sources, eb.sources);
+            assert_eq!(ea.targets, eb.targets);
+        }
+    }
+
+    #[test]
+    fn strict_build_rejects_mostly_invalid_shard() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join("events.jsonl"),
+            "{\"t_ns\": 100}\nnot json\n{broken\n???\n",
+        )
+        .expect("write shard");
+
+        // Lenient (default) keeps the valid row only
+        let mf = DatasetManifest::build_from_dir(dir.path(), "ds", "0.1.0", "NDF-H 1.0")
+            .expect("lenient build");
+        assert_eq!(mf.shards["events"].num_rows, 1);
+
+        let opts = BuildOptions {
+            strict: true,
+            max_invalid_fraction: 0.5,
+        };
+        let err = DatasetManifest::build_from_dir_with_options(
+            dir.path(),
+            "ds",
+            "0.1.0",
+            "NDF-H 1.0",
+            &opts,
+        )
+        .expect_err("strict build should fail");
+        let msg = err.to_string();
+        assert!(msg.contains("events.jsonl"), "{msg}");
+        assert!(msg.contains("first at line 2"), "{msg}");
+
+        let tolerant = BuildOptions {
+            strict: true,
+            max_invalid_fraction: 0.8,
+        };
+        assert!(DatasetManifest::build_from_dir_with_options(
+            dir.path(),
+            "ds",
+            "0.1.0",
+            "NDF-H 1.0",
+            &tolerant
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn validate_basic_requires_schema_version_per_table() {
+        let mut mf: DatasetManifest = serde_yaml::from_str(
+            r#"
+dataset_name: demo
+dataset_version: "0.1.0"
+ndf_version: NDF-H 1.0
+schema_versions: { events: "1.0" }
+license: MIT
+shards:
+  s1: { path: events.jsonl, table: events, checksum: "blake3:00", time_range: [0, 10], num_rows: 2 }
+  s2: { path: labels.jsonl, table: labels, checksum: "blake3:11", time_range: [0, 10], num_rows: 1 }
+"#,
+        )
+        .unwrap();
+        assert_eq!(mf.schema_version_for("events"), Some("1.0"));
+        assert_eq!(mf.schema_version_for("labels"), None);
+        let err = mf.validate_basic().unwrap_err().to_string();
+        assert!(
+            err.contains("missing from schema_versions: labels"),
+            "{}",
+            err
+        );
+
+        mf.schema_versions.insert("labels".into(), "1.0".into());
+        assert!(mf.validate_basic().is_ok());
+    }
+
+    #[test]
+    fn shard_schema_version_overrides_table_version() {
+        let mf: DatasetManifest = serde_yaml::from_str(
+            r#"
+dataset_name: demo
+dataset_version: "0.1.0"
+ndf_version: NDF-H 1.0
+schema_versions: { events: "1.0" }
+license: MIT
+shards:
+  old: { path: events-old.jsonl, table: events, checksum: "blake3:00", time_range: [0, 10], num_rows: 2 }
+  new: { path: events-new.jsonl, table: events, checksum: "blake3:11", time_range: [10, 20], num_rows: 3, schema_version: "2.0" }
+  lab: { path: labels.jsonl, table: labels, checksum: "blake3:22", time_range: [0, 20], num_rows: 1, schema_version: "1.1" }
+"#,
+        )
+        .unwrap();
+        // A per-shard version also satisfies a table absent from schema_versions
+        mf.validate_basic().unwrap();
+
+        let reloaded: DatasetManifest = serde_yaml::from_str(&mf.to_yaml().unwrap()).unwrap();
+        assert_eq!(reloaded.shards.len(), 3);
+        assert_eq!(reloaded.shard_schema_version("old"), Some("1.0"));
+        assert_eq!(reloaded.shard_schema_version("new"), Some("2.0"));
+        assert_eq!(reloaded.shard_schema_version("lab"), Some("1.1"));
+        assert_eq!(reloaded.shard_schema_version("missing"), None);
+
+        let summary = reloaded.summary();
+        assert!(
+            summary.contains("events: 2 shards, 5 rows (schema versions 1.0, 2.0)"),
+            "{}",
+            summary
+        );
+        // Tables on a single version keep the plain line
+        assert!(summary.ends_with("labels: 1 shards, 1 rows"), "{}", summary);
+    }
+
+    #[test]
+    fn validate_basic_rejects_unknown_split_shards_and_reports_overlap() {
+        let mut mf: DatasetManifest = serde_yaml::from_str(
+            r#"
+dataset_name: demo
+dataset_version: "0.1.0"
+ndf_version: NDF-H 1.0
+schema_versions: { events: "1.0" }
+license: MIT
+splits: { train: [s1, s2], test: [s2, s3] }
+shards:
+  s1: { path: a.jsonl, table: events, checksum: "blake3:00", time_range: [0, 10], num_rows: 2 }
+  s2: { path: b.jsonl, table: events, checksum: "blake3:11", time_range: [0, 10], num_rows: 1 }
+"#,
+        )
+        .unwrap();
+        let err = mf.validate_basic().unwrap_err().to_string();
+        assert!(
+            err.contains("split test references unknown shard s3"),
+            "{}",
+            err
+        );
+
+        mf.splits.get_mut("test").unwrap().retain(|id| id != "s3");
+        assert!(mf.validate_basic().is_ok());
+        let overlaps = mf.split_overlaps();
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(
+            overlaps["s2"],
+            vec!["test".to_string(), "train".to_string()]
+        );
+    }
+
+    #[test]
+    fn content_hash_is_stable_across_equivalent_manifests() {
+        let a: DatasetManifest = serde_yaml::from_str(
+            r#"
+dataset_name: demo
+dataset_version: "0.1.0"
+ndf_version: NDF-H 1.0
+schema_versions: { events: "1.0" }
+license: MIT
+splits: { train: [s2, s1] }
+shards:
+  s1: { path: events.jsonl, table: events, checksum: "blake3:00", time_range: [0, 10], num_rows: 2, pii_class: low }
+  s2: { path: labels.jsonl, table: labels, checksum: "blake3:11", time_range: [0, 10], num_rows: 1 }
+"#,
+        )
+        .unwrap();
+        let b: DatasetManifest = serde_yaml::from_str(
+            r#"
+dataset_name: " demo "
+dataset_version: "0.1.0"
+ndf_version: "NDF-H 1.0"
+schema_versions: { events: "1.0" }
+license: "mit"
+splits: { train: [s1, s2, s1] }
+shards:
+  s2: { path: labels.jsonl, table: labels, checksum: "blake3:11", time_range: [0, 10], num_rows: 1, pii_class: "" }
+  s1: { path: events.jsonl, table: events, checksum: "blake3:00", time_range: [0, 10], num_rows: 2, pii_class: " LOW" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(a.content_hash().unwrap(), b.content_hash().unwrap());
 
-        ConformanceLevel::Unknown
+        let mut c = a.clone();
+        c.license = " Apache-2.0".into();
+        assert_ne!(a.content_hash().unwrap(), c.content_hash().unwrap());
+        // The canonical manifest keeps the SPDX spelling
+        assert_eq!(c.canonicalize().license, "Apache-2.0");
     }
 }
@@ -1,7 +1,8 @@
 #![allow(dead_code)]
 //! NDF-H HGTS: temporal semantics engine (AS OF / OVER) — skeleton.
 
-use ndfh_core::{HyperedgeCatalog, HypergraphNetwork, MembershipLog};
+use ndfh_core::{materialize_hyperedges, HyperedgeCatalog, HypergraphNetwork, MembershipLog};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Copy)]
 pub enum TemporalContext {
@@ -9,6 +10,12 @@ pub enum TemporalContext {
     Over { start: i64, end: i64 },
 }
 
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum HgtsError {
+    #[error("OVER step must be positive, got {0} ns")]
+    NonPositiveStep(i64),
+}
+
 pub struct AsOfEngine;
 
 impl AsOfEngine {
@@ -25,4 +32,147 @@ impl AsOfEngine {
     ) -> HypergraphNetwork {
         log.snapshot_as_of_with_catalog(t_ns, catalog)
     }
+
+    /// OVER query: one `AS OF` snapshot per `step_ns` in the half-open interval
+    /// `[start, end)`. The log is swept once in time order, keeping the active
+    /// memberships up to date between steps instead of rescanning every row.
+    /// Tails keep log order, as in [`AsOfEngine::snapshot_with_catalog`].
+    pub fn snapshots_over(
+        log: &MembershipLog,
+        catalog: &HyperedgeCatalog,
+        start: i64,
+        end: i64,
+        step_ns: i64,
+    ) -> Result<Vec<(i64, HypergraphNetwork)>, HgtsError> {
+        if step_ns <= 0 {
+            return Err(HgtsError::NonPositiveStep(step_ns));
+        }
+        if start >= end {
+            return Ok(Vec::new());
+        }
+
+        // (time, is_open, row index, h_id, tail_v); at equal times closes sort
+        // first, matching the [t_start, t_end) validity of a row
+        let mut events: Vec<(i64, bool, usize, u64, u64)> = Vec::new();
+        for (idx, row) in log.iter().enumerate() {
+            if row
+                .t_end
+                .map(|e| e <= start || e <= row.t_start)
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            events.push((row.t_start, true, idx, row.h_id, row.tail_v));
+            if let Some(t_end) = row.t_end {
+                events.push((t_end, false, idx, row.h_id, row.tail_v));
+            }
+        }
+        events.sort_unstable();
+
+        // h_id -> row index -> tail_v of its open rows, iterating in log order
+        let mut active: BTreeMap<u64, BTreeMap<usize, u64>> = BTreeMap::new();
+        let mut next = 0;
+        let mut snapshots = Vec::new();
+        let mut t = start;
+        while t < end {
+            while let Some(&(time, is_open, idx, h_id, tail_v)) = events.get(next) {
+                if time > t {
+                    break;
+                }
+                let tails = active.entry(h_id).or_default();
+                if is_open {
+                    tails.insert(idx, tail_v);
+                } else {
+                    tails.remove(&idx);
+                }
+                if tails.is_empty() {
+                    active.remove(&h_id);
+                }
+                next += 1;
+            }
+
+            let tails_by_h: HashMap<u64, Vec<u64>> = active
+                .iter()
+                .map(|(&h_id, tails)| (h_id, tails.values().copied().collect()))
+                .collect();
+            snapshots.push((t, materialize_hyperedges(tails_by_h, catalog)));
+
+            match t.checked_add(step_ns) {
+                Some(t_next) => t = t_next,
+                None => break,
+            }
+        }
+
+        Ok(snapshots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndfh_core::{HyperedgeId, MembershipRow, NeuronId};
+
+    fn catalog() -> HyperedgeCatalog {
+        let mut catalog = HyperedgeCatalog::new();
+        catalog.register_head(1, 100);
+        catalog
+    }
+
+    #[test]
+    fn snapshots_over_match_as_of_and_show_late_tails() {
+        let log = MembershipLog::from_rows(vec![
+            MembershipRow::new(1, 10, 0),
+            MembershipRow::new(1, 11, 25),
+            MembershipRow::closed(1, 12, 0, 20).unwrap(),
+            // Out of tail order, so a sorted sweep would differ from the log
+            MembershipRow::new(1, 5, 0),
+        ])
+        .expect("log");
+        let catalog = catalog();
+
+        let snapshots = AsOfEngine::snapshots_over(&log, &catalog, 0, 40, 10).expect("over");
+        let times: Vec<i64> = snapshots.iter().map(|(t, _)| *t).collect();
+        assert_eq!(times, vec![0, 10, 20, 30]);
+
+        for (t, net) in &snapshots {
+            let expected = AsOfEngine::snapshot_with_catalog(&log, &catalog, *t);
+            let tails = |net: &HypergraphNetwork| {
+                net.get_hyperedge(HyperedgeId::from(1))
+                    .map(|e| e.sources.clone())
+                    .unwrap_or_default()
+            };
+            assert_eq!(tails(net), tails(&expected), "t = {}", t);
+            // Tail 11 joins at t_start = 25, so only the last snapshot has it
+            assert_eq!(
+                tails(net).contains(&NeuronId::from(11)),
+                *t >= 25,
+                "t = {}",
+                t
+            );
+            // Tail 12 leaves at t_end = 20
+            assert_eq!(
+                tails(net).contains(&NeuronId::from(12)),
+                *t < 20,
+                "t = {}",
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn snapshots_over_rejects_zero_step_and_handles_empty_interval() {
+        let log = MembershipLog::from_rows(vec![MembershipRow::new(1, 10, 0)]).expect("log");
+        let catalog = catalog();
+
+        assert_eq!(
+            AsOfEngine::snapshots_over(&log, &catalog, 0, 10, 0).unwrap_err(),
+            HgtsError::NonPositiveStep(0)
+        );
+        assert!(AsOfEngine::snapshots_over(&log, &catalog, 10, 10, 1)
+            .expect("over")
+            .is_empty());
+        assert!(AsOfEngine::snapshots_over(&log, &catalog, 10, 5, 1)
+            .expect("over")
+            .is_empty());
+    }
 }
@@ -0,0 +1,45 @@
+//! Sequential vs. rayon-parallel neuron pool updates on large mixed populations
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use shnn_core::neuron::{AnyNeuron, Neuron, NeuronId, NeuronPool, NeuronType};
+use shnn_core::time::{TimeStep, TimeStepExt};
+
+fn mixed_pool(size: usize) -> NeuronPool<AnyNeuron> {
+    let types = [NeuronType::LIF, NeuronType::AdEx, NeuronType::Izhikevich];
+    let mut pool = NeuronPool::with_capacity(size);
+    for i in 0..size {
+        pool.add_neuron(types[i % types.len()].create(NeuronId(i as u32)));
+    }
+    pool
+}
+
+fn drive(pool: &mut NeuronPool<AnyNeuron>, dt: TimeStep) {
+    for (i, neuron) in pool.iter_mut().enumerate() {
+        neuron.integrate((i % 7) as f64, dt);
+    }
+}
+
+fn bench_update_all(c: &mut Criterion) {
+    let dt = TimeStep::from_ms(0.1);
+    let mut group = c.benchmark_group("neuron_pool_update");
+    for size in [10_000usize, 100_000] {
+        group.bench_with_input(BenchmarkId::new("sequential", size), &size, |b, &size| {
+            let mut pool = mixed_pool(size);
+            b.iter(|| {
+                drive(&mut pool, dt);
+                black_box(pool.update_all(dt))
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", size), &size, |b, &size| {
+            let mut pool = mixed_pool(size);
+            b.iter(|| {
+                drive(&mut pool, dt);
+                black_box(pool.update_all_parallel(dt))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_update_all);
+criterion_main!(benches);
@@ -44,6 +44,7 @@
 //! - `parallel`: Enable parallel processing
 //! - `hardware-accel`: Enable hardware acceleration support
 //! - `optimized`: Enable all performance optimizations
+//! - `ndfh`: Enable conversions to `ndfh-core` hypergraph/ledger types
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
@@ -52,7 +53,7 @@
 
 // Re-export important types for convenience
 pub use crate::{
-    neuron::{Neuron, NeuronId, NeuronType, LIFNeuron, AdExNeuron, IzhikevichNeuron, DetailedLIFNeuron, DetailedHHNeuron, DetailedIzhikevichNeuron},
+    neuron::{Neuron, NeuronId, NeuronType, AnyNeuron, LIFNeuron, AdExNeuron, IzhikevichNeuron, DetailedLIFNeuron, DetailedHHNeuron, DetailedIzhikevichNeuron},
     spike::{Spike, SpikeTarget, TimedSpike},
     hypergraph::{Hyperedge, HyperedgeId, HypergraphNetwork},
     plasticity::{PlasticityRule, STDPRule},
@@ -97,6 +98,9 @@ pub mod math;
 #[cfg(any(feature = "serialize", feature = "legacy-serde"))]
 pub mod serialization;
 
+#[cfg(feature = "ndfh")]
+pub mod ndfh_bridge;
+
 #[cfg(all(test, feature = "legacy-tests"))]
 pub mod test_helpers;
 
@@ -117,6 +121,7 @@ pub mod prelude {
     // Re-export detailed neuron models with original parameters
     pub use crate::neuron::{
         DetailedLIFNeuron, DetailedHHNeuron, DetailedIzhikevichNeuron,
+        DetailedLIFConfig, DetailedHHConfig,
     };
     
     #[cfg(feature = "async")]
@@ -0,0 +1,211 @@
+//! Bridge between SHNN simulation hypergraphs and NDF-H packaging types
+//!
+//! Converts a [`HypergraphNetwork`] built for simulation into the `ndfh-core`
+//! representation used by the NDF-H storage, snapshot and export tooling.
+
+use crate::hypergraph::{Hyperedge, HyperedgeId, HyperedgeType, HypergraphNetwork};
+use crate::spike::NeuronId;
+
+impl From<NeuronId> for ndfh_core::NeuronId {
+    fn from(id: NeuronId) -> Self {
+        ndfh_core::NeuronId::from(id.raw())
+    }
+}
+
+impl From<ndfh_core::NeuronId> for NeuronId {
+    fn from(id: ndfh_core::NeuronId) -> Self {
+        NeuronId::new(id.raw())
+    }
+}
+
+impl From<HyperedgeId> for ndfh_core::HyperedgeId {
+    fn from(id: HyperedgeId) -> Self {
+        ndfh_core::HyperedgeId::from(id.raw())
+    }
+}
+
+impl From<ndfh_core::HyperedgeId> for HyperedgeId {
+    fn from(id: ndfh_core::HyperedgeId) -> Self {
+        HyperedgeId::new(id.raw())
+    }
+}
+
+/// NDF-H hyperedges and head catalog converted from a SHNN hypergraph
+#[derive(Debug, Default)]
+pub struct NdfhNetwork {
+    /// Converted hyperedges with the same ids and endpoints
+    pub network: ndfh_core::HypergraphNetwork,
+    /// Head vertex and kind of every converted hyperedge
+    pub catalog: ndfh_core::HyperedgeCatalog,
+    /// Hyperedges with neither a single source nor a single target, or that
+    /// `ndfh_core` rejected
+    pub skipped: Vec<HyperedgeId>,
+}
+
+/// Membership ledger and head catalog derived from a SHNN hypergraph
+#[derive(Debug, Default)]
+pub struct NdfhLedger {
+    /// Open memberships (one per hyperedge tail) starting at the conversion time
+    pub log: ndfh_core::MembershipLog,
+    /// Head vertex and kind of every converted hyperedge
+    pub catalog: ndfh_core::HyperedgeCatalog,
+    /// Hyperedges with neither a single source nor a single target, which the
+    /// NDF-H catalog cannot represent
+    pub skipped: Vec<HyperedgeId>,
+}
+
+/// NDF-H kind, head and tails of a hyperedge
+///
+/// Convergent (single-target) hyperedges become ManyToOne and divergent
+/// (single-source) ones OneToMany; a 1:1 pairwise edge follows its SHNN type.
+fn ndfh_shape(edge: &Hyperedge) -> Option<(ndfh_core::HyperedgeType, NeuronId, &[NeuronId])> {
+    let fans_out = match (edge.sources.len(), edge.targets.len()) {
+        (1, 1) => edge.edge_type == HyperedgeType::OneToMany,
+        (_, 1) => false,
+        (1, _) => true,
+        _ => return None,
+    };
+    Some(if fans_out {
+        (ndfh_core::HyperedgeType::OneToMany, edge.sources[0], edge.targets.as_slice())
+    } else {
+        (ndfh_core::HyperedgeType::ManyToOne, edge.targets[0], edge.sources.as_slice())
+    })
+}
+
+/// Convert every hyperedge into an `ndfh_core` hyperedge with the same id and
+/// endpoints, registering its head and kind in the catalog
+pub fn to_ndfh_network(net: &HypergraphNetwork) -> NdfhNetwork {
+    let mut out = NdfhNetwork::default();
+    for id in net.hyperedge_ids() {
+        let Some(edge) = net.get_hyperedge(id) else {
+            continue;
+        };
+        let Some((kind, head, _)) = ndfh_shape(edge) else {
+            out.skipped.push(id);
+            continue;
+        };
+        let sources = edge.sources.iter().map(|&n| n.into()).collect();
+        let targets = edge.targets.iter().map(|&n| n.into()).collect();
+        let added = ndfh_core::Hyperedge::new(id.into(), sources, targets, kind)
+            .and_then(|converted| out.network.add_hyperedge(converted));
+        if added.is_err() {
+            out.skipped.push(id);
+            continue;
+        }
+        let h_id = id.raw() as u64;
+        out.catalog.register_head(h_id, head.raw() as u64);
+        out.catalog.register_kind(h_id, kind);
+    }
+    out
+}
+
+/// Convert hyperedges into an NDF-H membership log and head catalog, with every
+/// tail joining its hyperedge at `t_start` (nanoseconds)
+///
+/// Only hyperedges with a single target (ManyToOne) or a single source
+/// (OneToMany) map onto the `h_id -> head_v` catalog; the ids of the rest are
+/// reported in [`NdfhLedger::skipped`].
+pub fn to_ndfh_ledger(net: &HypergraphNetwork, t_start: i64) -> NdfhLedger {
+    let mut ledger = NdfhLedger::default();
+    for id in net.hyperedge_ids() {
+        let Some(edge) = net.get_hyperedge(id) else {
+            continue;
+        };
+        let Some((kind, head, tails)) = ndfh_shape(edge) else {
+            ledger.skipped.push(id);
+            continue;
+        };
+        let h_id = id.raw() as u64;
+        ledger.catalog.register_head(h_id, head.raw() as u64);
+        ledger.catalog.register_kind(h_id, kind);
+        for tail in tails {
+            ledger.log.add(h_id, tail.raw() as u64, t_start);
+        }
+    }
+    ledger
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_round_trip_between_crates() {
+        let id = NeuronId::new(42);
+        let converted: ndfh_core::NeuronId = id.into();
+        assert_eq!(converted.raw(), 42);
+        assert_eq!(NeuronId::from(converted), id);
+
+        let h_id = HyperedgeId::new(7);
+        let converted: ndfh_core::HyperedgeId = h_id.into();
+        assert_eq!(converted, ndfh_core::HyperedgeId::from(7));
+        assert_eq!(HyperedgeId::from(converted), h_id);
+    }
+
+    #[test]
+    fn test_ledger_snapshot_matches_shnn_topology() {
+        let mut net = HypergraphNetwork::new();
+        net.add_hyperedge(
+            Hyperedge::convergent(
+                HyperedgeId::new(0),
+                vec![NeuronId::new(10), NeuronId::new(11)],
+                NeuronId::new(99),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        net.add_hyperedge(
+            Hyperedge::divergent(
+                HyperedgeId::new(1),
+                NeuronId::new(1),
+                vec![NeuronId::new(2), NeuronId::new(3)],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        net.add_hyperedge(
+            Hyperedge::many_to_many(
+                HyperedgeId::new(2),
+                vec![NeuronId::new(4), NeuronId::new(5)],
+                vec![NeuronId::new(6), NeuronId::new(7)],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let ledger = to_ndfh_ledger(&net, 100);
+        assert_eq!(ledger.skipped, vec![HyperedgeId::new(2)]);
+        assert_eq!(ledger.catalog.kind_of(1), ndfh_core::HyperedgeType::OneToMany);
+
+        let snapshot = ledger.log.snapshot_as_of_with_catalog(150, &ledger.catalog);
+        let edge = snapshot
+            .get_hyperedge(ndfh_core::HyperedgeId::from(0))
+            .expect("converted hyperedge");
+        assert_eq!(edge.sources.len(), 2);
+        assert_eq!(edge.targets, vec![ndfh_core::NeuronId::from(99)]);
+
+        // The divergent hyperedge keeps its source as the head
+        let fan_out = snapshot
+            .get_hyperedge(ndfh_core::HyperedgeId::from(1))
+            .expect("divergent hyperedge");
+        assert_eq!(fan_out.kind(), ndfh_core::HyperedgeType::OneToMany);
+        assert_eq!(fan_out.sources, vec![ndfh_core::NeuronId::from(1)]);
+        assert_eq!(fan_out.targets.len(), 2);
+
+        // Nothing is active before the conversion time
+        let before = ledger.log.snapshot_as_of_with_catalog(50, &ledger.catalog);
+        assert!(before.hyperedge_ids().is_empty());
+
+        // The direct conversion agrees with the ledger snapshot
+        let direct = to_ndfh_network(&net);
+        assert_eq!(direct.skipped, vec![HyperedgeId::new(2)]);
+        assert_eq!(direct.network.hyperedge_ids(), snapshot.hyperedge_ids());
+        for id in snapshot.hyperedge_ids() {
+            let converted = direct.network.get_hyperedge(id).unwrap();
+            assert!(converted.structurally_eq(snapshot.get_hyperedge(id).unwrap()));
+        }
+        assert_eq!(direct.catalog.head_of(1), Some(1));
+        assert_eq!(direct.catalog.kind_of(1), ndfh_core::HyperedgeType::OneToMany);
+    }
+}
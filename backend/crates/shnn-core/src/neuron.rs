@@ -20,6 +20,8 @@ pub enum NeuronType {
     AdEx,
     /// Izhikevich neuron model
     Izhikevich,
+    /// Hodgkin-Huxley conductance-based neuron model
+    HodgkinHuxley,
 }
 
 impl Default for NeuronType {
@@ -28,6 +30,20 @@ impl Default for NeuronType {
     }
 }
 
+impl NeuronType {
+    /// Construct a neuron of this type with default parameters
+    pub fn create(self, id: NeuronId) -> AnyNeuron {
+        match self {
+            Self::LIF => AnyNeuron::LIF(LIFNeuron::new(id)),
+            Self::AdEx => AnyNeuron::AdEx(AdExNeuron::new(id)),
+            Self::Izhikevich => AnyNeuron::Izhikevich(IzhikevichNeuron::regular_spiking(id)),
+            Self::HodgkinHuxley => {
+                AnyNeuron::HodgkinHuxley(DetailedHHNeuron::from_spiking_networks_defaults(id))
+            }
+        }
+    }
+}
+
 /// Collection of neurons for efficient management
 #[derive(Debug, Clone)]
 pub struct NeuronPool<T: Neuron> {
@@ -101,12 +117,38 @@ impl<T: Neuron> NeuronPool<T> {
         spikes
     }
 
+    /// Update all neurons across the rayon thread pool and collect generated spikes.
+    /// Produces the same spikes as `update_all`, ordered by neuron index.
+    #[cfg(feature = "parallel")]
+    pub fn update_all_parallel(&mut self, dt: TimeStep) -> Vec<(usize, Spike)> {
+        use rayon::prelude::*;
+
+        let mut spikes: Vec<(usize, Spike)> = self
+            .neurons
+            .par_iter_mut()
+            .enumerate()
+            .filter_map(|(index, neuron)| neuron.update(dt).map(|spike| (index, spike)))
+            .collect();
+        spikes.sort_unstable_by_key(|(index, _)| *index);
+        spikes
+    }
+
     /// Reset all neurons to their default state
     pub fn reset_all(&mut self) {
         for neuron in self.neurons.iter_mut() {
             neuron.reset();
         }
     }
+
+    /// Number of neurons currently in their refractory period
+    pub fn refractory_count(&self) -> usize {
+        self.neurons.iter().filter(|n| n.is_refractory()).count()
+    }
+
+    /// Sum of the metabolic cost accumulated by all neurons in the pool
+    pub fn total_metabolic_cost(&self) -> f64 {
+        self.neurons.iter().map(|n| n.metabolic_cost()).sum()
+    }
 }
 
 impl<T: Neuron> Default for NeuronPool<T> {
@@ -140,8 +182,21 @@ pub trait Neuron: Send + Sync + Clone {
 
     /// Set neuron's identifier
     fn set_id(&mut self, id: NeuronId);
+
+    /// Estimated metabolic cost accumulated so far (arbitrary units, 0.0 if not modeled)
+    fn metabolic_cost(&self) -> f64 {
+        0.0
+    }
+
+    /// Whether the neuron is in its refractory period (false if not modeled)
+    fn is_refractory(&self) -> bool {
+        false
+    }
 }
 
+/// Metabolic cost charged per emitted spike by the point neuron models
+pub const SPIKE_METABOLIC_COST: f64 = 1.0;
+
 /// Leaky Integrate-and-Fire neuron model
 /// The LIF model is the simplest spiking neuron model, where the membrane potential
 /// Configuration for LIF neuron parameters
@@ -180,6 +235,7 @@ impl Default for LIFConfig {
 
 /// integrates input current with exponential decay (leak).
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LIFNeuron {
     id: NeuronId,
     state: NeuronState,
@@ -211,15 +267,23 @@ pub struct NeuronState {
     pub refractory_timer: TimeStep,
     /// Timestamp of the last spike generated
     pub last_spike_time: Option<TimeStep>,
+    /// Number of spikes generated so far
+    pub spike_count: u64,
 }
 
 impl NeuronState {
     /// Create new default neuron state
     pub fn new() -> Self {
+        Self::at_rest(-65.0) // Typical resting potential
+    }
+
+    /// Create new neuron state starting at the given resting potential (mV)
+    pub fn at_rest(resting_potential: f64) -> Self {
         Self {
-            membrane_potential: -65.0, // Typical resting potential
+            membrane_potential: resting_potential,
             refractory_timer: 0,
             last_spike_time: None,
+            spike_count: 0,
         }
     }
 
@@ -250,7 +314,7 @@ impl LIFNeuron {
     pub fn with_config(id: NeuronId, config: LIFConfig) -> Self {
         Self {
             id,
-            state: NeuronState::new(),
+            state: NeuronState::at_rest(config.resting_potential),
             tau_membrane: config.tau_membrane,
             resistance: config.resistance,
             capacitance: config.capacitance,
@@ -306,6 +370,7 @@ impl Neuron for LIFNeuron {
         if self.state.membrane_potential >= self.threshold {
             self.reset();
             self.state.last_spike_time = Some(0); // Would need current time
+            self.state.spike_count += 1;
             self.state.refractory_timer = (self.refractory_period * 1000.0) as TimeStep;
 
             // Create spike with proper type conversion and error handling
@@ -342,12 +407,21 @@ impl Neuron for LIFNeuron {
     fn set_id(&mut self, id: NeuronId) {
         self.id = id;
     }
+
+    fn is_refractory(&self) -> bool {
+        self.state.is_refractory()
+    }
+
+    fn metabolic_cost(&self) -> f64 {
+        self.state.spike_count as f64 * SPIKE_METABOLIC_COST
+    }
 }
 
 /// Adaptive Exponential Integrate-and-Fire neuron model
 /// The AdEx model includes an exponential term and adaptation current,
 /// providing more realistic spike generation and frequency adaptation.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdExNeuron {
     id: NeuronId,
     state: NeuronState,
@@ -364,8 +438,11 @@ pub struct AdExNeuron {
     pub conductance: f64,
     /// Membrane capacitance in picoFarads
     pub capacitance: f64,
-    /// Spike threshold in millivolts
+    /// Exponential threshold (rheobase potential) in millivolts
     pub threshold: f64,
+    /// Peak potential in millivolts at which a spike is detected; the exponential
+    /// term is capped here too, so both use the same effective threshold
+    pub v_peak: f64,
     /// Reset potential in millivolts
     pub reset_potential: f64,
     /// Resting potential in millivolts
@@ -379,9 +456,10 @@ pub struct AdExNeuron {
 impl AdExNeuron {
     /// Create new AdEx neuron with default parameters
     pub fn new(id: NeuronId) -> Self {
+        let resting_potential = -70.6; // -70.6mV resting
         Self {
             id,
-            state: NeuronState::new(),
+            state: NeuronState::at_rest(resting_potential),
             adaptation_current: 0.0,
             tau_membrane: 9.3,       // 9.3ms membrane time constant
             tau_adaptation: 144.0,   // 144ms adaptation time constant
@@ -389,8 +467,9 @@ impl AdExNeuron {
             conductance: 30.0,       // 30nS leak conductance
             capacitance: 281.0,      // 281pF capacitance
             threshold: -50.4,        // -50.4mV threshold
+            v_peak: -40.4,           // -40.4mV spike detection
             reset_potential: -70.6,  // -70.6mV reset
-            resting_potential: -70.6, // -70.6mV resting
+            resting_potential,
             adaptation_increment: 4.0, // 4pA adaptation increment
             refractory_period: 2.0,   // 2ms refractory
         }
@@ -410,40 +489,39 @@ impl Default for AdExNeuron {
 
 impl Neuron for AdExNeuron {
     fn integrate(&mut self, input_current: f64, dt: TimeStep) {
+        let dt_ms = dt as f64 / 1000.0; // Convert from TimeStep (u64) to milliseconds
+
+        // Adaptation current relaxes with tau_adaptation, including while refractory
+        let adaptation = self.adaptation_current;
+        self.adaptation_current -= adaptation / self.tau_adaptation * dt_ms;
+
         if self.state.is_refractory() {
             self.state.refractory_timer = self.state.refractory_timer.saturating_sub(dt);
             return;
         }
 
-        let dt_ms = dt as f64 / 1000.0; // Convert from TimeStep (u64) to milliseconds
         let v = self.state.membrane_potential;
 
-        // Exponential term for spike generation
-        let exp_term = if v - self.threshold < 10.0 { // Avoid overflow
-            self.delta_t * ((v - self.threshold) / self.delta_t).exp()
-        } else {
-            self.delta_t * (10.0f64).exp() // Large value to trigger spike
-        };
+        // Exponential term for spike generation, capped at v_peak to avoid overflow
+        let exp_term = self.delta_t * ((v.min(self.v_peak) - self.threshold) / self.delta_t).exp();
 
         // Membrane equation with exponential term
         let leak_current = self.conductance * (self.resting_potential - v);
-        let adaptation_term = -self.adaptation_current;
+        let adaptation_term = -adaptation;
         let exponential_current = self.conductance * exp_term;
 
         let dv_dt = (leak_current + adaptation_term + exponential_current + input_current) / self.capacitance;
 
         // Update membrane potential
         self.state.membrane_potential += dv_dt * dt_ms;
-
-        // Update adaptation current
-        let da_dt = -self.adaptation_current / self.tau_adaptation;
-        self.adaptation_current += da_dt * dt_ms;
     }
 
     fn update(&mut self, _dt: TimeStep) -> Option<Spike> {
-        if self.state.membrane_potential >= self.threshold + 10.0 { // Spike condition
-            self.reset();
+        if self.state.membrane_potential >= self.v_peak { // Spike condition
+            // Post-spike reset keeps the accumulated adaptation, unlike `reset()`
+            self.state.membrane_potential = self.reset_potential;
             self.adaptation_current += self.adaptation_increment;
+            self.state.spike_count += 1;
             self.state.refractory_timer = (self.refractory_period * 1000.0) as TimeStep;
 
             // Create spike with proper type conversion and error handling
@@ -469,8 +547,11 @@ impl Neuron for AdExNeuron {
         self.threshold
     }
 
+    /// Reset the membrane and clear the adaptation current, so adaptation
+    /// does not carry over between independent runs
     fn reset(&mut self) {
         self.state.membrane_potential = self.reset_potential;
+        self.adaptation_current = 0.0;
     }
 
     fn id(&self) -> NeuronId {
@@ -480,12 +561,21 @@ impl Neuron for AdExNeuron {
     fn set_id(&mut self, id: NeuronId) {
         self.id = id;
     }
+
+    fn is_refractory(&self) -> bool {
+        self.state.is_refractory()
+    }
+
+    fn metabolic_cost(&self) -> f64 {
+        self.state.spike_count as f64 * SPIKE_METABOLIC_COST
+    }
 }
 
 /// Izhikevich neuron model
 /// A computationally efficient model that can reproduce various firing patterns
 /// depending on parameter values.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IzhikevichNeuron {
     id: NeuronId,
     state: NeuronState,
@@ -500,19 +590,26 @@ pub struct IzhikevichNeuron {
     pub c: f64,
     /// Recovery increment in picoAmperes
     pub d: f64,
+    /// Euler substeps per `integrate` call for the membrane equation
+    /// (2 matches the reference model's two half-steps; 0 is treated as 1)
+    pub substeps: usize,
 }
 
+/// Spike peak of the Izhikevich model in millivolts
+const IZHIKEVICH_V_PEAK: f64 = 30.0;
+
 impl IzhikevichNeuron {
     /// Create new Izhikevich neuron with specified parameters
     pub fn new(a: f64, b: f64, c: f64, d: f64) -> Self {
         Self {
             id: NeuronId(0),
-            state: NeuronState::new(),
+            state: NeuronState::at_rest(c),
             recovery_variable: -14.0, // Typical initial value
             a,
             b,
             c,
             d,
+            substeps: 2,
         }
     }
 
@@ -566,17 +663,29 @@ impl Neuron for IzhikevichNeuron {
         // dv/dt = 0.04*v^2 + 5*v + 140 - u + I
         // du/dt = a*(b*v - u)
 
-        let dv_dt = 0.04 * v * v + 5.0 * v + 140.0 - u + input_current;
         let du_dt = self.a * (self.b * v - u);
 
-        self.state.membrane_potential += dv_dt * dt_ms;
+        // Subdivide the stiff quadratic voltage update; stop at the spike peak so a
+        // large step cannot overshoot into runaway voltages before `update` resets
+        let substeps = self.substeps.max(1);
+        let h = dt_ms / substeps as f64;
+        for _ in 0..substeps {
+            let v = self.state.membrane_potential;
+            let dv_dt = 0.04 * v * v + 5.0 * v + 140.0 - u + input_current;
+            self.state.membrane_potential += dv_dt * h;
+            if self.state.membrane_potential >= IZHIKEVICH_V_PEAK {
+                self.state.membrane_potential = IZHIKEVICH_V_PEAK;
+                break;
+            }
+        }
         self.recovery_variable += du_dt * dt_ms;
     }
 
     fn update(&mut self, _dt: TimeStep) -> Option<Spike> {
-        if self.state.membrane_potential >= 30.0 { // Fixed threshold for Izhikevich
+        if self.state.membrane_potential >= IZHIKEVICH_V_PEAK { // Fixed threshold for Izhikevich
             self.state.membrane_potential = self.c;
             self.recovery_variable += self.d;
+            self.state.spike_count += 1;
 
             // Create spike with proper type conversion and error handling
             Spike::new(
@@ -598,7 +707,7 @@ impl Neuron for IzhikevichNeuron {
     }
 
     fn threshold(&self) -> f64 {
-        30.0 // Fixed threshold for Izhikevich model
+        IZHIKEVICH_V_PEAK // Fixed threshold for Izhikevich model
     }
 
     fn reset(&mut self) {
@@ -613,11 +722,50 @@ impl Neuron for IzhikevichNeuron {
     fn set_id(&mut self, id: NeuronId) {
         self.id = id;
     }
+
+    fn metabolic_cost(&self) -> f64 {
+        self.state.spike_count as f64 * SPIKE_METABOLIC_COST
+    }
+}
+
+/// Configuration for DetailedLIFNeuron parameters
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DetailedLIFConfig {
+    /// Resting potential in millivolts
+    pub resting_potential: f64,
+    /// Reset potential after spike in millivolts
+    pub reset_potential: f64,
+    /// Spike threshold in millivolts
+    pub threshold: f64,
+    /// Membrane time constant in milliseconds
+    pub tau_membrane: f64,
+    /// Membrane resistance in MegaOhms
+    pub resistance: f64,
+    /// Membrane capacitance in nanoFarads
+    pub capacitance: f64,
+    /// Refractory period in milliseconds
+    pub refractory_period: f64,
+}
+
+impl Default for DetailedLIFConfig {
+    fn default() -> Self {
+        Self {
+            resting_potential: -65.0, // mV
+            reset_potential: -75.0,   // mV
+            threshold: -55.0,         // mV
+            tau_membrane: 20.0,       // ms
+            resistance: 10.0,         // MΩ
+            capacitance: 2.0,         // nF
+            refractory_period: 2.0,   // ms
+        }
+    }
 }
 
 /// Detailed Leaky Integrate-and-Fire neuron model
 /// More biologically realistic with proper membrane equation
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DetailedLIFNeuron {
     /// Neuron identifier
     pub id: NeuronId,
@@ -644,16 +792,21 @@ pub struct DetailedLIFNeuron {
 impl DetailedLIFNeuron {
     /// Create a DetailedLIFNeuron with default parameters from spiking-networks
     pub fn from_spiking_networks_defaults(id: NeuronId) -> Self {
+        Self::with_config(id, DetailedLIFConfig::default())
+    }
+
+    /// Create a DetailedLIFNeuron with the given parameters, starting at rest
+    pub fn with_config(id: NeuronId, config: DetailedLIFConfig) -> Self {
         Self {
             id,
-            membrane_potential: -65.0, // mV
-            resting_potential: -65.0,  // mV
-            reset_potential: -75.0,    // mV
-            threshold: -55.0,          // mV
-            tau_membrane: 20.0,        // ms
-            resistance: 10.0,          // MΩ
-            capacitance: 2.0,          // nF
-            refractory_period: 2.0,    // ms
+            membrane_potential: config.resting_potential,
+            resting_potential: config.resting_potential,
+            reset_potential: config.reset_potential,
+            threshold: config.threshold,
+            tau_membrane: config.tau_membrane,
+            resistance: config.resistance,
+            capacitance: config.capacitance,
+            refractory_period: config.refractory_period,
             refractory_timer: 0,
         }
     }
@@ -715,11 +868,62 @@ impl Neuron for DetailedLIFNeuron {
     fn set_id(&mut self, id: NeuronId) {
         self.id = id;
     }
+
+    fn is_refractory(&self) -> bool {
+        self.refractory_timer > 0
+    }
+}
+
+/// Configuration for DetailedHHNeuron parameters
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DetailedHHConfig {
+    /// Resting potential in millivolts
+    pub resting_potential: f64,
+    /// Reset potential after spike in millivolts
+    pub reset_potential: f64,
+    /// Spike threshold in millivolts
+    pub threshold: f64,
+    /// Membrane capacitance in nanoFarads
+    pub capacitance: f64,
+    /// Sodium conductance in mS/cm²
+    pub g_na: f64,
+    /// Potassium conductance in mS/cm²
+    pub g_k: f64,
+    /// Leak conductance in mS/cm²
+    pub g_l: f64,
+    /// Sodium reversal potential in mV
+    pub e_na: f64,
+    /// Potassium reversal potential in mV
+    pub e_k: f64,
+    /// Leak reversal potential in mV
+    pub e_l: f64,
+    /// Refractory period in milliseconds
+    pub refractory_period: f64,
+}
+
+impl Default for DetailedHHConfig {
+    fn default() -> Self {
+        Self {
+            resting_potential: -65.0, // mV
+            reset_potential: -75.0,   // mV
+            threshold: -55.0,         // mV (approximate)
+            capacitance: 1.0,         // nF
+            g_na: 120.0,              // mS/cm²
+            g_k: 36.0,                // mS/cm²
+            g_l: 0.3,                 // mS/cm²
+            e_na: 50.0,               // mV
+            e_k: -77.0,               // mV
+            e_l: -54.4,               // mV
+            refractory_period: 2.0,   // ms
+        }
+    }
 }
 
 /// Detailed Hodgkin-Huxley neuron model
 /// Full implementation of the classic HH equations with sodium, potassium, and leak channels
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DetailedHHNeuron {
     /// Neuron identifier
     pub id: NeuronId,
@@ -755,29 +959,38 @@ pub struct DetailedHHNeuron {
     pub refractory_period: f64,
     /// Remaining refractory time
     pub refractory_timer: TimeStep,
+    /// Gross ionic flux `|I_na| + |I_k| + |I_l|` integrated over time (uA/cm² * ms),
+    /// used as metabolic cost
+    pub metabolic_cost: f64,
 }
 
 impl DetailedHHNeuron {
     /// Create a DetailedHHNeuron with default parameters from spiking-networks
     pub fn from_spiking_networks_defaults(id: NeuronId) -> Self {
+        Self::with_config(id, DetailedHHConfig::default())
+    }
+
+    /// Create a DetailedHHNeuron with the given parameters, starting at rest
+    pub fn with_config(id: NeuronId, config: DetailedHHConfig) -> Self {
         Self {
             id,
-            membrane_potential: -65.0, // mV
-            resting_potential: -65.0,  // mV
-            reset_potential: -75.0,    // mV
-            threshold: -55.0,          // mV (approximate)
-            capacitance: 1.0,          // nF
-            g_na: 120.0,               // mS/cm²
-            g_k: 36.0,                 // mS/cm²
-            g_l: 0.3,                  // mS/cm²
-            e_na: 50.0,                // mV
-            e_k: -77.0,                // mV
-            e_l: -54.4,                // mV
-            na_m: 0.05,                // Initial sodium activation
-            na_h: 0.6,                 // Initial sodium inactivation
-            k_n: 0.32,                 // Initial potassium activation
-            refractory_period: 2.0,    // ms
+            membrane_potential: config.resting_potential,
+            resting_potential: config.resting_potential,
+            reset_potential: config.reset_potential,
+            threshold: config.threshold,
+            capacitance: config.capacitance,
+            g_na: config.g_na,
+            g_k: config.g_k,
+            g_l: config.g_l,
+            e_na: config.e_na,
+            e_k: config.e_k,
+            e_l: config.e_l,
+            na_m: 0.05, // Initial sodium activation
+            na_h: 0.6,  // Initial sodium inactivation
+            k_n: 0.32,  // Initial potassium activation
+            refractory_period: config.refractory_period,
             refractory_timer: 0,
+            metabolic_cost: 0.0,
         }
     }
 
@@ -830,6 +1043,10 @@ impl Neuron for DetailedHHNeuron {
         let dv_dt = -total_current / self.capacitance;
 
         self.membrane_potential += dv_dt * dt_ms;
+
+        // Every ion that crosses the membrane has to be pumped back, so the cost follows the
+        // gross flux of each current; the Na/K flux is non-zero even at rest
+        self.metabolic_cost += (i_na.abs() + i_k.abs() + i_l.abs()) * dt_ms;
     }
 
     fn update(&mut self, _dt: TimeStep) -> Option<Spike> {
@@ -876,11 +1093,20 @@ impl Neuron for DetailedHHNeuron {
     fn set_id(&mut self, id: NeuronId) {
         self.id = id;
     }
+
+    fn is_refractory(&self) -> bool {
+        self.refractory_timer > 0
+    }
+
+    fn metabolic_cost(&self) -> f64 {
+        self.metabolic_cost
+    }
 }
 
 /// Detailed Izhikevich neuron model
 /// Efficient model that can reproduce various firing patterns
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DetailedIzhikevichNeuron {
     /// Neuron identifier
     pub id: NeuronId,
@@ -988,8 +1214,289 @@ impl Neuron for DetailedIzhikevichNeuron {
     fn set_id(&mut self, id: NeuronId) {
         self.id = id;
     }
+
+    fn is_refractory(&self) -> bool {
+        self.refractory_timer > 0
+    }
+}
+
+/// A neuron of any model listed in [`NeuronType`], as produced by [`NeuronType::create`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnyNeuron {
+    /// Leaky Integrate-and-Fire neuron
+    LIF(LIFNeuron),
+    /// Adaptive Exponential Integrate-and-Fire neuron
+    AdEx(AdExNeuron),
+    /// Izhikevich neuron
+    Izhikevich(IzhikevichNeuron),
+    /// Hodgkin-Huxley neuron
+    HodgkinHuxley(DetailedHHNeuron),
+}
+
+impl AnyNeuron {
+    /// The model this neuron was built from
+    pub fn neuron_type(&self) -> NeuronType {
+        match self {
+            Self::LIF(_) => NeuronType::LIF,
+            Self::AdEx(_) => NeuronType::AdEx,
+            Self::Izhikevich(_) => NeuronType::Izhikevich,
+            Self::HodgkinHuxley(_) => NeuronType::HodgkinHuxley,
+        }
+    }
+}
+
+macro_rules! any_neuron_dispatch {
+    ($self:ident, $n:ident => $body:expr) => {
+        match $self {
+            AnyNeuron::LIF($n) => $body,
+            AnyNeuron::AdEx($n) => $body,
+            AnyNeuron::Izhikevich($n) => $body,
+            AnyNeuron::HodgkinHuxley($n) => $body,
+        }
+    };
+}
+
+impl Neuron for AnyNeuron {
+    fn integrate(&mut self, input_current: f64, dt: TimeStep) {
+        any_neuron_dispatch!(self, n => n.integrate(input_current, dt))
+    }
+
+    fn update(&mut self, dt: TimeStep) -> Option<Spike> {
+        any_neuron_dispatch!(self, n => n.update(dt))
+    }
+
+    fn membrane_potential(&self) -> f64 {
+        any_neuron_dispatch!(self, n => n.membrane_potential())
+    }
+
+    fn set_membrane_potential(&mut self, voltage: f64) {
+        any_neuron_dispatch!(self, n => n.set_membrane_potential(voltage))
+    }
+
+    fn threshold(&self) -> f64 {
+        any_neuron_dispatch!(self, n => n.threshold())
+    }
+
+    fn reset(&mut self) {
+        any_neuron_dispatch!(self, n => n.reset())
+    }
+
+    fn id(&self) -> NeuronId {
+        any_neuron_dispatch!(self, n => n.id())
+    }
+
+    fn set_id(&mut self, id: NeuronId) {
+        any_neuron_dispatch!(self, n => n.set_id(id))
+    }
+
+    fn is_refractory(&self) -> bool {
+        any_neuron_dispatch!(self, n => n.is_refractory())
+    }
+
+    fn metabolic_cost(&self) -> f64 {
+        any_neuron_dispatch!(self, n => n.metabolic_cost())
+    }
 }
 
+    #[test]
+    fn test_neuron_type_factory() {
+        let types = [
+            NeuronType::LIF,
+            NeuronType::AdEx,
+            NeuronType::Izhikevich,
+            NeuronType::HodgkinHuxley,
+        ];
+        for (i, ty) in types.into_iter().enumerate() {
+            let neuron = ty.create(NeuronId(i as u32));
+            assert_eq!(neuron.neuron_type(), ty);
+            assert_eq!(neuron.id(), NeuronId(i as u32));
+        }
+
+        match NeuronType::HodgkinHuxley.create(NeuronId(7)) {
+            AnyNeuron::HodgkinHuxley(hh) => {
+                assert_eq!(hh, DetailedHHNeuron::from_spiking_networks_defaults(NeuronId(7)));
+            }
+            other => panic!("expected DetailedHHNeuron, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_neurons_start_at_resting_potential() {
+        let adex = AdExNeuron::new(NeuronId(0));
+        assert_eq!(adex.membrane_potential(), adex.resting_potential);
+
+        let config = LIFConfig { resting_potential: -60.0, ..LIFConfig::default() };
+        let lif = LIFNeuron::with_config(NeuronId(0), config);
+        assert_eq!(lif.membrane_potential(), -60.0);
+
+        let izh = IzhikevichNeuron::chattering(NeuronId(0));
+        assert_eq!(izh.membrane_potential(), izh.c);
+    }
+
+    #[test]
+    fn test_adex_adaptation_decays_between_spikes() {
+        use crate::time::TimeStepExt;
+        let dt = TimeStep::from_ms(0.1);
+        let mut adex = AdExNeuron::new(NeuronId(0));
+        let decay = 1.0 - (dt as f64 / 1000.0) / adex.tau_adaptation;
+        let mut spikes = 0;
+        for _ in 0..5000 {
+            let before = adex.adaptation_current();
+            adex.integrate(1000.0, dt);
+            let decayed = adex.adaptation_current();
+            assert!((decayed - before * decay).abs() < 1e-9);
+            if adex.update(dt).is_some() {
+                spikes += 1;
+                assert!((adex.adaptation_current() - (decayed + adex.adaptation_increment)).abs() < 1e-9);
+                assert_eq!(adex.membrane_potential(), adex.reset_potential);
+            }
+            assert!(adex.membrane_potential() < adex.v_peak);
+        }
+        assert!(spikes > 2);
+        assert!(adex.adaptation_current() > 0.0);
+
+        adex.reset();
+        assert_eq!(adex.adaptation_current(), 0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_neuron_state_round_trips_through_serde() {
+        use crate::time::TimeStepExt;
+        let dt = TimeStep::from_ms(0.1);
+        let types = [NeuronType::LIF, NeuronType::AdEx, NeuronType::Izhikevich, NeuronType::HodgkinHuxley];
+        for (i, ty) in types.into_iter().enumerate() {
+            let mut neuron = ty.create(NeuronId(i as u32));
+            // Stop mid-simulation so spike counts, timers and recovery/adaptation are non-trivial
+            let current = if ty == NeuronType::AdEx { 1000.0 } else { 10.0 };
+            for _ in 0..1234 {
+                neuron.integrate(current, dt);
+                neuron.update(dt);
+            }
+            // bincode keeps f64 state bit-exact, as a checkpoint must
+            let bytes = bincode::serialize(&neuron).unwrap();
+            let restored: AnyNeuron = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(restored, neuron);
+        }
+
+        let mut adex = AdExNeuron::new(NeuronId(9));
+        for _ in 0..500 {
+            adex.integrate(1000.0, dt);
+            adex.update(dt);
+        }
+        let restored: AdExNeuron = bincode::deserialize(&bincode::serialize(&adex).unwrap()).unwrap();
+        assert!(adex.adaptation_current() > 0.0);
+        assert_eq!(restored.adaptation_current(), adex.adaptation_current());
+        assert_eq!(restored, adex);
+    }
+
+    #[test]
+    fn test_izhikevich_substeps_keep_voltage_bounded() {
+        use crate::time::TimeStepExt;
+        for dt_ms in [0.1, 1.0] {
+            let dt = TimeStep::from_ms(dt_ms);
+            for substeps in [1, 2, 4] {
+                let mut izh = IzhikevichNeuron::regular_spiking(NeuronId(0));
+                izh.substeps = substeps;
+                let mut spikes = 0;
+                for _ in 0..(200.0 / dt_ms) as usize {
+                    izh.integrate(200.0, dt);
+                    let v = izh.membrane_potential();
+                    assert!(v.is_finite() && v <= 30.0, "v={} at dt={} substeps={}", v, dt_ms, substeps);
+                    if izh.update(dt).is_some() {
+                        spikes += 1;
+                        assert_eq!(izh.membrane_potential(), izh.c);
+                    }
+                }
+                assert!(spikes > 5, "only {} spikes at dt={} substeps={}", spikes, dt_ms, substeps);
+            }
+        }
+        assert_eq!(IzhikevichNeuron::default().substeps, 2);
+    }
+
+    #[test]
+    fn test_metabolic_cost_grows_with_activity() {
+        use crate::time::TimeStepExt;
+        let dt = TimeStep::from_ms(0.1);
+        let mut idle = LIFNeuron::new(NeuronId(0));
+        let mut active = LIFNeuron::new(NeuronId(1));
+        for _ in 0..1000 {
+            idle.integrate(0.0, dt);
+            idle.update(dt);
+            active.integrate(5.0, dt);
+            active.update(dt);
+        }
+        assert_eq!(idle.metabolic_cost(), 0.0);
+        assert!(active.metabolic_cost() >= SPIKE_METABOLIC_COST);
+
+        let dt = TimeStep::from_ms(0.01);
+        let mut pool = NeuronPool::new();
+        let idle = pool.add_neuron(DetailedHHNeuron::from_spiking_networks_defaults(NeuronId(0)));
+        let active = pool.add_neuron(DetailedHHNeuron::from_spiking_networks_defaults(NeuronId(1)));
+        for _ in 0..2000 {
+            for (index, current) in [(idle, 0.0), (active, 10.0)] {
+                let neuron = pool.get_neuron_mut(index).unwrap();
+                neuron.integrate(current, dt);
+                neuron.update(dt);
+            }
+        }
+        let idle_cost = pool.get_neuron(idle).unwrap().metabolic_cost();
+        let active_cost = pool.get_neuron(active).unwrap().metabolic_cost();
+        assert!(active_cost > idle_cost);
+        assert!((pool.total_metabolic_cost() - (idle_cost + active_cost)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lif_neuron_is_refractory_after_spiking() {
+        use crate::time::TimeStepExt;
+        let dt = TimeStep::from_ms(0.1);
+        let mut pool = NeuronPool::new();
+        let spiking = pool.add_neuron(LIFNeuron::new(NeuronId(0)));
+        pool.add_neuron(LIFNeuron::new(NeuronId(1)));
+        assert_eq!(pool.refractory_count(), 0);
+
+        let neuron = pool.get_neuron_mut(spiking).unwrap();
+        assert!(!neuron.is_refractory());
+        let threshold = neuron.threshold();
+        neuron.set_membrane_potential(threshold + 1.0);
+        assert!(neuron.update(dt).is_some());
+        assert!(neuron.is_refractory());
+        assert_eq!(pool.refractory_count(), 1);
+
+        // Models without refractoriness never report it
+        assert!(!IzhikevichNeuron::regular_spiking(NeuronId(2)).is_refractory());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_update_all_parallel_matches_sequential() {
+        use crate::time::TimeStepExt;
+        let dt = TimeStep::from_ms(0.1);
+        let types = [NeuronType::LIF, NeuronType::AdEx, NeuronType::Izhikevich];
+        let mut sequential = NeuronPool::new();
+        for i in 0..300 {
+            sequential.add_neuron(types[i % types.len()].create(NeuronId(i as u32)));
+        }
+        let mut parallel = sequential.clone();
+
+        let mut total_spikes = 0;
+        for step in 0..200 {
+            for pool in [&mut sequential, &mut parallel] {
+                for (i, neuron) in pool.iter_mut().enumerate() {
+                    neuron.integrate(((i + step) % 7) as f64, dt);
+                }
+            }
+            let expected = sequential.update_all(dt);
+            assert_eq!(parallel.update_all_parallel(dt), expected);
+            total_spikes += expected.len();
+        }
+        assert!(total_spikes > 0);
+        for (a, b) in sequential.iter_mut().zip(parallel.iter_mut()) {
+            assert_eq!(a.membrane_potential(), b.membrane_potential());
+        }
+    }
+
     #[test]
     fn test_detailed_lif_neuron() {
         use crate::time::TimeStepExt;
@@ -1007,6 +1514,24 @@ impl Neuron for DetailedIzhikevichNeuron {
         assert_eq!(neuron.membrane_potential(), -75.0); // Should be reset
     }
 
+    #[test]
+    fn test_detailed_models_with_config() {
+        let config = DetailedHHConfig { g_na: 80.0, ..DetailedHHConfig::default() };
+        let hh = DetailedHHNeuron::with_config(NeuronId(3), config);
+        assert_eq!(hh.g_na, 80.0);
+        assert_eq!(hh.g_k, DetailedHHConfig::default().g_k);
+        assert_eq!(hh.id(), NeuronId(3));
+        assert_eq!(
+            DetailedHHNeuron::with_config(NeuronId(0), DetailedHHConfig::default()),
+            DetailedHHNeuron::from_spiking_networks_defaults(NeuronId(0))
+        );
+
+        let config = DetailedLIFConfig { tau_membrane: 5.0, resting_potential: -70.0, ..DetailedLIFConfig::default() };
+        let lif = DetailedLIFNeuron::with_config(NeuronId(1), config);
+        assert_eq!(lif.tau_membrane, 5.0);
+        assert_eq!(lif.membrane_potential(), -70.0);
+    }
+
     #[test]
     fn test_detailed_hh_neuron() {
         use crate::time::TimeStepExt;
@@ -194,10 +194,22 @@ impl AtomicFlag {
         !current
     }
 
-    /// Test and set operation - set to true if currently false
+    /// Compare and exchange - store `new` if current value equals `expected`.
+    /// Returns `Ok(previous)` on success and `Err(actual)` on failure.
+    #[inline]
+    pub fn compare_exchange(&self, expected: bool, new: bool, success: Ordering, failure: Ordering) -> core::result::Result<bool, bool> {
+        self.inner.compare_exchange(expected, new, success, failure)
+    }
+
+    /// Test and set operation - set to true if currently false.
+    /// Returns the previous value, so exactly one caller observes `false`
+    /// (useful for lock-free one-shot initialization).
     #[inline]
     pub fn test_and_set(&self) -> bool {
-        self.inner.compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed).is_err()
+        match self.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(prev) => prev,
+            Err(actual) => actual,
+        }
     }
 }
 
@@ -272,5 +284,37 @@ mod tests {
         
         assert!(!flag.test_and_set());
         assert!(flag.test_and_set());
+
+        assert_eq!(flag.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire), core::result::Result::Err(true));
+        assert_eq!(flag.compare_exchange(true, false, Ordering::AcqRel, Ordering::Acquire), core::result::Result::Ok(true));
+        assert!(!flag.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_atomic_flag_test_and_set_single_winner() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let flag = Arc::new(AtomicFlag::new(false));
+        let winners = Arc::new(AtomicCounter::new(0));
+
+        let handles: Vec<_> = (0..16)
+            .map(|_| {
+                let flag = Arc::clone(&flag);
+                let winners = Arc::clone(&winners);
+                thread::spawn(move || {
+                    if !flag.test_and_set() {
+                        winners.increment();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(winners.load(Ordering::Relaxed), 1);
+        assert!(flag.load(Ordering::Relaxed));
     }
 }
\ No newline at end of file
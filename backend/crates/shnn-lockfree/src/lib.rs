@@ -22,6 +22,9 @@ pub mod queue;
 /// Lock-free stack implementations
 pub mod stack;
 
+/// Overwriting ring buffer for recent spike history
+pub mod ring_buffer;
+
 /// Memory ordering utilities
 pub mod ordering;
 
@@ -32,6 +35,7 @@ pub mod epoch;
 pub use atomic::{AtomicFloat, AtomicCounter, AtomicFlag};
 pub use queue::{SPSCQueue, MPSCQueue, MPMCQueue, BoundedMPMCQueue};
 pub use stack::LockFreeStack;
+pub use ring_buffer::SpikeRingBuffer;
 pub use ordering::MemoryOrdering;
 
 /// Common error types for lock-free operations
@@ -0,0 +1,275 @@
+//! Lock-free overwriting ring buffer for recent spike history
+//!
+//! Producers never block on a full buffer: each push claims the next ticket and
+//! overwrites whatever the slot held one lap earlier. A single consumer reads a
+//! consistent copy of the most recent items with [`SpikeRingBuffer::snapshot`].
+
+use core::sync::atomic::{fence, AtomicBool, AtomicUsize, Ordering};
+use core::mem::MaybeUninit;
+use core::cell::UnsafeCell;
+use core::ptr;
+use crate::{Result, LockFreeError, ordering::Backoff};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Fixed-capacity, multi-producer ring buffer that overwrites the oldest entries
+///
+/// Each slot carries a stamp encoding which lap of the buffer it holds: for
+/// ticket `t` with lap base `b = t & !(capacity - 1)`, `b + 1` means the ticket
+/// is being written and `b + capacity` means it is published. Stamps use wrapping
+/// arithmetic and are compared by wrapping distance, so tickets may wrap around
+/// `usize::MAX`.
+/// Readers copy a slot and re-check its stamp (seqlock style), so `T` is
+/// restricted to `Copy` types such as spike events.
+///
+/// Notes:
+/// - Capacity must be a power of two (≥ 2).
+/// - `push` never fails; a push that loses the slot to a newer lap is dropped,
+///   since the newer item would have overwritten it anyway.
+/// - `snapshot` is intended for a single consumer; it does not remove items.
+pub struct SpikeRingBuffer<T> {
+    buffer: Vec<Slot<T>>,
+    mask: usize,
+    head: AtomicUsize,
+    /// Ticket of the first push
+    origin: usize,
+    /// Set once every slot has been claimed, so the whole buffer is in the window
+    full: AtomicBool,
+}
+
+struct Slot<T> {
+    stamp: AtomicUsize,
+    val: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T: Copy> SpikeRingBuffer<T> {
+    /// Create a new ring buffer with the given capacity (must be power of two)
+    pub fn with_capacity(capacity: usize) -> Result<Self> {
+        Self::with_capacity_from(capacity, 0)
+    }
+
+    /// Ring buffer whose first push takes ticket `origin`
+    fn with_capacity_from(capacity: usize, origin: usize) -> Result<Self> {
+        if capacity < 2 || !capacity.is_power_of_two() {
+            return Err(LockFreeError::InvalidCapacity);
+        }
+        let mask = capacity - 1;
+
+        let mut buffer = Vec::with_capacity(capacity);
+        for index in 0..capacity {
+            // Mark each slot as published one lap before its first ticket
+            let first = origin.wrapping_add(index.wrapping_sub(origin) & mask);
+            let stamp = Self::published(first.wrapping_sub(capacity), mask);
+            buffer.push(Slot {
+                stamp: AtomicUsize::new(stamp),
+                val: UnsafeCell::new(MaybeUninit::uninit()),
+            });
+        }
+
+        Ok(Self {
+            buffer,
+            mask,
+            head: AtomicUsize::new(origin),
+            origin,
+            full: AtomicBool::new(false),
+        })
+    }
+
+    /// Stamp of a slot while `ticket` is being written (always odd)
+    fn writing(ticket: usize, mask: usize) -> usize {
+        (ticket & !mask) | 1
+    }
+
+    /// Stamp of a slot once `ticket` is published (always even)
+    fn published(ticket: usize, mask: usize) -> usize {
+        (ticket & !mask).wrapping_add(mask + 1)
+    }
+
+    /// Push an item, overwriting the oldest entry once the buffer is full
+    pub fn push(&self, item: T) {
+        let ticket = self.head.fetch_add(1, Ordering::AcqRel);
+        if ticket.wrapping_sub(self.origin) == self.mask {
+            self.full.store(true, Ordering::Release);
+        }
+        // Safety: index in bounds
+        let slot = unsafe { self.buffer.get_unchecked(ticket & self.mask) };
+        let writing = Self::writing(ticket, self.mask);
+        let mut backoff = Backoff::new();
+
+        loop {
+            let stamp = slot.stamp.load(Ordering::Acquire);
+            if (stamp.wrapping_sub(writing) as isize) > 0 {
+                // A newer lap already claimed this slot; our item is stale
+                return;
+            }
+            if stamp % 2 == 1 {
+                // An older lap is mid-write; wait for it to publish
+                backoff.backoff();
+                continue;
+            }
+            if slot
+                .stamp
+                .compare_exchange_weak(stamp, writing, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                break;
+            }
+            backoff.backoff();
+        }
+
+        // Order the odd stamp before the data so readers never see new data
+        // under the old published stamp
+        fence(Ordering::Release);
+        unsafe { ptr::write_volatile(slot.val.get(), MaybeUninit::new(item)); }
+        slot.stamp.store(Self::published(ticket, self.mask), Ordering::Release);
+    }
+
+    /// Copy the most recent items, oldest first
+    ///
+    /// Slots that are mid-write or already overwritten by a later push are
+    /// skipped, so the result never exceeds `capacity()` items.
+    pub fn snapshot(&self) -> Vec<T> {
+        let head = self.head.load(Ordering::Acquire);
+        let window = self.window(head);
+        let mut out = Vec::with_capacity(window);
+
+        for offset in (1..=window).rev() {
+            let ticket = head.wrapping_sub(offset);
+            // Safety: index in bounds
+            let slot = unsafe { self.buffer.get_unchecked(ticket & self.mask) };
+            let published = Self::published(ticket, self.mask);
+            if slot.stamp.load(Ordering::Acquire) != published {
+                continue;
+            }
+            let val = unsafe { ptr::read_volatile(slot.val.get()) };
+            fence(Ordering::Acquire);
+            if slot.stamp.load(Ordering::Relaxed) == published {
+                // Safety: the stamp was unchanged across the copy, so the
+                // value was fully written for this ticket
+                out.push(unsafe { val.assume_init() });
+            }
+        }
+
+        out
+    }
+
+    /// Number of claimed tickets below `head` that can still be in the buffer
+    fn window(&self, head: usize) -> usize {
+        if self.full.load(Ordering::Acquire) {
+            self.capacity()
+        } else {
+            head.wrapping_sub(self.origin).min(self.capacity())
+        }
+    }
+
+    /// Number of items currently retained (racy)
+    pub fn len(&self) -> usize {
+        self.window(self.head.load(Ordering::Acquire))
+    }
+
+    /// Returns true if nothing has been pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total number of pushes since creation, including overwritten items
+    /// (wraps around after `usize::MAX` pushes)
+    pub fn total_pushed(&self) -> usize {
+        self.head.load(Ordering::Acquire).wrapping_sub(self.origin)
+    }
+
+    /// Capacity
+    pub fn capacity(&self) -> usize {
+        self.mask + 1
+    }
+}
+
+unsafe impl<T: Send> Send for SpikeRingBuffer<T> {}
+unsafe impl<T: Send> Sync for SpikeRingBuffer<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_keeps_most_recent() {
+        assert_eq!(
+            SpikeRingBuffer::<u32>::with_capacity(6).err(),
+            Some(LockFreeError::InvalidCapacity)
+        );
+
+        let ring = SpikeRingBuffer::with_capacity(4).unwrap();
+        assert!(ring.is_empty());
+        assert!(ring.snapshot().is_empty());
+
+        for i in 0..10u32 {
+            ring.push(i);
+        }
+
+        assert_eq!(ring.len(), 4);
+        assert_eq!(ring.total_pushed(), 10);
+        assert_eq!(ring.snapshot(), vec![6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_ring_buffer_tickets_wrap_around() {
+        let ring = SpikeRingBuffer::with_capacity_from(4, usize::MAX - 5).unwrap();
+        assert!(ring.is_empty());
+        assert!(ring.snapshot().is_empty());
+
+        ring.push(0u32);
+        ring.push(1);
+        assert_eq!(ring.snapshot(), vec![0, 1]);
+
+        // Tickets run past usize::MAX and back to 0
+        for i in 2..10u32 {
+            ring.push(i);
+        }
+        assert_eq!(ring.len(), 4);
+        assert_eq!(ring.total_pushed(), 10);
+        assert_eq!(ring.snapshot(), vec![6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_ring_buffer_concurrent_producers() {
+        use std::sync::Arc;
+        use std::thread;
+
+        const PRODUCERS: usize = 4;
+        const PER_PRODUCER: usize = 2000;
+        const CAPACITY: usize = 64;
+
+        let ring = Arc::new(SpikeRingBuffer::<(usize, usize)>::with_capacity(CAPACITY).unwrap());
+
+        let handles: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let ring = Arc::clone(&ring);
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        ring.push((p, i));
+                    }
+                })
+            })
+            .collect();
+
+        while ring.total_pushed() < PRODUCERS * PER_PRODUCER {
+            assert!(ring.snapshot().len() <= CAPACITY);
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let snapshot = ring.snapshot();
+        assert_eq!(snapshot.len(), CAPACITY);
+        for (p, i) in snapshot {
+            assert!(p < PRODUCERS);
+            // At most CAPACITY - 1 later pushes exist, so only each producer's tail survives
+            assert!(i >= PER_PRODUCER - CAPACITY, "stale item {} from producer {}", i, p);
+        }
+    }
+}
@@ -205,6 +205,7 @@ impl PyGraphNetwork {
 #[pyclass(name = "MatrixNetwork")]
 pub struct PyMatrixNetwork {
     inner: MatrixNetwork,
+    with_delays: bool,
 }
 
 #[pymethods]
@@ -212,12 +213,50 @@ impl PyMatrixNetwork {
     #[new]
     #[pyo3(signature = (max_neurons, with_delays=false))]
     fn new(max_neurons: usize, with_delays: Option<bool>) -> Self {
-        let inner = if with_delays.unwrap_or(false) {
+        let with_delays = with_delays.unwrap_or(false);
+        let inner = if with_delays {
             MatrixNetwork::with_delays(max_neurons)
         } else {
             MatrixNetwork::new(max_neurons)
         };
-        Self { inner }
+        Self { inner, with_delays }
+    }
+
+    /// Register a neuron, returning its matrix index
+    fn add_neuron(&mut self, neuron_id: u32) -> PyResult<usize> {
+        self.inner.add_neuron(NeuronId::new(neuron_id))
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to add neuron: {:?}", e)))
+    }
+
+    /// Neuron IDs in matrix index order (row/column order of `weights_matrix`)
+    fn neuron_ids(&self) -> Vec<u32> {
+        (0..self.inner.neuron_count())
+            .filter_map(|idx| self.inner.get_neuron_id(idx))
+            .map(|id| id.raw())
+            .collect()
+    }
+
+    /// Full weight matrix as a nested list, `matrix[i][j]` is the weight from
+    /// the i-th to the j-th registered neuron (see `neuron_ids`)
+    fn weights_matrix(&self) -> PyResult<PyObject> {
+        let matrix = self.collect_matrix(|source, target| {
+            self.inner.get_weight(source, target)
+                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get weight: {:?}", e)))
+        })?;
+        Python::with_gil(|py| Ok(matrix.to_object(py)))
+    }
+
+    /// Full delay matrix in milliseconds as a nested list (same layout as `weights_matrix`)
+    fn delays_matrix(&self) -> PyResult<PyObject> {
+        if !self.with_delays {
+            return Err(PyValueError::new_err("Delays are not enabled for this network (use with_delays=True)"));
+        }
+        let matrix = self.collect_matrix(|source, target| {
+            self.inner.get_delay(source, target)
+                .map(|d| d.as_nanos() as f32 / 1_000_000.0)
+                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get delay: {:?}", e)))
+        })?;
+        Python::with_gil(|py| Ok(matrix.to_object(py)))
     }
 
     /// Set weight between neurons
@@ -301,6 +340,21 @@ impl PyMatrixNetwork {
     }
 }
 
+impl PyMatrixNetwork {
+    /// Evaluate `entry` for every (source, target) pair of registered neurons
+    fn collect_matrix<F>(&self, entry: F) -> PyResult<Vec<Vec<f32>>>
+    where
+        F: Fn(NeuronId, NeuronId) -> PyResult<f32>,
+    {
+        let ids: Vec<NeuronId> = (0..self.inner.neuron_count())
+            .filter_map(|idx| self.inner.get_neuron_id(idx))
+            .collect();
+        ids.iter()
+            .map(|&source| ids.iter().map(|&target| entry(source, target)).collect())
+            .collect()
+    }
+}
+
 /// Python wrapper for SparseMatrixNetwork
 #[pyclass(name = "SparseMatrixNetwork")]
 pub struct PySparseMatrixNetwork {
@@ -28,8 +28,8 @@ impl PyNDFHypergraph {
     #[pyo3(signature = (id, sources, targets))]
     fn add_hyperedge(&mut self, id: u32, sources: Vec<u32>, targets: Vec<u32>) -> PyResult<()> {
         let hid = HyperedgeId::from(id);
-        let src_ids: Vec<NeuronId> = sources.into_iter().map(NeuronId::from).collect();
-        let tgt_ids: Vec<NeuronId> = targets.into_iter().map(NeuronId::from).collect();
+        let src_ids = NeuronId::from_slice(&sources);
+        let tgt_ids = NeuronId::from_slice(&targets);
 
         let edge = Hyperedge::new(hid, src_ids, tgt_ids, HyperedgeType::ManyToOne)
             .map_err(|e| PyRuntimeError::new_err(format!("Failed to create hyperedge: {}", e)))?;
@@ -285,6 +285,17 @@ impl PyFormatConverter {
         binary_array
     }
 
+    /// Convert (time, amplitude) spike events to binary array, keeping only
+    /// spikes whose amplitude is at least `min_amplitude`
+    fn spike_events_to_binary(&self, events: Vec<(f64, f32)>, duration: f64, resolution: f64, min_amplitude: f32) -> Vec<u8> {
+        let strong: Vec<f64> = events.into_iter()
+            .filter(|&(_, amplitude)| amplitude >= min_amplitude)
+            .map(|(time, _)| time)
+            .collect();
+
+        self.spike_times_to_binary(strong, duration, resolution)
+    }
+
     /// Convert binary array to spike times
     fn binary_to_spike_times(&self, binary_array: Vec<u8>, resolution: f64) -> Vec<f64> {
         let mut spike_times = Vec::new();
@@ -321,4 +332,18 @@ impl PyFormatConverter {
             .map(|binary_array| self.binary_to_spike_times(binary_array, resolution))
             .collect()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spike_events_to_binary_thresholds_amplitude() {
+        let converter = PyFormatConverter::new();
+        // Weak spike in bin 2, strong spike in adjacent bin 3
+        let events = vec![(0.0025, 0.2), (0.0035, 1.5)];
+        let binary = converter.spike_events_to_binary(events, 0.005, 0.001, 1.0);
+        assert_eq!(binary, vec![0, 0, 0, 1, 0]);
+    }
+}
@@ -18,6 +18,24 @@ use shnn_storage::{vevt::{VEVTEvent, encode_vevt}, StreamId, Time as StorageTime
 
 use std::path::PathBuf;
 
+/// Accepted values for the string options of `compile_to_file`
+const NEURON_CHOICES: &[&str] = &["lif", "adex", "izhikevich"];
+const PLASTICITY_CHOICES: &[&str] = &["stdp", "none"];
+const TOPOLOGY_CHOICES: &[&str] = &["fully-connected", "random", "small-world"];
+const STIMULUS_CHOICES: &[&str] = &["poisson", "constant", "none"];
+
+/// Parse a string option, raising `ValueError` with the valid choices instead of
+/// silently falling back to the default variant
+fn parse_choice<T: std::str::FromStr>(option: &str, value: &str, valid: &[&str]) -> PyResult<T> {
+    let invalid = || PyValueError::new_err(format!(
+        "unknown {} '{}' (valid: {})", option, value, valid.join(", ")
+    ));
+    if !valid.contains(&value) {
+        return Err(invalid());
+    }
+    value.parse().map_err(|_| invalid())
+}
+
 /// Python wrapper for NIR operations
 #[pyclass(name = "NIRCompiler")]
 pub struct PyNIRCompiler;
@@ -61,17 +79,22 @@ impl PyNIRCompiler {
         record_potentials: Option<bool>,
         seed: Option<u64>,
     ) -> PyResult<()> {
+        let neurons = parse_choice("neurons", neurons.as_deref().unwrap_or("lif"), NEURON_CHOICES)?;
+        let plasticity = parse_choice("plasticity", plasticity.as_deref().unwrap_or("stdp"), PLASTICITY_CHOICES)?;
+        let topology = parse_choice("topology", topology.as_deref().unwrap_or("fully-connected"), TOPOLOGY_CHOICES)?;
+        let stimulus = parse_choice("stimulus", stimulus.as_deref().unwrap_or("poisson"), STIMULUS_CHOICES)?;
+
         let args = NirCompile {
             output: PathBuf::from(output_path),
-            neurons: neurons.unwrap_or("lif".to_string()).parse().unwrap_or_default(),
-            plasticity: plasticity.unwrap_or("stdp".to_string()).parse().unwrap_or_default(),
+            neurons,
+            plasticity,
             inputs: inputs.unwrap_or(10),
             hidden: hidden.unwrap_or(50),
             outputs: outputs.unwrap_or(5),
-            topology: topology.unwrap_or("fully-connected".to_string()).parse().unwrap_or_default(),
+            topology,
             steps: steps.unwrap_or(10000),
             dt_us: dt_us.unwrap_or(100),
-            stimulus: stimulus.unwrap_or("poisson".to_string()).parse().unwrap_or_default(),
+            stimulus,
             stimulus_rate: stimulus_rate.unwrap_or(20.0),
             record_potentials: record_potentials.unwrap_or(false),
             seed,
@@ -265,7 +288,17 @@ impl PyNIRCompiler {
             seed, // seed
         ));
 
-        Ok(module.to_text())
+        // Round-trip through the parser and verifier so builder bugs surface here
+        // instead of as text that fails to load later
+        let text = module.to_text();
+        let reparsed = parse_text(&text).map_err(|e| PyRuntimeError::new_err(format!(
+            "generated NIR module does not re-parse: {:?}", e
+        )))?;
+        verify_module(&reparsed).map_err(|e| PyRuntimeError::new_err(format!(
+            "generated NIR module failed verification: {:?}", e
+        )))?;
+
+        Ok(text)
     }
 }
 
@@ -654,6 +654,40 @@ class TestThreadingSafety(unittest.TestCase):
         # Note: We can't easily verify exact spike count due to internal processing
 
 
+class TestMatrixNetworkExport(unittest.TestCase):
+    """Test dense matrix extraction from MatrixNetwork"""
+    
+    def setUp(self):
+        if not SHNN_AVAILABLE:
+            self.skipTest("SHNN Python module not available")
+    
+    def test_weights_and_delays_matrix(self):
+        """Weights/delays set pairwise come back at the right indices"""
+        network = shnn.MatrixNetwork(4, with_delays=True)
+        for neuron_id in [10, 11, 12]:
+            network.add_neuron(neuron_id)
+        
+        network.set_weight(10, 11, 0.5, delay=2.0)
+        network.set_weight(12, 10, -0.25)
+        network.set_weight(11, 11, 1.5)
+        
+        self.assertEqual(network.neuron_ids(), [10, 11, 12])
+        weights = network.weights_matrix()
+        self.assertEqual(len(weights), 3)
+        self.assertTrue(all(len(row) == 3 for row in weights))
+        self.assertAlmostEqual(weights[0][1], 0.5)
+        self.assertAlmostEqual(weights[2][0], -0.25)
+        self.assertAlmostEqual(weights[1][1], 1.5)
+        self.assertEqual(weights[1][0], 0.0)
+        
+        delays = network.delays_matrix()
+        self.assertAlmostEqual(delays[0][1], 2.0)
+        self.assertEqual(delays[2][0], 0.0)
+        
+        with self.assertRaises(ValueError):
+            shnn.MatrixNetwork(4).delays_matrix()
+
+
 class TestErrorHandling(unittest.TestCase):
     """Test error handling in Python bindings"""
     
@@ -692,6 +726,29 @@ class TestErrorHandling(unittest.TestCase):
         # Invalid neuron parameters
         with self.assertRaises(Exception):
             PyLIFNeuron(tau_membrane=-1.0)  # Negative time constant
+    
+    def test_nir_compile_rejects_unknown_options(self):
+        """Misspelled model names raise instead of falling back to LIF"""
+        compiler = shnn.NIRCompiler()
+        
+        with self.assertRaises(ValueError) as ctx:
+            compiler.compile_to_file("out.nir", neurons="adexx")
+        self.assertIn("izhikevich", str(ctx.exception))  # names the valid options
+        
+        with self.assertRaises(ValueError):
+            compiler.compile_to_file("out.nir", topology="fully_connected")
+    
+    def test_nir_basic_module_without_inputs_is_valid(self):
+        """A module with no inputs is either well-formed or rejected clearly"""
+        compiler = shnn.NIRCompiler()
+        
+        try:
+            text = compiler.create_basic_module(num_inputs=0)
+        except RuntimeError as e:
+            self.assertIn("generated NIR module", str(e))
+        else:
+            self.assertIsInstance(text, str)
+            self.assertTrue(text.strip())
 
 
 if __name__ == '__main__':
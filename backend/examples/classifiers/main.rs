@@ -3,9 +3,32 @@ use std::io::{BufWriter, Write};
 extern crate spiking_neural_networks;
 use spiking_neural_networks::{
     error::SpikingNeuralNetworksError,
-    classifiers::{Classifier, Regressor, STDPClassifier, RSTDPClassifier, LSMClassifier, RSTDPRegressor, metrics},
+    classifiers::{
+        Classifier, ClassifierNeuron, Regressor, STDPClassifier, RSTDPClassifier, LSMClassifier,
+        RSTDPRegressor, RSTDPParams, metrics,
+    },
+    neuron::integrate_and_fire::{
+        IzhikevichNeuron, LeakyIntegrateAndFireNeuron, AdaptiveExpLeakyIntegrateAndFireNeuron,
+    },
 };
 
+/// Trains an STDP classifier on the given base neuron, maps its output neurons
+/// to labels and returns its test accuracy
+fn run_stdp<N: ClassifierNeuron>(
+    base_neuron: &N,
+    train_inputs: &[Vec<f32>],
+    train_labels: &[usize],
+    test_inputs: &[Vec<f32>],
+) -> Result<f32, SpikingNeuralNetworksError> {
+    let mut stdp_classifier = STDPClassifier::with_model(3, 3, base_neuron);
+    stdp_classifier.train(train_inputs, train_labels)?;
+    // Align output neurons with labels so accuracy is measured against true classes
+    stdp_classifier.fit_label_mapping(train_inputs, train_labels)?;
+
+    let stdp_predictions: Vec<usize> = test_inputs.iter().map(|inp| stdp_classifier.predict(inp)).collect();
+    Ok(metrics::accuracy(&stdp_predictions, &[0, 1, 2]))
+}
+
 /// Example usage of classifiers and regressors
 fn main() -> Result<(), SpikingNeuralNetworksError> {
     println!("Training STDP-based unsupervised classifier...");
@@ -20,22 +43,33 @@ fn main() -> Result<(), SpikingNeuralNetworksError> {
     ];
     let train_labels = vec![0, 1, 2, 0, 1]; // For supervised, but STDP ignores
 
-    // STDP Classifier
-    let mut stdp_classifier = STDPClassifier::new(3, 3);
-    stdp_classifier.train(&train_inputs, &train_labels)?;
-
     let test_inputs = vec![
         vec![1.0, 0.0, 0.0],
         vec![0.0, 1.0, 0.0],
         vec![0.0, 0.0, 1.0],
     ];
-    let stdp_predictions: Vec<usize> = test_inputs.iter().map(|inp| stdp_classifier.predict(inp)).collect();
-    let stdp_accuracy = metrics::accuracy(&stdp_predictions, &[0, 1, 2]);
-    println!("STDP Classifier Accuracy: {:.2}", stdp_accuracy);
+
+    // STDP Classifier, base neuron selected with `--neuron-model izhikevich|lif|adex`
+    let args: Vec<String> = std::env::args().collect();
+    let neuron_model = args.iter()
+        .position(|arg| arg == "--neuron-model")
+        .and_then(|i| args.get(i + 1))
+        .map(|model| model.to_lowercase())
+        .unwrap_or_else(|| String::from("izhikevich"));
+    let stdp_accuracy = match neuron_model.as_str() {
+        "lif" => run_stdp(&LeakyIntegrateAndFireNeuron::default_impl(), &train_inputs, &train_labels, &test_inputs)?,
+        "adex" => run_stdp(&AdaptiveExpLeakyIntegrateAndFireNeuron::default_impl(), &train_inputs, &train_labels, &test_inputs)?,
+        "izhikevich" => run_stdp(&IzhikevichNeuron::default_impl(), &train_inputs, &train_labels, &test_inputs)?,
+        other => {
+            eprintln!("Unknown --neuron-model '{}', expected izhikevich, lif, or adex", other);
+            std::process::exit(1);
+        }
+    };
+    println!("STDP Classifier ({}) Accuracy: {:.2}", neuron_model, stdp_accuracy);
 
     // R-STDP Classifier
     println!("Training R-STDP classifier...");
-    let mut rstdp_classifier = RSTDPClassifier::new(3, 3);
+    let mut rstdp_classifier = RSTDPClassifier::new(3, 3, RSTDPParams::default());
     rstdp_classifier.train(&train_inputs, &train_labels)?;
 
     let rstdp_predictions: Vec<usize> = test_inputs.iter().map(|inp| rstdp_classifier.predict(inp)).collect();
@@ -54,7 +88,7 @@ fn main() -> Result<(), SpikingNeuralNetworksError> {
     // R-STDP Regressor
     println!("Training R-STDP regressor...");
     let train_targets = vec![1.0, 2.0, 3.0, 1.5, 2.5];
-    let mut regressor = RSTDPRegressor::new(3);
+    let mut regressor = RSTDPRegressor::new(3, RSTDPParams::default());
     regressor.train(&train_inputs, &train_targets)?;
 
     let test_targets = vec![1.0, 2.0, 3.0];
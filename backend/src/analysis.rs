@@ -0,0 +1,45 @@
+//! Spike train analysis utilities
+//!
+//! Shared readouts computed from spike counts so regions and classifiers
+//! report firing rates with the same semantics.
+
+
+/// Converts per-neuron spike counts observed over `window_ms` milliseconds into
+/// firing rates in Hz, a non-positive window yields rates of 0
+pub fn firing_rate(spike_counts: &[u64], window_ms: f32) -> Vec<f32> {
+    if window_ms <= 0. {
+        return vec![0.; spike_counts.len()];
+    }
+
+    let window_s = window_ms / 1000.;
+
+    spike_counts.iter().map(|&count| count as f32 / window_s).collect()
+}
+
+/// Mean firing rate in Hz across the population, total spikes divided by the
+/// number of neurons and the window length, 0 for an empty population
+pub fn population_rate(spike_counts: &[u64], window_ms: f32) -> f32 {
+    if spike_counts.is_empty() || window_ms <= 0. {
+        return 0.;
+    }
+
+    let total: u64 = spike_counts.iter().sum();
+
+    total as f32 / (spike_counts.len() as f32 * window_ms / 1000.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_from_known_spike_counts() {
+        // 500 ms window: 10 spikes is 20 Hz, 25 spikes is 50 Hz
+        let counts = [10, 25, 0];
+        assert_eq!(firing_rate(&counts, 500.), vec![20., 50., 0.]);
+        assert!((population_rate(&counts, 500.) - 70. / 3.).abs() < 1e-4);
+
+        assert_eq!(firing_rate(&counts, 0.), vec![0.; 3]);
+        assert_eq!(population_rate(&[], 500.), 0.);
+    }
+}
@@ -4,12 +4,19 @@
 //! R-STDP classifiers/regressors with reward optimization, and LSM-based models.
 //! Includes training algorithms, evaluation metrics, and integration with digital twin.
 
-use crate::neuron::integrate_and_fire::IzhikevichNeuron;
-use crate::neuron::iterate_and_spike::{ApproximateNeurotransmitter, ApproximateReceptor, IonotropicNeurotransmitterType};
+use crate::neuron::integrate_and_fire::{
+    IzhikevichNeuron, LeakyIntegrateAndFireNeuron, AdaptiveExpLeakyIntegrateAndFireNeuron,
+};
+use crate::neuron::iterate_and_spike::{
+    ApproximateNeurotransmitter, ApproximateReceptor, IonotropicNeurotransmitterType, IsSpiking, IterateAndSpike,
+    Timestep,
+};
 use crate::neuron::plasticity::{STDP, RewardModulatedSTDP, TraceRSTDP};
 use crate::neuron::{Lattice, AdjacencyMatrix, SpikeHistory, RewardModulatedLattice};
+use crate::graph::{Graph, AdjacencyList};
 use crate::error::SpikingNeuralNetworksError;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 /// Trait for classifiers
 pub trait Classifier {
@@ -18,6 +25,92 @@ pub trait Classifier {
 
     /// Predict class for a single input
     fn predict(&self, input: &[f32]) -> usize;
+
+    /// Restores the classifier to its freshly constructed state (initial weights,
+    /// unfitted normalization and readout) so it can be retrained from scratch
+    fn reset(&mut self);
+
+    /// Number of classes the classifier distinguishes
+    fn num_classes(&self) -> usize;
+
+    /// Expected length of each input vector
+    fn input_dim(&self) -> usize;
+
+    /// Check an input against `input_dim` before calling `predict`
+    fn validate_input(&self, input: &[f32]) -> Result<(), SpikingNeuralNetworksError> {
+        check_input_dim(self.input_dim(), input)
+    }
+
+    /// Predict class for a single input, returning `DimensionMismatch` instead of
+    /// aliasing features when the input has the wrong length
+    fn try_predict(&self, input: &[f32]) -> Result<usize, SpikingNeuralNetworksError> {
+        self.validate_input(input)?;
+        Ok(self.predict(input))
+    }
+}
+
+/// Errors with `DimensionMismatch` unless `input` has exactly `expected` features
+fn check_input_dim(expected: usize, input: &[f32]) -> Result<(), SpikingNeuralNetworksError> {
+    if input.len() != expected {
+        return Err(SpikingNeuralNetworksError::DimensionMismatch { expected, got: input.len() });
+    }
+    Ok(())
+}
+
+/// Validates every input of a training set before any state is modified
+fn check_training_inputs(expected: usize, inputs: &[Vec<f32>]) -> Result<(), SpikingNeuralNetworksError> {
+    inputs.iter().try_for_each(|input| check_input_dim(expected, input))
+}
+
+/// Winner-take-all selection: index of the highest score. Ties go to the lowest
+/// index, so predictions only depend on neuron order, never on how scores compare
+/// in scan order; an empty input yields 0
+fn winner_index(scores: impl IntoIterator<Item = f32>) -> usize {
+    let mut winner = 0;
+    let mut max_score = f32::NEG_INFINITY;
+    for (i, score) in scores.into_iter().enumerate() {
+        if score > max_score {
+            max_score = score;
+            winner = i;
+        }
+    }
+    winner
+}
+
+/// How the winning output neuron is read from the spikes of a prediction window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadoutMode {
+    /// Neuron with the latest absolute `last_firing_time` once the window has run
+    /// (see [`ClassifierNeuron::activity`]), so a neuron silent during the window can
+    /// still win on an earlier spike; this is the readout classifiers used before
+    /// modes were selectable and favors recency rather than activity
+    #[default]
+    LastFiringTime,
+    /// Neuron whose latest spike inside the window is most recent, a neuron silent
+    /// during the window never beats one that spiked in it
+    LastSpikeInWindow,
+    /// Rate coding, neuron with the most spikes in the window
+    SpikeCount,
+    /// Latency coding, neuron that spikes earliest in the window
+    FirstToFire,
+}
+
+impl ReadoutMode {
+    /// Winning neuron given each neuron's spike raster over the readout window
+    /// (`spikes[neuron][step]`) and its [`ClassifierNeuron::activity`] once the window
+    /// has run; only `LastFiringTime` reads `activity`. For the window modes a silent
+    /// neuron never beats one that spiked, and ties always go to the lowest index
+    pub fn winner(self, spikes: &[Vec<bool>], activity: &[f32]) -> usize {
+        let window_winner = |score: fn(&[bool]) -> Option<f32>| {
+            winner_index(spikes.iter().map(|train| score(train).unwrap_or(f32::NEG_INFINITY)))
+        };
+        match self {
+            ReadoutMode::LastFiringTime => winner_index(activity.iter().copied()),
+            ReadoutMode::LastSpikeInWindow => window_winner(|train| train.iter().rposition(|&s| s).map(|t| t as f32)),
+            ReadoutMode::SpikeCount => window_winner(|train| Some(train.iter().filter(|&&s| s).count() as f32)),
+            ReadoutMode::FirstToFire => window_winner(|train| train.iter().position(|&s| s).map(|t| -(t as f32))),
+        }
+    }
 }
 
 /// Trait for regressors
@@ -27,27 +120,193 @@ pub trait Regressor {
 
     /// Predict value for a single input
     fn predict(&self, input: &[f32]) -> f32;
+
+    /// Restores the regressor to its freshly constructed state (initial weights and
+    /// a zeroed readout) so it can be retrained from scratch
+    fn reset(&mut self);
+
+    /// Predict values for a batch of inputs, equivalent to calling `predict` per sample
+    fn predict_batch(&self, inputs: &[Vec<f32>]) -> Vec<f32> {
+        inputs.iter().map(|input| self.predict(input)).collect()
+    }
+}
+
+/// Neuron models that can serve as the base neuron of an [`STDPClassifier`] lattice
+pub trait ClassifierNeuron: IterateAndSpike<N = IonotropicNeurotransmitterType> {
+    /// Adds an input drive to the membrane potential
+    fn add_input(&mut self, value: f32);
+    /// Activity score used for winner-take-all readout (last firing timestep, 0 if silent)
+    fn activity(&self) -> f32 {
+        self.get_last_firing_time().map(|t| t as f32).unwrap_or(0.)
+    }
+}
+
+macro_rules! impl_classifier_neuron {
+    ($($name:ident),*) => {
+        $(
+            impl ClassifierNeuron for $name<ApproximateNeurotransmitter, ApproximateReceptor> {
+                fn add_input(&mut self, value: f32) {
+                    self.current_voltage += value;
+                }
+            }
+        )*
+    };
+}
+
+impl_classifier_neuron!(IzhikevichNeuron, LeakyIntegrateAndFireNeuron, AdaptiveExpLeakyIntegrateAndFireNeuron);
+
+/// Per-feature scaling applied to classifier inputs before they drive any neurons
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Normalization {
+    /// Feed raw feature values as currents
+    #[default]
+    None,
+    /// Rescale each feature to `[0, 1]` using the training minimum and maximum
+    MinMax,
+    /// Center each feature on the training mean and divide by its standard deviation
+    ZScore,
+}
+
+/// Input scaling fitted on the first training set by `train` and reused by later
+/// `train` calls and by `predict`, so incremental training keeps the scale earlier
+/// samples were learned at; `reset` clears the fit
+#[derive(Debug, Clone, Default)]
+pub struct InputNormalizer {
+    normalization: Normalization,
+    offsets: Vec<f32>,
+    scales: Vec<f32>,
+}
+
+impl InputNormalizer {
+    pub fn new(normalization: Normalization) -> Self {
+        InputNormalizer { normalization, offsets: Vec::new(), scales: Vec::new() }
+    }
+
+    /// Which normalization this scaler applies
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Whether `fit` has computed statistics (never for `Normalization::None`)
+    pub fn is_fitted(&self) -> bool {
+        !self.offsets.is_empty()
+    }
+
+    /// Fitted per-feature offsets (minimum or mean), empty until fitted
+    pub fn offsets(&self) -> &[f32] {
+        &self.offsets
+    }
+
+    /// Fitted per-feature scales (range or standard deviation), empty until fitted
+    pub fn scales(&self) -> &[f32] {
+        &self.scales
+    }
+
+    /// Fits the per-feature statistics on `inputs`; constant features get a scale
+    /// of 1 so they map to 0 instead of dividing by zero
+    pub fn fit(&mut self, inputs: &[Vec<f32>]) {
+        let dim = match inputs.first() {
+            Some(first) if self.normalization != Normalization::None => first.len(),
+            _ => return,
+        };
+        let n = inputs.len() as f32;
+        let column = |j: usize| inputs.iter().map(move |input| input[j]);
+        let (offsets, scales): (Vec<f32>, Vec<f32>) = (0..dim)
+            .map(|j| match self.normalization {
+                Normalization::MinMax => {
+                    let min = column(j).fold(f32::INFINITY, f32::min);
+                    let max = column(j).fold(f32::NEG_INFINITY, f32::max);
+                    (min, max - min)
+                }
+                Normalization::ZScore => {
+                    let mean = column(j).sum::<f32>() / n;
+                    let var = column(j).map(|x| (x - mean).powi(2)).sum::<f32>() / n;
+                    (mean, var.sqrt())
+                }
+                Normalization::None => (0., 1.),
+            })
+            .map(|(offset, scale)| (offset, if scale > 0. { scale } else { 1. }))
+            .unzip();
+        self.offsets = offsets;
+        self.scales = scales;
+    }
+
+    /// Scales `input` with the fitted statistics (unchanged before fitting)
+    pub fn apply(&self, input: &[f32]) -> Vec<f32> {
+        if self.offsets.is_empty() {
+            return input.to_vec();
+        }
+        input
+            .iter()
+            .zip(self.offsets.iter().zip(&self.scales))
+            .map(|(&x, (&offset, &scale))| (x - offset) / scale)
+            .collect()
+    }
 }
 
-/// STDP-based unsupervised classifier using competitive learning
-pub struct STDPClassifier {
-    lattice: Lattice<
-        IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>,
-        AdjacencyMatrix<(usize, usize), f32>,
-        SpikeHistory,
-        STDP,
-        ApproximateNeurotransmitter,
-    >,
+type STDPLattice<N> = Lattice<
+    N,
+    AdjacencyMatrix<(usize, usize), f32>,
+    SpikeHistory,
+    STDP,
+    ApproximateNeurotransmitter,
+>;
+
+/// Runs `lattice` for a readout window of one iteration per `drive` step, adding each
+/// step's per-feature drive to the class neurons first (feature `i` drives neuron
+/// `i % n_classes`), and returns each neuron's spike train over the window
+/// (`spikes[neuron][step]`) and its activity afterwards
+fn run_readout_window<N: ClassifierNeuron>(
+    lattice: &mut STDPLattice<N>,
+    drive: &[Vec<f32>],
+) -> Result<(Vec<Vec<bool>>, Vec<f32>), SpikingNeuralNetworksError> {
+    let n_classes = lattice.grid.len();
+    let mut spikes = vec![Vec::with_capacity(drive.len()); n_classes];
+    for step in drive {
+        for (i, &val) in step.iter().enumerate() {
+            if let Some(neuron) = lattice.get_mut(i % n_classes, 0) {
+                neuron.add_input(val);
+            }
+        }
+        lattice.iterate()?;
+        for (train, neuron) in spikes.iter_mut().zip(&lattice.grid) {
+            train.push(neuron.is_spiking());
+        }
+    }
+    let activity = lattice.grid.iter().map(|neuron| neuron.activity()).collect();
+    Ok((spikes, activity))
+}
+
+/// STDP-based unsupervised classifier using competitive learning,
+/// generic over the base neuron model (Izhikevich by default)
+pub struct STDPClassifier<N: ClassifierNeuron = IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>> {
+    lattice: STDPLattice<N>,
+    /// Lattice as constructed, restored by `reset`
+    initial_lattice: STDPLattice<N>,
     n_classes: usize,
     input_size: usize,
+    /// Label assigned to each output neuron by `fit_label_mapping`, if fitted
+    label_mapping: Option<Vec<usize>>,
+    normalizer: InputNormalizer,
+    readout: ReadoutMode,
+    /// Iterations the lattice runs per training sample and per prediction
+    readout_steps: usize,
+    encoder: InputEncoder,
 }
 
 impl STDPClassifier {
-    /// Create a new STDP classifier
+    /// Create a new STDP classifier on Izhikevich neurons
     pub fn new(input_size: usize, n_classes: usize) -> Self {
-        let base_neuron = IzhikevichNeuron::default_impl();
+        Self::with_model(input_size, n_classes, &IzhikevichNeuron::default_impl())
+    }
+}
+
+impl<N: ClassifierNeuron> STDPClassifier<N> {
+    /// Create a new STDP classifier whose class neurons are copies of `base_neuron`,
+    /// e.g. `LeakyIntegrateAndFireNeuron::default_impl()` for LIF
+    pub fn with_model(input_size: usize, n_classes: usize, base_neuron: &N) -> Self {
         let mut lattice = Lattice::default();
-        lattice.populate(&base_neuron, n_classes, 1).unwrap();
+        lattice.populate(base_neuron, n_classes, 1).unwrap();
         // Connect inputs to classes (fully connected with random weights)
         // Note: This is simplified; in practice, need input connections
         lattice.connect(
@@ -57,77 +316,234 @@ impl STDPClassifier {
         lattice.do_plasticity = true;
         lattice.update_grid_history = true;
 
-        Self { lattice, n_classes, input_size }
+        Self {
+            initial_lattice: lattice.clone(),
+            lattice,
+            n_classes,
+            input_size,
+            label_mapping: None,
+            normalizer: InputNormalizer::default(),
+            readout: ReadoutMode::default(),
+            readout_steps: 1,
+            encoder: InputEncoder::new(InputEncoding::Current),
+        }
+    }
+
+    /// Selects how inputs are scaled before driving the lattice (raw by default)
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalizer = InputNormalizer::new(normalization);
+        self
+    }
+
+    /// Selects how inputs drive the class neurons over each readout window (raw
+    /// currents by default)
+    pub fn with_encoding(mut self, encoding: InputEncoding) -> Self {
+        self.encoder = InputEncoder::new(encoding);
+        self
+    }
+
+    /// Selects how the winner is determined from a window of `steps` iterations, both
+    /// for winner-take-all inhibition during training and for prediction (last firing
+    /// time over a single step by default)
+    pub fn with_readout(mut self, readout: ReadoutMode, steps: usize) -> Self {
+        self.readout = readout;
+        self.readout_steps = steps.max(1);
+        self
+    }
+
+    /// Assigns each output neuron to the label it most often wins for on `inputs`
+    /// (greedy majority vote, ties go to the lowest label), so `predict` returns labels
+    /// rather than raw neuron indices. Call after unsupervised training; neurons that
+    /// never win keep their own index.
+    pub fn fit_label_mapping(&mut self, inputs: &[Vec<f32>], labels: &[usize]) -> Result<(), SpikingNeuralNetworksError> {
+        check_training_inputs(self.input_size, inputs)?;
+        if labels.len() != inputs.len() {
+            return Err(SpikingNeuralNetworksError::DimensionMismatch { expected: inputs.len(), got: labels.len() });
+        }
+
+        let n_labels = labels.iter().max().map(|&l| l + 1).unwrap_or(0);
+        let mut wins = vec![vec![0usize; n_labels]; self.n_classes];
+        for (input, &label) in inputs.iter().zip(labels) {
+            wins[self.winner(input)][label] += 1;
+        }
+
+        let mapping = wins
+            .iter()
+            .enumerate()
+            .map(|(neuron, counts)| {
+                let mut best = (neuron, 0);
+                for (label, &count) in counts.iter().enumerate() {
+                    if count > best.1 {
+                        best = (label, count);
+                    }
+                }
+                best.0
+            })
+            .collect();
+        self.label_mapping = Some(mapping);
+
+        Ok(())
+    }
+
+    /// Label assigned to each output neuron, if `fit_label_mapping` has been called
+    pub fn label_mapping(&self) -> Option<&[usize]> {
+        self.label_mapping.as_deref()
+    }
+
+    /// Index of the output neuron that wins the competition for `input`
+    fn winner(&self, input: &[f32]) -> usize {
+        let mut temp_lattice = self.lattice.clone();
+        let drive = self.encoder.clone().encode_sample(&self.normalizer.apply(input), self.readout_steps, self.dt());
+        let (spikes, activity) = run_readout_window(&mut temp_lattice, &drive).unwrap();
+        self.readout.winner(&spikes, &activity)
+    }
+
+    /// Timestep of the class neurons, used to encode inputs
+    fn dt(&self) -> f32 {
+        self.lattice.grid.first().map(|n| n.get_dt()).unwrap_or(0.1)
+    }
+
+    /// Winner-take-all inhibition after a training window: every neuron but the
+    /// readout's winner is inhibited, returns the winner
+    fn inhibit_losers(&mut self, spikes: &[Vec<bool>], activity: &[f32]) -> usize {
+        let winner = self.readout.winner(spikes, activity);
+        for (i, neuron) in self.lattice.grid.iter_mut().enumerate() {
+            if i != winner {
+                neuron.add_input(-1.0); // Inhibition
+            }
+        }
+        winner
     }
 }
 
-impl Classifier for STDPClassifier {
+impl<N: ClassifierNeuron> Classifier for STDPClassifier<N> {
     fn train(&mut self, inputs: &[Vec<f32>], labels: &[usize]) -> Result<(), SpikingNeuralNetworksError> {
+        check_training_inputs(self.input_size, inputs)?;
+        if !self.normalizer.is_fitted() {
+            self.normalizer.fit(inputs);
+        }
+        let dt = self.dt();
         // Unsupervised: ignore labels, use competitive learning
         for input in inputs {
-            // Set input as external current to neurons (simplified)
-            for (i, &val) in input.iter().enumerate() {
-                if let Some(neuron) = self.lattice.get_mut(i % self.n_classes, 0) {
-                    neuron.current_voltage += val;
-                }
-            }
-            // Run iteration
-            self.lattice.iterate()?;
+            // Encode the input as drive to the neurons (simplified) and run the readout window
+            let drive = self.encoder.encode_sample(&self.normalizer.apply(input), self.readout_steps, dt);
+            let (spikes, activity) = run_readout_window(&mut self.lattice, &drive)?;
             // Apply winner-take-all inhibition (simplified: reduce others)
-            // Find winner
-            let mut max_spike = 0.0;
-            let mut winner = 0;
-            for (i, neuron) in self.lattice.grid.iter().enumerate() {
-                if neuron.last_firing_time > max_spike {
-                    max_spike = neuron.last_firing_time;
-                    winner = i;
-                }
-            }
-            // Inhibit others
-            for (i, neuron) in self.lattice.grid.iter_mut().enumerate() {
-                if i != winner {
-                    neuron.current_voltage -= 1.0; // Inhibition
-                }
-            }
+            self.inhibit_losers(&spikes, &activity);
         }
         Ok(())
     }
 
     fn predict(&self, input: &[f32]) -> usize {
-        // Run prediction
-        let mut temp_lattice = self.lattice.clone();
-        for (i, &val) in input.iter().enumerate() {
-            if let Some(neuron) = temp_lattice.get_mut(i % self.n_classes, 0) {
-                neuron.current_voltage += val;
-            }
+        let winner = self.winner(input);
+        match &self.label_mapping {
+            Some(mapping) => mapping[winner],
+            None => winner,
         }
-        temp_lattice.iterate().unwrap();
-        // Return winner
-        let mut max_spike = 0.0;
-        let mut winner = 0;
-        for (i, neuron) in temp_lattice.grid.iter().enumerate() {
-            if neuron.last_firing_time > max_spike {
-                max_spike = neuron.last_firing_time;
-                winner = i;
-            }
+    }
+
+    fn reset(&mut self) {
+        self.lattice = self.initial_lattice.clone();
+        self.label_mapping = None;
+        self.encoder = InputEncoder::new(self.encoder.encoding);
+        self.normalizer = InputNormalizer::new(self.normalizer.normalization());
+    }
+
+    fn num_classes(&self) -> usize {
+        self.n_classes
+    }
+
+    fn input_dim(&self) -> usize {
+        self.input_size
+    }
+}
+
+/// Reward-modulation settings for the R-STDP models
+#[derive(Debug, Clone, Copy)]
+pub struct RSTDPParams {
+    /// Eligibility trace decay constant (`tau_c`) used when updating each `TraceRSTDP` weight
+    pub tau_c: f32,
+    /// Scales the STDP amplitudes that feed the eligibility trace, and so the
+    /// size of every reward-driven weight update
+    pub learning_rate: f32,
+}
+
+impl Default for RSTDPParams {
+    fn default() -> Self {
+        RSTDPParams {
+            tau_c: RewardModulatedSTDP::default().tau_c,
+            learning_rate: 1.0,
+        }
+    }
+}
+
+impl RSTDPParams {
+    /// Builds the reward modulator used by the lattice from these settings
+    fn modulator(&self) -> RewardModulatedSTDP {
+        let defaults = RewardModulatedSTDP::default();
+        RewardModulatedSTDP {
+            tau_c: self.tau_c,
+            a_plus: defaults.a_plus * self.learning_rate,
+            a_minus: defaults.a_minus * self.learning_rate,
+            ..defaults
+        }
+    }
+}
+
+/// Readout update settings for [`RSTDPRegressor`], applied as
+/// `v = momentum * v + learning_rate * (grad - weight_decay * w); w += v`
+#[derive(Debug, Clone, Copy)]
+pub struct ReadoutParams {
+    /// Step size applied to each readout gradient
+    pub learning_rate: f32,
+    /// Fraction of the previous update carried into the next one (0 disables momentum)
+    pub momentum: f32,
+    /// L2 penalty pulling readout weights towards zero (0 disables decay)
+    pub weight_decay: f32,
+}
+
+impl Default for ReadoutParams {
+    fn default() -> Self {
+        ReadoutParams {
+            learning_rate: 0.01,
+            momentum: 0.,
+            weight_decay: 0.,
+        }
+    }
+}
+
+impl ReadoutParams {
+    /// Applies one momentum step along `grad` (the error-weighted activity) to `weights`
+    fn step(&self, weights: &mut [f32], velocity: &mut [f32], grad: &[f32]) {
+        for ((w, v), &g) in weights.iter_mut().zip(velocity.iter_mut()).zip(grad) {
+            *v = self.momentum * *v + self.learning_rate * (g - self.weight_decay * *w);
+            *w += *v;
         }
-        winner
     }
 }
 
+type RSTDPLattice = RewardModulatedLattice<
+    IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>,
+    AdjacencyMatrix<(usize, usize), TraceRSTDP>,
+    SpikeHistory,
+>;
+
 /// R-STDP classifier with reward optimization
 pub struct RSTDPClassifier {
-    lattice: RewardModulatedLattice<
-        IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>,
-        AdjacencyMatrix<(usize, usize), TraceRSTDP>,
-        SpikeHistory,
-    >,
+    lattice: RSTDPLattice,
+    /// Lattice as constructed, restored by `reset`
+    initial_lattice: RSTDPLattice,
     n_classes: usize,
     input_size: usize,
+    normalizer: InputNormalizer,
+    readout: ReadoutMode,
+    /// Iterations the lattice runs per prediction
+    readout_steps: usize,
+    encoder: InputEncoder,
 }
 
 impl RSTDPClassifier {
-    pub fn new(input_size: usize, n_classes: usize) -> Self {
+    pub fn new(input_size: usize, n_classes: usize, params: RSTDPParams) -> Self {
         let base_neuron = IzhikevichNeuron::default_impl();
         let mut lattice = RewardModulatedLattice::default();
         lattice.populate(&base_neuron, n_classes, 1).unwrap();
@@ -138,18 +554,59 @@ impl RSTDPClassifier {
                 ..TraceRSTDP::default()
             }),
         ).unwrap();
+        lattice.reward_modulator = params.modulator();
         lattice.do_modulation = true;
         lattice.update_graph_history = true;
 
-        Self { lattice, n_classes, input_size }
+        Self {
+            initial_lattice: lattice.clone(),
+            lattice,
+            n_classes,
+            input_size,
+            normalizer: InputNormalizer::default(),
+            readout: ReadoutMode::default(),
+            readout_steps: 1,
+            encoder: InputEncoder::new(InputEncoding::Current),
+        }
+    }
+
+    /// Selects how inputs are scaled before driving the lattice (raw by default)
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalizer = InputNormalizer::new(normalization);
+        self
+    }
+
+    /// Selects how inputs drive the class neurons on each training step and over
+    /// each prediction window (raw currents by default)
+    pub fn with_encoding(mut self, encoding: InputEncoding) -> Self {
+        self.encoder = InputEncoder::new(encoding);
+        self
+    }
+
+    /// Selects how the winner is determined from a prediction window of `steps`
+    /// iterations (last firing time over a single step by default)
+    pub fn with_readout(mut self, readout: ReadoutMode, steps: usize) -> Self {
+        self.readout = readout;
+        self.readout_steps = steps.max(1);
+        self
+    }
+
+    /// Timestep of the class neurons, used to encode inputs
+    fn dt(&self) -> f32 {
+        self.lattice.grid.first().map(|n| n.dt).unwrap_or(0.1)
     }
 }
 
 impl Classifier for RSTDPClassifier {
     fn train(&mut self, inputs: &[Vec<f32>], labels: &[usize]) -> Result<(), SpikingNeuralNetworksError> {
+        check_training_inputs(self.input_size, inputs)?;
+        if !self.normalizer.is_fitted() {
+            self.normalizer.fit(inputs);
+        }
+        let dt = self.dt();
         for (input, &label) in inputs.iter().zip(labels) {
             // Set input
-            for (i, &val) in input.iter().enumerate() {
+            for (i, &val) in self.encoder.encode_step(&self.normalizer.apply(input), dt).iter().enumerate() {
                 if let Some(neuron) = self.lattice.get_mut(i % self.n_classes, 0) {
                     neuron.current_voltage += val;
                 }
@@ -167,41 +624,183 @@ impl Classifier for RSTDPClassifier {
 
     fn predict(&self, input: &[f32]) -> usize {
         let mut temp_lattice = self.lattice.clone();
-        for (i, &val) in input.iter().enumerate() {
-            if let Some(neuron) = temp_lattice.get_mut(i % self.n_classes, 0) {
-                neuron.current_voltage += val;
+        let drive = self.encoder.clone().encode_sample(&self.normalizer.apply(input), self.readout_steps, self.dt());
+        let mut spikes = vec![Vec::with_capacity(self.readout_steps); temp_lattice.grid.len()];
+        for step in &drive {
+            for (i, &val) in step.iter().enumerate() {
+                if let Some(neuron) = temp_lattice.get_mut(i % self.n_classes, 0) {
+                    neuron.current_voltage += val;
+                }
+            }
+            temp_lattice.iterate().unwrap();
+            for (train, neuron) in spikes.iter_mut().zip(&temp_lattice.grid) {
+                train.push(neuron.is_spiking());
+            }
+        }
+        let activity: Vec<f32> = temp_lattice.grid.iter().map(|neuron| neuron.activity()).collect();
+        self.readout.winner(&spikes, &activity)
+    }
+
+    fn reset(&mut self) {
+        self.lattice = self.initial_lattice.clone();
+        self.encoder = InputEncoder::new(self.encoder.encoding);
+        self.normalizer = InputNormalizer::new(self.normalizer.normalization());
+    }
+
+    fn num_classes(&self) -> usize {
+        self.n_classes
+    }
+
+    fn input_dim(&self) -> usize {
+        self.input_size
+    }
+}
+
+/// How input features are turned into drive for the input neurons
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEncoding {
+    /// Each feature value is added to the membrane voltage of its input neuron every step
+    Current,
+    /// Each feature (clamped to `[0, 1]`) sets a Poisson firing rate of up to `max_hz`;
+    /// every input spike adds [`POISSON_SPIKE_DRIVE`] to its input neuron, shifted in
+    /// time by `U(-jitter_ms, jitter_ms)` within the sample window (0 for no jitter)
+    PoissonRate { max_hz: f32, seed: u64, jitter_ms: f32 },
+}
+
+/// Voltage (mV) added to an input neuron per Poisson input spike
+pub const POISSON_SPIKE_DRIVE: f32 = 30.;
+
+/// Stateful per-step encoder for an [`InputEncoding`]
+#[derive(Debug, Clone)]
+pub struct InputEncoder {
+    encoding: InputEncoding,
+    rng: StdRng,
+}
+
+impl InputEncoder {
+    pub fn new(encoding: InputEncoding) -> Self {
+        let seed = match encoding {
+            InputEncoding::PoissonRate { seed, .. } => seed,
+            InputEncoding::Current => 0,
+        };
+        InputEncoder { encoding, rng: StdRng::seed_from_u64(seed) }
+    }
+
+    /// Drive for each feature over one step of `dt` milliseconds
+    pub fn encode_step(&mut self, input: &[f32], dt: f32) -> Vec<f32> {
+        match self.encoding {
+            InputEncoding::Current => input.to_vec(),
+            InputEncoding::PoissonRate { max_hz, .. } => input
+                .iter()
+                .map(|&val| {
+                    // Same per-step firing chance as `PoissonNeuron::from_firing_rate`
+                    let chance = (val.clamp(0., 1.) * max_hz * dt / 1000.).clamp(0., 1.);
+                    if self.rng.gen_bool(chance as f64) { POISSON_SPIKE_DRIVE } else { 0. }
+                })
+                .collect(),
+        }
+    }
+
+    /// Spike times (ms from the start of a sample window of `steps` steps of `dt`
+    /// milliseconds) of each feature's Poisson train, each jittered by
+    /// `U(-jitter_ms, jitter_ms)` and clamped to the window, empty for
+    /// [`InputEncoding::Current`]
+    pub fn spike_times(&mut self, input: &[f32], steps: usize, dt: f32) -> Vec<Vec<f32>> {
+        let mut times = vec![Vec::new(); input.len()];
+        let InputEncoding::PoissonRate { jitter_ms, .. } = self.encoding else {
+            return times;
+        };
+
+        for step in 0..steps {
+            for (train, &drive) in times.iter_mut().zip(&self.encode_step(input, dt)) {
+                if drive > 0. {
+                    train.push(step as f32 * dt);
+                }
             }
         }
-        temp_lattice.iterate().unwrap();
-        let mut max_spike = 0.0;
-        let mut winner = 0;
-        for (i, neuron) in temp_lattice.grid.iter().enumerate() {
-            if neuron.last_firing_time > max_spike {
-                max_spike = neuron.last_firing_time;
-                winner = i;
+
+        // jitter after generating so the spikes drawn match the unjittered train
+        let last = steps.saturating_sub(1) as f32 * dt;
+        if jitter_ms > 0. {
+            for time in times.iter_mut().flatten() {
+                *time = (*time + self.rng.gen_range(-jitter_ms..=jitter_ms)).clamp(0., last);
             }
         }
-        winner
+
+        times
+    }
+
+    /// Drive for each feature at every step of a sample window of `steps` steps
+    pub fn encode_sample(&mut self, input: &[f32], steps: usize, dt: f32) -> Vec<Vec<f32>> {
+        if let InputEncoding::Current = self.encoding {
+            return vec![input.to_vec(); steps];
+        }
+
+        let mut drive = vec![vec![0.; input.len()]; steps];
+        for (i, train) in self.spike_times(input, steps, dt).iter().enumerate() {
+            for &time in train {
+                let step = ((time / dt).round() as usize).min(steps - 1);
+                drive[step][i] += POISSON_SPIKE_DRIVE;
+            }
+        }
+
+        drive
     }
 }
 
-/// LSM-based classifier (simplified)
-pub struct LSMClassifier {
-    reservoir: Lattice<
-        IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>,
-        AdjacencyMatrix<(usize, usize), f32>,
-        SpikeHistory,
-        STDP,
-        ApproximateNeurotransmitter,
-    >,
-    readout_weights: Vec<Vec<f32>>, // Weights from reservoir to classes
-    n_classes: usize,
+type LSMLattice<G> = Lattice<
+    IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>,
+    G,
+    SpikeHistory,
+    STDP,
+    ApproximateNeurotransmitter,
+>;
+
+/// Reservoir size at and above which [`ConnectivityKind::Auto`] picks a sparse graph
+pub const SPARSE_RESERVOIR_THRESHOLD: usize = 512;
+
+/// How the recurrent connections of an LSM reservoir are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectivityKind {
+    /// [`AdjacencyMatrix`], one weight slot for every pair of neurons
+    Dense,
+    /// [`AdjacencyList`], memory proportional to the number of connections
+    Sparse,
+    /// Dense below [`SPARSE_RESERVOIR_THRESHOLD`] neurons, sparse otherwise
+    #[default]
+    Auto,
 }
 
-impl LSMClassifier {
-    pub fn new(input_size: usize, reservoir_size: usize, n_classes: usize) -> Self {
+impl ConnectivityKind {
+    /// Concrete representation (`Dense` or `Sparse`) used for a reservoir of `reservoir_size` neurons
+    pub fn resolve(self, reservoir_size: usize) -> Self {
+        match self {
+            ConnectivityKind::Auto if reservoir_size >= SPARSE_RESERVOIR_THRESHOLD => ConnectivityKind::Sparse,
+            ConnectivityKind::Auto => ConnectivityKind::Dense,
+            kind => kind,
+        }
+    }
+}
+
+/// LSM reservoir lattice backed by either connectivity representation
+#[derive(Clone)]
+enum LSMReservoir {
+    Dense(LSMLattice<AdjacencyMatrix<(usize, usize), f32>>),
+    Sparse(LSMLattice<AdjacencyList<(usize, usize), f32>>),
+}
+
+impl LSMReservoir {
+    fn new(reservoir_size: usize, connectivity: ConnectivityKind) -> Self {
+        match connectivity.resolve(reservoir_size) {
+            ConnectivityKind::Sparse => LSMReservoir::Sparse(Self::build_lattice(reservoir_size)),
+            _ => LSMReservoir::Dense(Self::build_lattice(reservoir_size)),
+        }
+    }
+
+    // randomly connects 10% of neuron pairs with weights in [-1, 1)
+    fn build_lattice<G: Graph<K = (usize, usize), V = f32>>(reservoir_size: usize) -> LSMLattice<G> {
         let base_neuron = IzhikevichNeuron::default_impl();
-        let mut reservoir = Lattice::default();
+        let mut reservoir: LSMLattice<G> = Lattice::default();
         reservoir.populate(&base_neuron, reservoir_size, 1).unwrap();
         reservoir.connect(
             &|x, y| x != y && rand::thread_rng().gen_bool(0.1),
@@ -209,31 +808,159 @@ impl LSMClassifier {
         ).unwrap();
         reservoir.update_grid_history = true;
 
+        reservoir
+    }
+
+    fn kind(&self) -> ConnectivityKind {
+        match self {
+            LSMReservoir::Dense(_) => ConnectivityKind::Dense,
+            LSMReservoir::Sparse(_) => ConnectivityKind::Sparse,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            LSMReservoir::Dense(lattice) => lattice.grid.len(),
+            LSMReservoir::Sparse(lattice) => lattice.grid.len(),
+        }
+    }
+
+    /// Number of weight slots the connectivity graph allocates
+    fn stored_weights(&self) -> usize {
+        match self {
+            LSMReservoir::Dense(lattice) => lattice.graph.matrix.iter().map(|row| row.len()).sum(),
+            LSMReservoir::Sparse(lattice) => lattice.graph.incoming_connections.values().map(|inputs| inputs.len()).sum(),
+        }
+    }
+}
+
+/// LSM-based classifier (simplified)
+pub struct LSMClassifier {
+    reservoir: LSMReservoir,
+    /// Reservoir as constructed, restored by `reset`
+    initial_reservoir: LSMReservoir,
+    readout_weights: Vec<Vec<f32>>, // Weights from reservoir to classes
+    n_classes: usize,
+    input_size: usize,
+    steps_per_sample: usize,
+    encoder: InputEncoder,
+    normalizer: InputNormalizer,
+}
+
+impl LSMClassifier {
+    pub fn new(input_size: usize, reservoir_size: usize, n_classes: usize) -> Self {
+        Self::with_steps_per_sample(input_size, reservoir_size, n_classes, 10)
+    }
+
+    /// Creates a classifier that drives the reservoir for `steps_per_sample` iterations
+    /// per input and reads out the spike count of each reservoir neuron over that window
+    pub fn with_steps_per_sample(
+        input_size: usize,
+        reservoir_size: usize,
+        n_classes: usize,
+        steps_per_sample: usize,
+    ) -> Self {
+        Self::with_connectivity(input_size, reservoir_size, n_classes, steps_per_sample, ConnectivityKind::Auto)
+    }
+
+    /// Creates a classifier whose reservoir connections are stored as `connectivity`,
+    /// use [`ConnectivityKind::Sparse`] to avoid the quadratic memory of a dense matrix
+    pub fn with_connectivity(
+        input_size: usize,
+        reservoir_size: usize,
+        n_classes: usize,
+        steps_per_sample: usize,
+        connectivity: ConnectivityKind,
+    ) -> Self {
+        let reservoir = LSMReservoir::new(reservoir_size, connectivity);
+
         let readout_weights = vec![vec![0.0; reservoir_size]; n_classes];
 
-        Self { reservoir, readout_weights, n_classes }
+        Self {
+            initial_reservoir: reservoir.clone(),
+            reservoir,
+            readout_weights,
+            n_classes,
+            input_size,
+            steps_per_sample: steps_per_sample.max(1),
+            encoder: InputEncoder::new(InputEncoding::Current),
+            normalizer: InputNormalizer::default(),
+        }
+    }
+
+    /// Representation (`Dense` or `Sparse`) of the reservoir connections
+    pub fn connectivity(&self) -> ConnectivityKind {
+        self.reservoir.kind()
+    }
+
+    /// Selects how inputs are scaled before encoding (raw by default)
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalizer = InputNormalizer::new(normalization);
+        self
+    }
+
+    /// Selects how inputs drive the reservoir (raw currents by default)
+    pub fn with_encoding(mut self, encoding: InputEncoding) -> Self {
+        self.encoder = InputEncoder::new(encoding);
+        self
+    }
+
+    /// Drives `reservoir` with `input` for `steps` iterations and returns the
+    /// rate-coded state: the number of spikes each reservoir neuron fired
+    fn reservoir_state(
+        reservoir: &mut LSMReservoir,
+        encoder: &mut InputEncoder,
+        input: &[f32],
+        steps: usize,
+    ) -> Result<Vec<f32>, SpikingNeuralNetworksError> {
+        match reservoir {
+            LSMReservoir::Dense(lattice) => Self::lattice_state(lattice, encoder, input, steps),
+            LSMReservoir::Sparse(lattice) => Self::lattice_state(lattice, encoder, input, steps),
+        }
+    }
+
+    fn lattice_state<G: Graph<K = (usize, usize), V = f32>>(
+        reservoir: &mut LSMLattice<G>,
+        encoder: &mut InputEncoder,
+        input: &[f32],
+        steps: usize,
+    ) -> Result<Vec<f32>, SpikingNeuralNetworksError> {
+        let mut spike_counts = vec![0.0; reservoir.grid.len()];
+        let dt = reservoir.grid.first().map(|n| n.dt).unwrap_or(0.1);
+        for drive in encoder.encode_sample(input, steps, dt) {
+            for (i, &val) in drive.iter().enumerate() {
+                if let Some(neuron) = reservoir.get_mut(i % reservoir.grid.len(), 0) {
+                    neuron.current_voltage += val;
+                }
+            }
+            reservoir.iterate()?;
+            for (count, neuron) in spike_counts.iter_mut().zip(&reservoir.grid) {
+                if neuron.is_spiking() {
+                    *count += 1.0;
+                }
+            }
+        }
+        Ok(spike_counts)
     }
 }
 
 impl Classifier for LSMClassifier {
     fn train(&mut self, inputs: &[Vec<f32>], labels: &[usize]) -> Result<(), SpikingNeuralNetworksError> {
+        check_training_inputs(self.input_size, inputs)?;
+        if !self.normalizer.is_fitted() {
+            self.normalizer.fit(inputs);
+        }
         let mut reservoir_states = Vec::new();
         for input in inputs {
-            // Drive reservoir with input
-            for (i, &val) in input.iter().enumerate() {
-                if let Some(neuron) = self.reservoir.get_mut(i % self.reservoir.grid.len(), 0) {
-                    neuron.current_voltage += val;
-                }
-            }
-            self.reservoir.iterate()?;
-            // Collect state
-            let state: Vec<f32> = self.reservoir.grid.iter().map(|n| n.last_firing_time).collect();
+            // Drive reservoir with input and collect spike counts
+            let input = self.normalizer.apply(input);
+            let state = Self::reservoir_state(&mut self.reservoir, &mut self.encoder, &input, self.steps_per_sample)?;
             reservoir_states.push(state);
         }
         // Train readout with pseudo-inverse or simple rule
         // Simplified: For each class, average state
         for class in 0..self.n_classes {
-            let mut class_states = vec![0.0; self.reservoir.grid.len()];
+            let mut class_states = vec![0.0; self.reservoir.len()];
             let mut count = 0;
             for (state, &label) in reservoir_states.iter().zip(labels) {
                 if label == class {
@@ -244,8 +971,8 @@ impl Classifier for LSMClassifier {
                 }
             }
             if count > 0 {
-                for w in &mut self.readout_weights[class] {
-                    *w /= count as f32;
+                for (w, s) in self.readout_weights[class].iter_mut().zip(&class_states) {
+                    *w = s / count as f32;
                 }
             }
         }
@@ -255,40 +982,52 @@ impl Classifier for LSMClassifier {
     fn predict(&self, input: &[f32]) -> usize {
         // Drive reservoir
         let mut temp_reservoir = self.reservoir.clone();
-        for (i, &val) in input.iter().enumerate() {
-            if let Some(neuron) = temp_reservoir.get_mut(i % temp_reservoir.grid.len(), 0) {
-                neuron.current_voltage += val;
-            }
-        }
-        temp_reservoir.iterate().unwrap();
-        let state: Vec<f32> = temp_reservoir.grid.iter().map(|n| n.last_firing_time).collect();
+        let mut encoder = self.encoder.clone();
+        let input = self.normalizer.apply(input);
+        let state = Self::reservoir_state(&mut temp_reservoir, &mut encoder, &input, self.steps_per_sample).unwrap();
         // Compute readout
-        let mut max_score = f32::NEG_INFINITY;
-        let mut prediction = 0;
-        for (class, weights) in self.readout_weights.iter().enumerate() {
-            let score: f32 = state.iter().zip(weights).map(|(s, w)| s * w).sum();
-            if score > max_score {
-                max_score = score;
-                prediction = class;
-            }
-        }
-        prediction
+        winner_index(
+            self.readout_weights
+                .iter()
+                .map(|weights| state.iter().zip(weights).map(|(s, w)| s * w).sum()),
+        )
+    }
+
+    fn reset(&mut self) {
+        self.reservoir = self.initial_reservoir.clone();
+        self.readout_weights.iter_mut().for_each(|weights| weights.fill(0.0));
+        self.encoder = InputEncoder::new(self.encoder.encoding);
+        self.normalizer = InputNormalizer::new(self.normalizer.normalization());
+    }
+
+    fn num_classes(&self) -> usize {
+        self.n_classes
+    }
+
+    fn input_dim(&self) -> usize {
+        self.input_size
     }
 }
 
 /// R-STDP regressor
 pub struct RSTDPRegressor {
-    lattice: RewardModulatedLattice<
-        IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>,
-        AdjacencyMatrix<(usize, usize), TraceRSTDP>,
-        SpikeHistory,
-    >,
+    lattice: RSTDPLattice,
+    /// Lattice as constructed, restored by `reset`
+    initial_lattice: RSTDPLattice,
     readout: Vec<f32>,
+    readout_velocity: Vec<f32>,
+    readout_params: ReadoutParams,
     input_size: usize,
 }
 
 impl RSTDPRegressor {
-    pub fn new(input_size: usize) -> Self {
+    pub fn new(input_size: usize, params: RSTDPParams) -> Self {
+        Self::with_readout(input_size, params, ReadoutParams::default())
+    }
+
+    /// Creates a regressor whose readout is trained with the given learning rate,
+    /// momentum and weight decay
+    pub fn with_readout(input_size: usize, params: RSTDPParams, readout_params: ReadoutParams) -> Self {
         let base_neuron = IzhikevichNeuron::default_impl();
         let mut lattice = RewardModulatedLattice::default();
         lattice.populate(&base_neuron, input_size, 1).unwrap();
@@ -299,17 +1038,68 @@ impl RSTDPRegressor {
                 ..TraceRSTDP::default()
             }),
         ).unwrap();
+        lattice.reward_modulator = params.modulator();
         lattice.do_modulation = true;
         lattice.update_graph_history = true;
 
         let readout = vec![0.0; input_size];
+        let readout_velocity = vec![0.0; input_size];
+
+        Self { initial_lattice: lattice.clone(), lattice, readout, readout_velocity, readout_params, input_size }
+    }
+}
+
+impl RSTDPRegressor {
+    /// Drive a lattice with one input sample and read the weighted output
+    fn drive_and_read(
+        lattice: &mut RSTDPLattice,
+        readout: &[f32],
+        input_size: usize,
+        input: &[f32],
+    ) -> Result<f32, SpikingNeuralNetworksError> {
+        for (i, &val) in input.iter().enumerate() {
+            if let Some(neuron) = lattice.get_mut(i % input_size, 0) {
+                neuron.current_voltage += val;
+            }
+        }
+        lattice.iterate()?;
+        Ok(lattice.grid.iter().zip(readout).map(|(n, &w)| n.activity() * w).sum())
+    }
 
-        Self { lattice, readout, input_size }
+    /// Mini-batch training: reward and readout updates are accumulated over each
+    /// batch of `batch_size` samples and applied once per batch
+    pub fn train_minibatch(
+        &mut self,
+        inputs: &[Vec<f32>],
+        targets: &[f32],
+        batch_size: usize,
+    ) -> Result<(), SpikingNeuralNetworksError> {
+        check_training_inputs(self.input_size, inputs)?;
+        let batch_size = batch_size.max(1);
+        for (input_batch, target_batch) in inputs.chunks(batch_size).zip(targets.chunks(batch_size)) {
+            let mut reward_sum = 0.0;
+            let mut readout_grad = vec![0.0; self.readout.len()];
+            for (input, &target) in input_batch.iter().zip(target_batch) {
+                let output = Self::drive_and_read(&mut self.lattice, &self.readout, self.input_size, input)?;
+                let error = target - output;
+                reward_sum += -error.abs();
+                for (i, neuron) in self.lattice.grid.iter().enumerate() {
+                    readout_grad[i] += error * neuron.activity();
+                }
+            }
+            let n = input_batch.len() as f32;
+            self.lattice.apply_reward(reward_sum / n);
+            self.lattice.update_plasticity();
+            readout_grad.iter_mut().for_each(|g| *g /= n);
+            self.readout_params.step(&mut self.readout, &mut self.readout_velocity, &readout_grad);
+        }
+        Ok(())
     }
 }
 
 impl Regressor for RSTDPRegressor {
     fn train(&mut self, inputs: &[Vec<f32>], targets: &[f32]) -> Result<(), SpikingNeuralNetworksError> {
+        check_training_inputs(self.input_size, inputs)?;
         for (input, &target) in inputs.iter().zip(targets) {
             // Set input
             for (i, &val) in input.iter().enumerate() {
@@ -319,29 +1109,42 @@ impl Regressor for RSTDPRegressor {
             }
             self.lattice.iterate()?;
             // Compute output
-            let output: f32 = self.lattice.grid.iter().zip(&self.readout).map(|(n, &w)| n.last_firing_time * w).sum();
+            let output: f32 = self.lattice.grid.iter().zip(&self.readout).map(|(n, &w)| n.activity() * w).sum();
             // Reward based on error
             let error = target - output;
             let reward = -error.abs(); // Negative error as reward
             self.lattice.apply_reward(reward);
             self.lattice.update_plasticity();
-            // Update readout (simple rule)
-            for (i, neuron) in self.lattice.grid.iter().enumerate() {
-                self.readout[i] += 0.01 * error * neuron.last_firing_time;
-            }
+            // Update readout
+            let readout_grad: Vec<f32> = self.lattice.grid.iter().map(|n| error * n.activity()).collect();
+            self.readout_params.step(&mut self.readout, &mut self.readout_velocity, &readout_grad);
         }
         Ok(())
     }
 
     fn predict(&self, input: &[f32]) -> f32 {
         let mut temp_lattice = self.lattice.clone();
-        for (i, &val) in input.iter().enumerate() {
-            if let Some(neuron) = temp_lattice.get_mut(i % self.input_size, 0) {
-                neuron.current_voltage += val;
-            }
-        }
-        temp_lattice.iterate().unwrap();
-        self.lattice.grid.iter().zip(&self.readout).map(|(n, &w)| n.last_firing_time * w).sum()
+        Self::drive_and_read(&mut temp_lattice, &self.readout, self.input_size, input).unwrap()
+    }
+
+    fn reset(&mut self) {
+        self.lattice = self.initial_lattice.clone();
+        self.readout.fill(0.0);
+        self.readout_velocity.fill(0.0);
+    }
+
+    /// Reuses one scratch lattice, restoring all of its state (neurons, connection
+    /// traces, spike history and clock) from the trained lattice before each sample
+    /// so every prediction starts exactly where `predict` does
+    fn predict_batch(&self, inputs: &[Vec<f32>]) -> Vec<f32> {
+        let mut temp_lattice = self.lattice.clone();
+        inputs
+            .iter()
+            .map(|input| {
+                temp_lattice.clone_from(&self.lattice);
+                Self::drive_and_read(&mut temp_lattice, &self.readout, self.input_size, input).unwrap()
+            })
+            .collect()
     }
 }
 
@@ -365,7 +1168,7 @@ mod tests {
 
     #[test]
     fn test_stdp_classifier() {
-        let mut classifier = STDPClassifier::new(10, 3);
+        let mut classifier = STDPClassifier::new(3, 3);
         let inputs = vec![
             vec![1.0, 0.0, 0.0],
             vec![0.0, 1.0, 0.0],
@@ -377,9 +1180,51 @@ mod tests {
         assert!(pred < 3);
     }
 
+    #[test]
+    fn test_stdp_classifier_with_lif_and_adex_models() {
+        let inputs = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let labels = vec![0, 1, 2];
+
+        let mut lif = STDPClassifier::with_model(3, 3, &LeakyIntegrateAndFireNeuron::default_impl());
+        lif.train(&inputs, &labels).unwrap();
+        assert!(lif.predict(&inputs[0]) < 3);
+
+        let mut adex = STDPClassifier::with_model(3, 3, &AdaptiveExpLeakyIntegrateAndFireNeuron::default_impl());
+        adex.train(&inputs, &labels).unwrap();
+        assert!(adex.predict(&inputs[1]) < 3);
+    }
+
+    #[test]
+    fn test_stdp_label_mapping_aligns_neurons_with_labels() {
+        let mut classifier = STDPClassifier::new(3, 3);
+        let inputs = vec![
+            vec![30.0, 0.0, 0.0],
+            vec![0.0, 30.0, 0.0],
+            vec![0.0, 0.0, 30.0],
+        ];
+        classifier.train(&inputs, &[0, 1, 2]).unwrap();
+
+        // Unsupervised neurons need not line up with label indices: label each
+        // input with a class that is never its raw winner
+        let winners: Vec<usize> = inputs.iter().map(|x| classifier.predict(x)).collect();
+        let labels: Vec<usize> = winners.iter().map(|w| (w + 1) % 3).collect();
+        assert_eq!(metrics::accuracy(&winners, &labels), 0.0);
+
+        classifier.fit_label_mapping(&inputs, &labels).unwrap();
+        let mapped: Vec<usize> = inputs.iter().map(|x| classifier.predict(x)).collect();
+        assert_eq!(metrics::accuracy(&mapped, &labels), 1.0);
+        assert_eq!(classifier.label_mapping().unwrap().len(), 3);
+
+        assert!(classifier.fit_label_mapping(&inputs, &labels[..2]).is_err());
+    }
+
     #[test]
     fn test_rstdp_classifier() {
-        let mut classifier = RSTDPClassifier::new(10, 3);
+        let mut classifier = RSTDPClassifier::new(3, 3, RSTDPParams::default());
         let inputs = vec![
             vec![1.0, 0.0, 0.0],
             vec![0.0, 1.0, 0.0],
@@ -393,7 +1238,7 @@ mod tests {
 
     #[test]
     fn test_lsm_classifier() {
-        let mut classifier = LSMClassifier::new(10, 20, 3);
+        let mut classifier = LSMClassifier::new(3, 20, 3);
         let inputs = vec![
             vec![1.0, 0.0, 0.0],
             vec![0.0, 1.0, 0.0],
@@ -405,9 +1250,331 @@ mod tests {
         assert!(pred < 3);
     }
 
+    #[test]
+    fn test_reset_restores_freshly_constructed_state() {
+        let inputs = vec![
+            vec![30.0, 0.0, 0.0],
+            vec![0.0, 30.0, 0.0],
+            vec![0.0, 0.0, 30.0],
+        ];
+        let labels = vec![0, 1, 2];
+
+        let mut stdp = STDPClassifier::new(3, 3).with_normalization(Normalization::MinMax);
+        let fresh: Vec<usize> = inputs.iter().map(|input| stdp.predict(input)).collect();
+        stdp.train(&inputs, &labels).unwrap();
+        stdp.fit_label_mapping(&inputs, &labels).unwrap();
+        stdp.reset();
+        assert!(stdp.label_mapping().is_none());
+        assert!(stdp.normalizer.offsets().is_empty());
+        assert_eq!(inputs.iter().map(|input| stdp.predict(input)).collect::<Vec<_>>(), fresh);
+
+        let mut lsm = LSMClassifier::with_steps_per_sample(3, 20, 3, 20);
+        let fresh: Vec<usize> = inputs.iter().map(|input| lsm.predict(input)).collect();
+        lsm.train(&inputs, &labels).unwrap();
+        lsm.reset();
+        assert!(lsm.readout_weights.iter().flatten().all(|&w| w == 0.0));
+        assert_eq!(inputs.iter().map(|input| lsm.predict(input)).collect::<Vec<_>>(), fresh);
+
+        let mut regressor = RSTDPRegressor::new(3, RSTDPParams::default());
+        let fresh = regressor.predict_batch(&inputs);
+        regressor.train(&inputs, &[1.0, 2.0, 3.0]).unwrap();
+        regressor.reset();
+        assert_eq!(regressor.predict_batch(&inputs), fresh);
+    }
+
+    #[test]
+    fn test_lsm_reservoir_state_counts_spikes() {
+        let classifier = LSMClassifier::with_steps_per_sample(3, 3, 2, 50);
+        let mut first = classifier.reservoir.clone();
+        let mut second = classifier.reservoir.clone();
+        let mut encoder = InputEncoder::new(InputEncoding::Current);
+        let a = LSMClassifier::reservoir_state(&mut first, &mut encoder, &[30.0, 0.0, 0.0], 50).unwrap();
+        let b = LSMClassifier::reservoir_state(&mut second, &mut encoder, &[0.0, 0.0, 30.0], 50).unwrap();
+
+        assert_eq!(a.len(), 3);
+        assert!(a.iter().chain(&b).all(|&c| (0.0..=50.0).contains(&c) && c.fract() == 0.0));
+        // Driven neurons fire repeatedly, so counts (not just timestamps) tell the inputs apart
+        assert!(a[0] > 1.0 && b[2] > 1.0);
+        assert!(a[0] > b[0] && b[2] > a[2]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_large_reservoir_uses_sparse_connectivity() {
+        let n = 1000;
+        let sparse = LSMClassifier::new(3, n, 2);
+        assert_eq!(sparse.connectivity(), ConnectivityKind::Sparse);
+        // Only the ~10% of pairs that are connected take up space
+        let stored = sparse.reservoir.stored_weights();
+        let pairs = n * (n - 1);
+        assert!(stored > pairs / 20 && stored < pairs / 5, "{}", stored);
+
+        let dense = LSMClassifier::with_connectivity(3, 100, 2, 10, ConnectivityKind::Auto);
+        assert_eq!(dense.connectivity(), ConnectivityKind::Dense);
+        assert_eq!(dense.reservoir.stored_weights(), 100 * 100);
+
+        assert_eq!(ConnectivityKind::Dense.resolve(n), ConnectivityKind::Dense);
+        assert_eq!(ConnectivityKind::Auto.resolve(SPARSE_RESERVOIR_THRESHOLD), ConnectivityKind::Sparse);
+    }
+
+    #[test]
+    fn test_poisson_encoding_is_stochastic_and_rate_proportional() {
+        let input = [0.0, 0.25, 1.0];
+        let spike_trains = |seed: u64| -> Vec<Vec<f32>> {
+            let mut encoder = InputEncoder::new(InputEncoding::PoissonRate { max_hz: 100., seed, jitter_ms: 0. });
+            (0..10_000).map(|_| encoder.encode_step(&input, 1.)).collect()
+        };
+
+        let trains = spike_trains(7);
+        let counts: Vec<usize> = (0..input.len())
+            .map(|i| trains.iter().filter(|step| step[i] > 0.).count())
+            .collect();
+        assert!(trains.iter().flatten().all(|&d| d == 0. || d == POISSON_SPIKE_DRIVE));
+        // 100 Hz over 10 s of 1 ms steps is ~1000 spikes, a quarter of that at 0.25
+        assert_eq!(counts[0], 0);
+        assert!((850..1150).contains(&counts[2]), "{:?}", counts);
+        let ratio = counts[2] as f32 / counts[1] as f32;
+        assert!((3.0..5.0).contains(&ratio), "{:?}", counts);
+
+        assert_eq!(trains, spike_trains(7));
+        assert_ne!(trains, spike_trains(8));
+
+        let mut current = InputEncoder::new(InputEncoding::Current);
+        assert_eq!(current.encode_step(&input, 1.), input.to_vec());
+
+        let poisson = InputEncoding::PoissonRate { max_hz: 100., seed: 7, jitter_ms: 0. };
+        let classifier = LSMClassifier::new(3, 10, 2).with_encoding(poisson);
+        assert_eq!(classifier.encoder.encoding, poisson);
+
+        // The STDP classifiers encode each readout window the same way
+        let stdp = STDPClassifier::new(3, 3).with_encoding(poisson).with_readout(ReadoutMode::SpikeCount, 20);
+        assert_eq!(stdp.encoder.encoding, poisson);
+        assert_eq!(stdp.predict(&input), stdp.predict(&input));
+        let mut rstdp = RSTDPClassifier::new(3, 3, RSTDPParams::default()).with_encoding(poisson);
+        assert_eq!(rstdp.encoder.encoding, poisson);
+        rstdp.train(&[input.to_vec()], &[2]).unwrap();
+        rstdp.reset();
+        assert_eq!(rstdp.encoder.encoding, poisson);
+    }
+
+    #[test]
+    fn test_input_jitter_moves_spike_times_but_preserves_count() {
+        let input = [0.5, 1.0];
+        let (steps, dt) = (1_000, 1.);
+        let encoder = |jitter_ms: f32| InputEncoder::new(InputEncoding::PoissonRate { max_hz: 100., seed: 11, jitter_ms });
+
+        let exact = encoder(0.).spike_times(&input, steps, dt);
+        let jittered = encoder(5.).spike_times(&input, steps, dt);
+        assert_ne!(exact, jittered);
+        for (exact, jittered) in exact.iter().zip(&jittered) {
+            assert!(!exact.is_empty());
+            assert_eq!(exact.len(), jittered.len());
+            for (&t, &t_jittered) in exact.iter().zip(jittered) {
+                assert!((t - t_jittered).abs() <= 5.);
+                assert!((0. ..=(steps - 1) as f32 * dt).contains(&t_jittered));
+            }
+        }
+
+        // binned drive keeps every jittered spike
+        let drive = encoder(5.).encode_sample(&input, steps, dt);
+        assert_eq!(drive.len(), steps);
+        let total: f32 = drive.iter().flatten().sum();
+        let spikes: usize = jittered.iter().map(Vec::len).sum();
+        assert_eq!(total, spikes as f32 * POISSON_SPIKE_DRIVE);
+    }
+
+    #[test]
+    fn test_normalization_improves_separability_of_unscaled_features() {
+        // Only feature 0 (in [0, 1]) carries the class; feature 1 is noise in [0, 1000]
+        let inputs = vec![
+            vec![0.0, 0.0], vec![0.1, 1000.0], vec![0.0, 300.0], vec![0.1, 700.0],
+            vec![1.0, 1000.0], vec![0.9, 0.0], vec![1.0, 700.0], vec![0.9, 300.0],
+        ];
+        let labels = vec![0, 0, 0, 0, 1, 1, 1, 1];
+        // Distance between class centroids over mean distance of samples to their centroid
+        let separability = |xs: &[Vec<f32>]| -> f32 {
+            let centroid = |class: usize| -> Vec<f32> {
+                let members: Vec<&Vec<f32>> = xs.iter().zip(&labels).filter(|(_, &l)| l == class).map(|(x, _)| x).collect();
+                (0..2).map(|j| members.iter().map(|x| x[j]).sum::<f32>() / members.len() as f32).collect()
+            };
+            let dist = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f32>().sqrt();
+            let centroids = [centroid(0), centroid(1)];
+            let within = xs.iter().zip(&labels).map(|(x, &l)| dist(x, &centroids[l])).sum::<f32>() / xs.len() as f32;
+            dist(&centroids[0], &centroids[1]) / within
+        };
+
+        let raw = separability(&inputs);
+        for normalization in [Normalization::MinMax, Normalization::ZScore] {
+            let mut normalizer = InputNormalizer::new(normalization);
+            normalizer.fit(&inputs);
+            let scaled: Vec<Vec<f32>> = inputs.iter().map(|x| normalizer.apply(x)).collect();
+            let improved = separability(&scaled);
+            assert!(improved > 100.0 * raw, "{:?}: {} vs raw {}", normalization, improved, raw);
+        }
+
+        let mut min_max = InputNormalizer::new(Normalization::MinMax);
+        min_max.fit(&inputs);
+        assert_eq!(min_max.offsets(), &[0.0, 0.0]);
+        assert_eq!(min_max.scales(), &[1.0, 1000.0]);
+        assert_eq!(min_max.apply(&[0.5, 250.0]), vec![0.5, 0.25]);
+
+        let mut none = InputNormalizer::new(Normalization::None);
+        none.fit(&inputs);
+        assert!(none.offsets().is_empty());
+        assert_eq!(none.apply(&inputs[1]), inputs[1]);
+
+        // Classifiers fit the statistics during `train` and keep them for `predict`
+        let mut lsm = LSMClassifier::new(2, 10, 2).with_normalization(Normalization::ZScore);
+        lsm.train(&inputs, &labels).unwrap();
+        assert_eq!(lsm.normalizer.offsets().len(), 2);
+        assert!(lsm.predict(&inputs[0]) < 2);
+
+        let mut stdp = STDPClassifier::new(2, 2).with_normalization(Normalization::MinMax);
+        stdp.train(&inputs, &labels).unwrap();
+        assert_eq!(stdp.normalizer.scales(), min_max.scales());
+
+        // Later batches reuse the first fit instead of rescaling what was learned
+        let wider = vec![vec![0.0, 0.0], vec![4.0, 4000.0]];
+        stdp.train(&wider, &labels[..2]).unwrap();
+        assert_eq!(stdp.normalizer.scales(), min_max.scales());
+        stdp.reset();
+        stdp.train(&wider, &labels[..2]).unwrap();
+        assert_eq!(stdp.normalizer.scales(), &[4.0, 4000.0]);
+    }
+
+    #[test]
+    fn test_winner_ties_go_to_lowest_index() {
+        use crate::neuron::iterate_and_spike::LastFiringTime;
+
+        assert_eq!(winner_index([3.0, 5.0, 5.0, 1.0]), 1);
+        assert_eq!(winner_index([0.0; 4]), 0);
+        assert_eq!(winner_index([-2.0, -1.0, -1.0]), 1);
+        assert_eq!(winner_index(std::iter::empty()), 0);
+
+        // Two output neurons in an identical firing state, ahead of the third: the
+        // lower index wins every prediction
+        let mut classifier = RSTDPClassifier::new(3, 3, RSTDPParams::default());
+        let mut tied = classifier.lattice.grid[0].clone();
+        tied.set_last_firing_time(Some(7));
+        classifier.lattice.grid[0] = tied.clone();
+        classifier.lattice.grid[1] = tied;
+        classifier.lattice.grid[2].set_last_firing_time(Some(1));
+        let predictions: Vec<usize> = (0..5).map(|_| classifier.predict(&[0.0; 3])).collect();
+        assert_eq!(predictions, vec![0; 5]);
+    }
+
+    #[test]
+    fn test_readout_modes_pick_semantically_correct_winner() {
+        let spikes = vec![
+            vec![false, true, true, true, false, false], // early and often
+            vec![false, false, false, false, false, true], // once, late
+            vec![true, false, false, false, false, false], // once, first
+            vec![false; 6], // silent
+        ];
+        // absolute last firing times once a window starting at step 100 has run
+        let activity = [103.0, 105.0, 100.0, 42.0];
+        assert_eq!(ReadoutMode::LastFiringTime.winner(&spikes, &activity), 1);
+        assert_eq!(ReadoutMode::LastSpikeInWindow.winner(&spikes, &activity), 1);
+        assert_eq!(ReadoutMode::SpikeCount.winner(&spikes, &activity), 0);
+        assert_eq!(ReadoutMode::FirstToFire.winner(&spikes, &activity), 2);
+
+        // A silent neuron only wins a window mode when every neuron is silent
+        let silent = vec![vec![false; 3]; 2];
+        for mode in [ReadoutMode::LastSpikeInWindow, ReadoutMode::SpikeCount, ReadoutMode::FirstToFire] {
+            assert_eq!(mode.winner(&silent, &[0.0, 77.0]), 0);
+        }
+
+        let classifier = RSTDPClassifier::new(3, 3, RSTDPParams::default())
+            .with_readout(ReadoutMode::SpikeCount, 5);
+        assert!(classifier.predict(&[1.0, 0.0, 0.0]) < 3);
+    }
+
+    #[test]
+    fn test_default_readout_keeps_absolute_last_firing_time() {
+        use crate::neuron::iterate_and_spike::LastFiringTime;
+
+        assert_eq!(ReadoutMode::default(), ReadoutMode::LastFiringTime);
+
+        // Spikes before the window still count, a neuron that never fired scores 0
+        let silent = vec![vec![false; 3]; 2];
+        assert_eq!(ReadoutMode::LastFiringTime.winner(&silent, &[0.0, 77.0]), 1);
+
+        // No neuron spikes during the default single-step window, so the winner is
+        // the neuron that fired most recently before it
+        let mut classifier = RSTDPClassifier::new(3, 3, RSTDPParams::default());
+        classifier.lattice.grid[0].set_last_firing_time(Some(5));
+        classifier.lattice.grid[2].set_last_firing_time(Some(40));
+        assert_eq!(classifier.predict(&[0.0; 3]), 2);
+    }
+
+    #[test]
+    fn test_stdp_training_inhibits_around_readout_winner() {
+        // Neuron 0 spikes most often, neuron 1 most recently
+        let spikes = vec![
+            vec![true, true, true, false],
+            vec![false, false, false, true],
+            vec![false; 4],
+        ];
+        let activity = [102.0, 103.0, 0.0];
+        let mut classifier = STDPClassifier::new(3, 3).with_readout(ReadoutMode::SpikeCount, 4);
+        let before: Vec<f32> = classifier.lattice.grid.iter().map(|n| n.current_voltage).collect();
+        assert_eq!(classifier.inhibit_losers(&spikes, &activity), 0);
+        let after: Vec<f32> = classifier.lattice.grid.iter().map(|n| n.current_voltage).collect();
+        assert_eq!(after, vec![before[0], before[1] - 1.0, before[2] - 1.0]);
+
+        // `train` inhibits around the winner of its own readout window
+        let mut classifier = STDPClassifier::new(3, 3).with_readout(ReadoutMode::SpikeCount, 4);
+        let mut expected = classifier.lattice.clone();
+        let (spikes, activity) = run_readout_window(&mut expected, &vec![vec![0.0; 3]; 4]).unwrap();
+        let winner = ReadoutMode::SpikeCount.winner(&spikes, &activity);
+        classifier.train(&[vec![0.0; 3]], &[0]).unwrap();
+        for (i, (neuron, reference)) in classifier.lattice.grid.iter().zip(&expected.grid).enumerate() {
+            let inhibition = if i == winner { 0.0 } else { 1.0 };
+            assert_eq!(neuron.current_voltage, reference.current_voltage - inhibition);
+        }
+    }
+
+    #[test]
+    fn test_classifier_dimensions() {
+        let classifier = STDPClassifier::new(10, 3);
+        assert_eq!(classifier.input_dim(), 10);
+        assert_eq!(classifier.num_classes(), 3);
+        assert!(classifier.validate_input(&[0.0; 10]).is_ok());
+        assert!(classifier.validate_input(&[0.0; 3]).is_err());
+
+        let classifier = RSTDPClassifier::new(10, 3, RSTDPParams::default());
+        assert_eq!((classifier.input_dim(), classifier.num_classes()), (10, 3));
+
+        let classifier = LSMClassifier::new(10, 20, 3);
+        assert_eq!((classifier.input_dim(), classifier.num_classes()), (10, 3));
+    }
+
+    #[test]
+    fn test_wrong_input_length_is_rejected() {
+        let mismatch = || SpikingNeuralNetworksError::DimensionMismatch { expected: 3, got: 5 };
+        let bad_inputs = vec![vec![1.0, 0.0, 0.0], vec![0.0; 5]];
+        let labels = vec![0, 1];
+
+        let mut stdp = STDPClassifier::new(3, 3);
+        assert_eq!(stdp.train(&bad_inputs, &labels), Err(mismatch()));
+        assert_eq!(stdp.try_predict(&bad_inputs[1]), Err(mismatch()));
+
+        let mut rstdp = RSTDPClassifier::new(3, 3, RSTDPParams::default());
+        assert_eq!(rstdp.train(&bad_inputs, &labels), Err(mismatch()));
+        assert_eq!(rstdp.try_predict(&bad_inputs[1]), Err(mismatch()));
+
+        let mut lsm = LSMClassifier::new(3, 20, 3);
+        assert_eq!(lsm.train(&bad_inputs, &labels), Err(mismatch()));
+        assert_eq!(lsm.try_predict(&bad_inputs[1]), Err(mismatch()));
+
+        let mut regressor = RSTDPRegressor::new(3, RSTDPParams::default());
+        assert_eq!(regressor.train(&bad_inputs, &[1.0, 2.0]), Err(mismatch()));
+        assert_eq!(regressor.train_minibatch(&bad_inputs, &[1.0, 2.0], 2), Err(mismatch()));
+    }
+
     #[test]
     fn test_rstdp_regressor() {
-        let mut regressor = RSTDPRegressor::new(10);
+        let mut regressor = RSTDPRegressor::new(2, RSTDPParams::default());
         let inputs = vec![
             vec![1.0, 0.0],
             vec![0.0, 1.0],
@@ -418,6 +1585,139 @@ mod tests {
         assert!(pred > 0.0);
     }
 
+    #[test]
+    fn test_rstdp_regressor_predict_batch_matches_predict() {
+        let mut regressor = RSTDPRegressor::new(2, RSTDPParams::default());
+        let inputs = vec![
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![0.5, 0.5],
+        ];
+        let targets = vec![1.0, 2.0, 1.5];
+        regressor.train_minibatch(&inputs, &targets, 2).unwrap();
+
+        // Repeated inputs must not see state left behind by earlier samples
+        let batch_inputs = vec![
+            inputs[0].clone(),
+            inputs[1].clone(),
+            inputs[0].clone(),
+            inputs[2].clone(),
+            inputs[0].clone(),
+        ];
+        let batch = regressor.predict_batch(&batch_inputs);
+        let fresh: Vec<f32> = batch_inputs.iter().map(|input| regressor.predict(input)).collect();
+        assert_eq!(batch, fresh);
+        assert_eq!(batch[0], batch[2]);
+        assert_eq!(batch[0], batch[4]);
+    }
+
+    #[test]
+    fn test_rstdp_params_configure_modulator() {
+        let params = RSTDPParams { tau_c: 0.5, learning_rate: 3.0 };
+        let regressor = RSTDPRegressor::new(4, params);
+        let defaults = RewardModulatedSTDP::default();
+        assert_eq!(regressor.lattice.reward_modulator.tau_c, 0.5);
+        assert_eq!(regressor.lattice.reward_modulator.a_plus, defaults.a_plus * 3.0);
+        assert_eq!(regressor.lattice.reward_modulator.a_minus, defaults.a_minus * 3.0);
+    }
+
+    #[test]
+    fn test_rstdp_zero_learning_rate_freezes_weights() {
+        use crate::graph::Graph;
+
+        let params = RSTDPParams { learning_rate: 0.0, ..RSTDPParams::default() };
+        let mut regressor = RSTDPRegressor::new(4, params);
+        let weights = |r: &RSTDPRegressor| -> Vec<f32> {
+            let mut nodes: Vec<(usize, usize)> = r.lattice.graph.get_every_node().into_iter().collect();
+            nodes.sort();
+            let mut out = Vec::new();
+            for pre in &nodes {
+                for post in &nodes {
+                    if let Ok(Some(w)) = r.lattice.graph.lookup_weight(pre, post) {
+                        out.push(w.weight);
+                    }
+                }
+            }
+            out
+        };
+        let before = weights(&regressor);
+        let inputs = vec![vec![5.0, 0.0, 5.0, 0.0], vec![0.0, 5.0, 0.0, 5.0]];
+        for _ in 0..20 {
+            regressor.train(&inputs, &[1.0, 2.0]).unwrap();
+        }
+        assert_eq!(weights(&regressor), before);
+    }
+
+    #[test]
+    fn test_rstdp_higher_learning_rate_converges_faster() {
+        use crate::neuron::iterate_and_spike::LastFiringTime;
+        use crate::neuron::plasticity::RewardModulator;
+
+        type Neuron = IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>;
+
+        // Separable two-class task: input `i` should drive output `i`, but the
+        // synapses start out favoring the wrong output. Each input fires, the
+        // output with the strongest synapse follows 3 steps later and the
+        // reward (+1 correct, -1 wrong) modulates that synapse's trace.
+        let epochs_to_learn = |learning_rate: f32| -> usize {
+            let mut modulator = RSTDPParams { learning_rate, ..RSTDPParams::default() }.modulator();
+            let synapse = |weight| TraceRSTDP { weight, ..TraceRSTDP::default() };
+            let mut weights = [[synapse(0.4), synapse(0.6)], [synapse(0.6), synapse(0.4)]];
+            let mut pre = Neuron::default_impl();
+            let mut post = Neuron::default_impl();
+            pre.set_last_firing_time(Some(10));
+            post.set_last_firing_time(Some(13));
+
+            for epoch in 1..=100 {
+                let mut correct = 0;
+                for (input, synapses) in weights.iter_mut().enumerate() {
+                    let winner = winner_index(synapses.iter().map(|w| w.weight));
+                    let reward = if winner == input { 1.0 } else { -1.0 };
+                    correct += usize::from(winner == input);
+                    <RewardModulatedSTDP as RewardModulator<Neuron, Neuron, TraceRSTDP>>::update(&mut modulator, reward);
+                    // the trace is rolled forward every second update
+                    for _ in 0..2 {
+                        modulator.update_weight(&mut synapses[winner], &pre, &post);
+                    }
+                }
+                if correct == 2 {
+                    return epoch;
+                }
+            }
+            usize::MAX
+        };
+
+        let slow = epochs_to_learn(0.1);
+        let fast = epochs_to_learn(1.0);
+        assert!(slow < usize::MAX, "learning rate 0.1 never separated the classes");
+        assert!(fast < slow, "learning rate 1.0 took {} epochs, 0.1 took {}", fast, slow);
+    }
+
+    #[test]
+    fn test_readout_momentum_lowers_linear_mse() {
+        let inputs = [[1.0, 0.0], [0.0, 1.0], [1.0, 1.0], [2.0, 1.0], [0.5, 2.0]];
+        let targets: Vec<f32> = inputs.iter().map(|x| 2.0 * x[0] - x[1]).collect();
+        let fit = |params: ReadoutParams| -> f32 {
+            let (mut weights, mut velocity) = (vec![0.0; 2], vec![0.0; 2]);
+            for _ in 0..20 {
+                for (x, &y) in inputs.iter().zip(&targets) {
+                    let error = y - (weights[0] * x[0] + weights[1] * x[1]);
+                    let grad = [error * x[0], error * x[1]];
+                    params.step(&mut weights, &mut velocity, &grad);
+                }
+            }
+            let preds: Vec<f32> = inputs.iter().map(|x| weights[0] * x[0] + weights[1] * x[1]).collect();
+            metrics::mse(&preds, &targets)
+        };
+
+        let plain = fit(ReadoutParams::default());
+        let momentum = fit(ReadoutParams { momentum: 0.9, ..ReadoutParams::default() });
+        assert!(momentum < plain, "momentum mse {} >= plain mse {}", momentum, plain);
+
+        let regressor = RSTDPRegressor::with_readout(2, RSTDPParams::default(), ReadoutParams { momentum: 0.9, ..ReadoutParams::default() });
+        assert_eq!(regressor.readout_params.momentum, 0.9);
+    }
+
     #[test]
     fn test_metrics() {
         let preds = vec![0, 1, 2];
@@ -6,7 +6,8 @@
 
 use crate::neuron::iterate_and_spike::{IterateAndSpike, ApproximateNeurotransmitter, ApproximateReceptor, LastFiringTime};
 use crate::graph::{Graph, AdjacencyList, AdjacencyMatrix};
-use crate::neuron::plasticity::STDP;
+use crate::neuron::iterate_and_spike::NeurotransmitterType;
+use crate::neuron::plasticity::{Plasticity, STDP};
 use crate::neuron::integrate_and_fire::IzhikevichNeuron;
 use crate::neuron::{Lattice, SpikeHistory, RunLattice};
 use crate::classifiers::{Classifier, Regressor};
@@ -25,6 +26,20 @@ pub trait BrainRegion {
 
     /// Update internal plasticity based on activity.
     fn update_plasticity(&mut self);
+
+    /// Deliver a reward (neuromodulator) signal, consumed by the next plasticity update.
+    /// Regions without reward modulated plasticity ignore it.
+    fn deliver_reward(&mut self, _amount: f32) {}
+}
+
+/// Whether the neuron fired on the given simulation step.
+fn fired_at(neuron: &impl LastFiringTime, step: usize) -> bool {
+    neuron.get_last_firing_time() == Some(step)
+}
+
+/// Output view of a neuron's last spike time in module time units (0.0 if it never fired).
+fn last_spike_time(neuron: &impl LastFiringTime, dt: f32) -> f32 {
+    neuron.get_last_firing_time().map_or(0.0, |t| t as f32 * dt)
 }
 
 /// Digital Twin orchestrator.
@@ -53,6 +68,13 @@ impl DigitalTwin {
         // In practice, map strings to indices.
     }
 
+    /// Deliver a reward to every region, applied on the next `iterate`.
+    pub fn deliver_reward(&mut self, amount: f32) {
+        for region in self.regions.values_mut() {
+            region.deliver_reward(amount);
+        }
+    }
+
     /// Run one time step of the entire twin.
     pub fn iterate(&mut self) {
         // Collect outputs from all regions.
@@ -70,20 +92,23 @@ impl DigitalTwin {
     }
 }
 
-// Example: Cortical Module with Izhikevich neurons and STDP.
+/// Default decay time constant of the cortical eligibility traces (same units as `STDP::dt`)
+pub const DEFAULT_ELIGIBILITY_TAU: f32 = 1000.;
+
+// Example: Cortical Module with Izhikevich neurons and three-factor (reward modulated) STDP.
 pub struct CorticalModule {
     neurons: Vec<IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>>,
-    last_firing_times: Vec<f32>,
     plasticity: STDP,
     graph: AdjacencyList<(usize, usize), f32>,
-    timestep: f32,
-    dopamine: f32, // Neuromodulator
+    step: usize, // Simulation step; spike times are recorded on the neurons
+    dopamine: f32, // Phasic neuromodulator, consumed by the next plasticity update
+    eligibility: HashMap<(usize, usize), f32>, // STDP eligibility trace per synapse, keyed like `graph`
+    eligibility_tau: f32, // Trace decay time constant
 }
 
 impl CorticalModule {
     pub fn new(size: usize) -> Self {
         let neurons = (0..size).map(|_| IzhikevichNeuron::default_impl()).collect();
-        let last_firing_times = vec![0.0; size];
         let plasticity = STDP::default();
         let mut graph = AdjacencyList::new();
         // Initialize full connectivity for simplicity
@@ -94,21 +119,41 @@ impl CorticalModule {
                 }
             }
         }
-        Self { neurons, last_firing_times, plasticity, graph, timestep: 0.0, dopamine: 1.0 }
+        Self {
+            neurons,
+            plasticity,
+            graph,
+            step: 0,
+            dopamine: 0.,
+            eligibility: HashMap::new(),
+            eligibility_tau: DEFAULT_ELIGIBILITY_TAU,
+        }
+    }
+
+    /// Sets the decay time constant of the eligibility traces
+    pub fn with_eligibility_tau(mut self, eligibility_tau: f32) -> Self {
+        self.eligibility_tau = eligibility_tau;
+        self
+    }
+
+    /// Current eligibility trace of the synapse from `pre` to `post`
+    pub fn eligibility(&self, pre: usize, post: usize) -> f32 {
+        self.eligibility.get(&(pre, post)).copied().unwrap_or(0.)
     }
 }
 
 impl BrainRegion for CorticalModule {
     fn iterate(&mut self, inputs: &[Vec<f32>]) -> Vec<f32> {
-        self.timestep += 0.1; // Assume dt=0.1
+        self.step += 1;
+        let fired: Vec<bool> = self.neurons.iter().map(|n| n.get_last_firing_time().is_some()).collect();
         let mut spikes = Vec::new();
         for (i, neuron) in self.neurons.iter_mut().enumerate() {
             // Calculate synaptic input from graph (simplified: sum weighted spikes from all others)
             let mut synaptic_input = 0.0;
-            for j in 0..self.neurons.len() {
+            for j in 0..fired.len() {
                 if i != j {
                     if let Some(&weight) = self.graph.get_edge(&(j, i)) {
-                        if self.last_firing_times[j] > 0.0 { // If recently spiked
+                        if fired[j] { // If recently spiked
                             synaptic_input += weight;
                         }
                     }
@@ -118,7 +163,7 @@ impl BrainRegion for CorticalModule {
             let total_input = synaptic_input + external_input;
             let spiked = neuron.iterate_and_spike(total_input);
             if spiked {
-                self.last_firing_times[i] = self.timestep;
+                neuron.set_last_firing_time(Some(self.step));
                 spikes.push(1.0);
             } else {
                 spikes.push(0.0);
@@ -128,29 +173,76 @@ impl BrainRegion for CorticalModule {
     }
 
     fn get_outputs(&self) -> Vec<f32> {
-        self.last_firing_times.clone() // Return last firing times as output
+        // Return last firing times as output
+        self.neurons.iter().map(|n| last_spike_time(n, self.plasticity.dt)).collect()
+    }
+
+    /// Releases dopamine, on the next plasticity update every synapse's weight
+    /// changes by `amount` times its eligibility trace
+    fn deliver_reward(&mut self, amount: f32) {
+        self.dopamine += amount;
     }
 
     fn update_plasticity(&mut self) {
-        // Apply R-STDP modulated by dopamine
+        // Eligibility decays every step so older spike pairs count for less
+        let decay = (-self.plasticity.dt / self.eligibility_tau).exp();
+        for trace in self.eligibility.values_mut() {
+            *trace *= decay;
+        }
+
+        // Accumulate STDP contributions into the traces
         for i in 0..self.neurons.len() {
             for j in 0..self.neurons.len() {
                 if i != j {
-                    if let Some(weight) = self.graph.get_edge_mut(&(j, i)) {
-                        let dt = self.last_firing_times[i] - self.last_firing_times[j];
-                        let delta_w = if dt > 0.0 { // Post after pre
-                            self.plasticity.a_minus * (-dt).exp()
-                        } else { // Pre after post
-                            self.plasticity.a_plus * dt.exp()
-                        };
-                        *weight += delta_w * self.dopamine; // Modulate by dopamine
+                    // Only pairs where either side spiked this step, using the recorded spike times
+                    if !fired_at(&self.neurons[i], self.step) && !fired_at(&self.neurons[j], self.step) {
+                        continue;
                     }
+                    if self.graph.get_edge(&(j, i)).is_some() {
+                        let mut delta_w = 0.0;
+                        self.plasticity.update_weight(&mut delta_w, &self.neurons[j], &self.neurons[i]);
+                        *self.eligibility.entry((j, i)).or_insert(0.) += delta_w;
+                    }
+                }
+            }
+        }
+
+        // Dopamine gates the transfer of the traces into the weights
+        if self.dopamine != 0. {
+            for (synapse, trace) in &self.eligibility {
+                if let Some(weight) = self.graph.get_edge_mut(synapse) {
+                    *weight += self.dopamine * trace;
                 }
             }
+            self.dopamine = 0.;
         }
     }
 }
 
+impl<T, U, W, N> Lattice<T, U, SpikeHistory, W, N>
+where
+    T: IterateAndSpike<N = N>,
+    U: Graph<K = (usize, usize), V = f32>,
+    W: Plasticity<T, T, f32>,
+    N: NeurotransmitterType,
+{
+    /// Spike raster of the recorded grid history as a neurons x timesteps matrix,
+    /// `raster[n][t]` is whether neuron `n` spiked on recorded step `t`, where neurons
+    /// are numbered row-major (`n = row * cols + col`), only filled when
+    /// `update_grid_history` is enabled
+    pub fn spike_raster(&self) -> Vec<Vec<bool>> {
+        let n_neurons = self.cell_grid().iter().map(|row| row.len()).sum();
+        let mut raster = vec![Vec::with_capacity(self.grid_history.history.len()); n_neurons];
+        for step in &self.grid_history.history {
+            for (n, is_spiking) in step.iter().flatten().enumerate() {
+                raster[n].push(*is_spiking);
+            }
+        }
+
+        raster
+    }
+}
+
 // Hippocampal Module with Ring Attractor.
 pub struct HippocampalModule {
     lattice: Lattice<
@@ -277,17 +369,15 @@ impl BrainRegion for LsmModule {
 // Cue Model Module for working memory with recurrent neurons and noise modulation
 pub struct CueModelModule {
     neurons: Vec<IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>>,
-    last_firing_times: Vec<f32>,
     plasticity: STDP,
     graph: AdjacencyMatrix<(usize, usize), f32>,
-    timestep: f32,
+    step: usize,
     noise_level: f32,
 }
 
 impl CueModelModule {
     pub fn new(size: usize, noise_level: f32) -> Self {
         let neurons = (0..size).map(|_| IzhikevichNeuron::default_impl()).collect();
-        let last_firing_times = vec![0.0; size];
         let plasticity = STDP::default();
         let mut graph = AdjacencyMatrix::default();
         // Recurrent connections with random weights
@@ -298,20 +388,21 @@ impl CueModelModule {
                 }
             }
         }
-        Self { neurons, last_firing_times, plasticity, graph, timestep: 0.0, noise_level }
+        Self { neurons, plasticity, graph, step: 0, noise_level }
     }
 }
 
 impl BrainRegion for CueModelModule {
     fn iterate(&mut self, inputs: &[Vec<f32>]) -> Vec<f32> {
-        self.timestep += 0.1;
+        self.step += 1;
+        let fired: Vec<bool> = self.neurons.iter().map(|n| n.get_last_firing_time().is_some()).collect();
         let mut spikes = Vec::new();
         for (i, neuron) in self.neurons.iter_mut().enumerate() {
             let mut synaptic_input = 0.0;
-            for j in 0..self.neurons.len() {
+            for j in 0..fired.len() {
                 if i != j {
                     if let Some(&weight) = self.graph.lookup_weight(&(j, i)) {
-                        if self.last_firing_times[j] > 0.0 {
+                        if fired[j] {
                             synaptic_input += weight;
                         }
                     }
@@ -323,7 +414,7 @@ impl BrainRegion for CueModelModule {
             let total_input = synaptic_input + external_input + noise;
             let spiked = neuron.iterate_and_spike(total_input);
             if spiked {
-                self.last_firing_times[i] = self.timestep;
+                neuron.set_last_firing_time(Some(self.step));
                 spikes.push(1.0);
             } else {
                 spikes.push(0.0);
@@ -333,7 +424,7 @@ impl BrainRegion for CueModelModule {
     }
 
     fn get_outputs(&self) -> Vec<f32> {
-        self.last_firing_times.clone()
+        self.neurons.iter().map(|n| last_spike_time(n, self.plasticity.dt)).collect()
     }
 
     fn update_plasticity(&mut self) {
@@ -341,14 +432,11 @@ impl BrainRegion for CueModelModule {
         for i in 0..self.neurons.len() {
             for j in 0..self.neurons.len() {
                 if i != j {
+                    if !fired_at(&self.neurons[i], self.step) && !fired_at(&self.neurons[j], self.step) {
+                        continue;
+                    }
                     if let Some(weight) = self.graph.lookup_weight_mut(&(j, i)) {
-                        let dt = self.last_firing_times[i] - self.last_firing_times[j];
-                        let delta_w = if dt > 0.0 {
-                            self.plasticity.a_minus * (-dt).exp()
-                        } else {
-                            self.plasticity.a_plus * dt.exp()
-                        };
-                        *weight += delta_w;
+                        self.plasticity.update_weight(weight, &self.neurons[j], &self.neurons[i]);
                     }
                 }
             }
@@ -466,10 +554,9 @@ impl BrainRegion for AstrocyteModule {
 // Pathology simulation: Schizophrenia model with GABA/NMDA imbalances
 pub struct SchizophreniaModule {
     neurons: Vec<IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>>,
-    last_firing_times: Vec<f32>,
     plasticity: STDP,
     graph: AdjacencyList<(usize, usize), f32>,
-    timestep: f32,
+    step: usize,
     nmda_reduction: f32, // Factor to reduce NMDA efficacy (e.g., 0.5 for hypofunction)
     gaba_increase: f32, // Factor to increase GABA (e.g., 1.5)
 }
@@ -488,7 +575,6 @@ impl SchizophreniaModule {
             }
             neurons.push(neuron);
         }
-        let last_firing_times = vec![0.0; size];
         let plasticity = STDP::default();
         let mut graph = AdjacencyList::new();
         for i in 0..size {
@@ -500,10 +586,9 @@ impl SchizophreniaModule {
         }
         Self {
             neurons,
-            last_firing_times,
             plasticity,
             graph,
-            timestep: 0.0,
+            step: 0,
             nmda_reduction,
             gaba_increase,
         }
@@ -512,14 +597,15 @@ impl SchizophreniaModule {
 
 impl BrainRegion for SchizophreniaModule {
     fn iterate(&mut self, inputs: &[Vec<f32>]) -> Vec<f32> {
-        self.timestep += 0.1;
+        self.step += 1;
+        let fired: Vec<bool> = self.neurons.iter().map(|n| n.get_last_firing_time().is_some()).collect();
         let mut spikes = Vec::new();
         for (i, neuron) in self.neurons.iter_mut().enumerate() {
             let mut synaptic_input = 0.0;
-            for j in 0..self.neurons.len() {
+            for j in 0..fired.len() {
                 if i != j {
                     if let Some(&weight) = self.graph.get_edge(&(j, i)) {
-                        if self.last_firing_times[j] > 0.0 {
+                        if fired[j] {
                             synaptic_input += weight;
                         }
                     }
@@ -529,7 +615,7 @@ impl BrainRegion for SchizophreniaModule {
             let total_input = synaptic_input + external_input;
             let spiked = neuron.iterate_and_spike(total_input);
             if spiked {
-                self.last_firing_times[i] = self.timestep;
+                neuron.set_last_firing_time(Some(self.step));
                 spikes.push(1.0);
             } else {
                 spikes.push(0.0);
@@ -539,7 +625,7 @@ impl BrainRegion for SchizophreniaModule {
     }
 
     fn get_outputs(&self) -> Vec<f32> {
-        self.last_firing_times.clone()
+        self.neurons.iter().map(|n| last_spike_time(n, self.plasticity.dt)).collect()
     }
 
     fn update_plasticity(&mut self) {
@@ -547,14 +633,11 @@ impl BrainRegion for SchizophreniaModule {
         for i in 0..self.neurons.len() {
             for j in 0..self.neurons.len() {
                 if i != j {
+                    if !fired_at(&self.neurons[i], self.step) && !fired_at(&self.neurons[j], self.step) {
+                        continue;
+                    }
                     if let Some(weight) = self.graph.get_edge_mut(&(j, i)) {
-                        let dt = self.last_firing_times[i] - self.last_firing_times[j];
-                        let delta_w = if dt > 0.0 {
-                            self.plasticity.a_minus * (-dt).exp()
-                        } else {
-                            self.plasticity.a_plus * dt.exp()
-                        };
-                        *weight += delta_w;
+                        self.plasticity.update_weight(weight, &self.neurons[j], &self.neurons[i]);
                     }
                 }
             }
@@ -693,6 +776,130 @@ mod tests {
         assert!(true);
     }
 
+    #[test]
+    fn test_cortical_stdp_uses_spike_timestamps() {
+        let mut cortical = CorticalModule::new(2);
+        let stdp = cortical.plasticity;
+        // Pre (0) fires at step 10, post (1) at step 15: delta = 5 steps
+        cortical.neurons[0].set_last_firing_time(Some(10));
+        cortical.neurons[1].set_last_firing_time(Some(15));
+        cortical.step = 15;
+        cortical.update_plasticity();
+
+        // The pair is recorded in the traces, weights wait for a reward
+        let delta_t = 5.0 * stdp.dt;
+        let expected_ltp = stdp.a_plus * (-delta_t / stdp.tau_plus).exp();
+        let expected_ltd = -stdp.a_minus * (-delta_t / stdp.tau_minus).exp();
+        assert!((cortical.eligibility(0, 1) - expected_ltp).abs() < 1e-6);
+        assert!((cortical.eligibility(1, 0) - expected_ltd).abs() < 1e-6);
+        assert_eq!(*cortical.graph.get_edge(&(0, 1)).unwrap(), 0.5);
+        assert_eq!(*cortical.graph.get_edge(&(1, 0)).unwrap(), 0.5);
+
+        // No new spikes on the next step: the pair is not counted again, the traces only decay
+        let decay = (-stdp.dt / DEFAULT_ELIGIBILITY_TAU).exp();
+        cortical.step = 16;
+        cortical.deliver_reward(1.);
+        cortical.update_plasticity();
+        assert!((cortical.eligibility(0, 1) - expected_ltp * decay).abs() < 1e-6);
+        assert!((cortical.eligibility(1, 0) - expected_ltd * decay).abs() < 1e-6);
+        let w_01 = *cortical.graph.get_edge(&(0, 1)).unwrap();
+        let w_10 = *cortical.graph.get_edge(&(1, 0)).unwrap();
+        assert!((w_01 - (0.5 + expected_ltp * decay)).abs() < 1e-6);
+        assert!((w_10 - (0.5 + expected_ltd * decay)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_digital_twin_forwards_reward_to_regions() {
+        struct RewardProbe(std::rc::Rc<std::cell::Cell<f32>>);
+
+        impl BrainRegion for RewardProbe {
+            fn iterate(&mut self, _inputs: &[Vec<f32>]) -> Vec<f32> {
+                vec![]
+            }
+
+            fn get_outputs(&self) -> Vec<f32> {
+                vec![]
+            }
+
+            fn update_plasticity(&mut self) {}
+
+            fn deliver_reward(&mut self, amount: f32) {
+                self.0.set(self.0.get() + amount);
+            }
+        }
+
+        let received = std::rc::Rc::new(std::cell::Cell::new(0.));
+        let mut twin = DigitalTwin::new();
+        twin.add_region("probe".to_string(), Box::new(RewardProbe(received.clone())));
+        twin.add_region("cortical".to_string(), Box::new(CorticalModule::new(2)));
+        twin.deliver_reward(1.5);
+        assert_eq!(received.get(), 1.5);
+    }
+
+    #[test]
+    fn test_spike_raster_rows_follow_neurons() {
+        let mut lattice: Lattice<
+            IzhikevichNeuron<ApproximateNeurotransmitter, ApproximateReceptor>,
+            AdjacencyMatrix<(usize, usize), f32>,
+            SpikeHistory,
+            STDP,
+            ApproximateNeurotransmitter,
+        > = Lattice::default();
+        lattice.populate(&IzhikevichNeuron::default_impl(), 3, 1).unwrap();
+        lattice.update_grid_history = true;
+
+        // Neuron 1 is pushed over threshold on steps 1 and 3
+        let forced = [false, true, false, true, false];
+        for force in forced {
+            if force {
+                let neuron = lattice.get_mut(1, 0).unwrap();
+                neuron.current_voltage = neuron.v_th + 20.;
+            }
+            lattice.iterate().unwrap();
+        }
+
+        let raster = lattice.spike_raster();
+        assert_eq!(raster.len(), 3);
+        assert!(raster.iter().all(|row| row.len() == forced.len()));
+        assert_eq!(raster[1], forced);
+        assert!(raster[0].iter().chain(raster[2].iter()).all(|is_spiking| !is_spiking));
+    }
+
+    #[test]
+    fn test_delayed_reward_transfers_decayed_eligibility() {
+        let mut cortical = CorticalModule::new(2).with_eligibility_tau(50.);
+        let dt = cortical.plasticity.dt;
+        cortical.neurons[0].set_last_firing_time(Some(10));
+        cortical.neurons[1].set_last_firing_time(Some(15));
+        cortical.step = 15;
+        cortical.update_plasticity();
+
+        // Without dopamine the pair is only remembered in the trace
+        let trace = cortical.eligibility(0, 1);
+        assert!(trace > 0.);
+        assert_eq!(*cortical.graph.get_edge(&(0, 1)).unwrap(), 0.5);
+
+        // Reward arrives 20 steps later
+        let delay = 20;
+        for step in 16..=15 + delay {
+            cortical.step = step;
+            if step == 15 + delay {
+                cortical.deliver_reward(2.);
+            }
+            cortical.update_plasticity();
+        }
+
+        let decayed = trace * (-(delay as f32) * dt / 50.).exp();
+        assert!((cortical.eligibility(0, 1) - decayed).abs() < 1e-6);
+        let w_01 = *cortical.graph.get_edge(&(0, 1)).unwrap();
+        assert!((w_01 - (0.5 + 2. * decayed)).abs() < 1e-6);
+
+        // The reward is consumed by a single update
+        cortical.step += 1;
+        cortical.update_plasticity();
+        assert_eq!(*cortical.graph.get_edge(&(0, 1)).unwrap(), w_01);
+    }
+
     #[test]
     fn test_astrocyte_module() {
         let mut astrocyte = AstrocyteModule::new();
@@ -743,7 +950,7 @@ mod tests {
     #[test]
     fn test_classifier_module() {
         use crate::classifiers::STDPClassifier;
-        let classifier = STDPClassifier::new(3, 2);
+        let classifier = STDPClassifier::new(2, 2);
         let mut module = ClassifierModule::new(classifier);
         let inputs = vec![vec![1.0, 0.0]];
         // Train first
@@ -754,8 +961,8 @@ mod tests {
 
     #[test]
     fn test_regressor_module() {
-        use crate::classifiers::RSTDPRegressor;
-        let regressor = RSTDPRegressor::new(3);
+        use crate::classifiers::{RSTDPRegressor, RSTDPParams};
+        let regressor = RSTDPRegressor::new(2, RSTDPParams::default());
         let mut module = RegressorModule::new(regressor);
         let inputs = vec![vec![1.0, 0.0]];
         // Train first
@@ -275,6 +275,13 @@ pub enum SpikingNeuralNetworksError {
     ReceptorNeurotransmitterRelatedError(ReceptorNeurotransmitterError),
     /// Errors related to agent
     AgentRelatedError(AgentError),
+    /// Input vector length does not match the model's expected input dimension
+    DimensionMismatch {
+        /// Expected input length
+        expected: usize,
+        /// Provided input length
+        got: usize,
+    },
     #[cfg(feature = "gpu")]
     /// Errors related to the gpu
     GPURelatedError(GPUError),
@@ -290,6 +297,9 @@ impl Display for SpikingNeuralNetworksError {
             SpikingNeuralNetworksError::PatternRelatedError(err) => write!(f, "{}", err),
             SpikingNeuralNetworksError::ReceptorNeurotransmitterRelatedError(err) => write!(f, "{}", err),
             SpikingNeuralNetworksError::AgentRelatedError(err) => write!(f, "{}", err),
+            SpikingNeuralNetworksError::DimensionMismatch { expected, got } => write!(
+                f, "Input dimension mismatch, expected: {}, got: {}", expected, got
+            ),
             #[cfg(feature = "gpu")]
             SpikingNeuralNetworksError::GPURelatedError(err) => write!(f, "{}", err),
         }
@@ -521,6 +521,7 @@
 //! }
 //! ```
 
+pub mod analysis;
 pub mod classifiers;
 pub mod correlation;
 pub mod distribution;
@@ -533,3 +534,4 @@ pub mod graph;
 pub mod interactable;
 pub mod nb_parsing;
 pub mod neuron;
+pub mod stimulus;
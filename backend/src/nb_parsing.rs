@@ -18,6 +18,45 @@ pub struct NbNeuronModel {
     pub ion_channels: HashMap<String, NbIonChannel>,
 }
 
+impl NbNeuronModel {
+    /// Whether the neuron's own `on_iteration`, `spike_detection` or `on_spike`
+    /// code draws noise from the injected `rng`
+    pub fn uses_noise(&self) -> bool {
+        self.on_iteration.iter()
+            .chain(self.on_spike.iter())
+            .chain(std::iter::once(&self.spike_detection))
+            .any(|line| mentions_rng(line))
+    }
+}
+
+/// True when `line` refers to the `rng` identifier
+fn mentions_rng(line: &str) -> bool {
+    line.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|token| token == "rng")
+}
+
+/// Binds the neuron's RNG as `rng` at the top of a generated method body
+const RNG_BINDING: &str = "        #[allow(unused_imports)]
+        use rand::Rng;
+        #[allow(unused_variables)]
+        let rng = &mut self.rng;
+";
+
+/// Generates the `rng` default and a `with_seed` constructor for a noisy neuron
+fn generate_seeded_constructor(name: &str) -> String {
+    format!("
+impl {name}Neuron {{
+    /// Creates a neuron whose noise is drawn from an RNG seeded with `seed`,
+    /// instances built from the same seed produce identical traces
+    pub fn with_seed(seed: u64) -> Self {{
+        {name}Neuron {{
+            rng: <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(seed),
+            ..Default::default()
+        }}
+    }}
+}}
+")
+}
+
 /// Represents a parsed ion channel from a .nb file
 #[derive(Debug, Clone)]
 pub struct NbIonChannel {
@@ -127,8 +166,15 @@ pub fn parse_nb_content(content: &str) -> Result<NbNeuronModel, Box<dyn std::err
 }
 
 /// Generate Rust code for a neuron model from the parsed .nb model
+///
+/// Models whose code uses noise (see [`NbNeuronModel::uses_noise`]) get a
+/// `rng: rand::rngs::StdRng` field that is bound as `rng` inside `iterate_and_spike`,
+/// noise must be drawn from it (for example `v += rng.gen_range(-0.1..0.1)`) rather
+/// than from `thread_rng()` so runs are reproducible. `Default` seeds it with 0 and
+/// `with_seed` picks the seed.
 pub fn generate_neuron_code(model: &NbNeuronModel) -> String {
     let mut code = String::new();
+    let noisy = model.uses_noise();
 
     code.push_str(&format!("
 #[derive(Debug, Clone)]
@@ -143,6 +189,9 @@ pub struct {}Neuron {{
     code.push_str("    pub dt: f32,\n");
     code.push_str("    pub is_spiking: bool,\n");
     code.push_str("    pub last_firing_time: Option<usize>,\n");
+    if noisy {
+        code.push_str("    pub rng: rand::rngs::StdRng,\n");
+    }
 
     // Add ion channels
     for (name, channel) in &model.ion_channels {
@@ -165,6 +214,9 @@ impl Default for {}Neuron {{
     code.push_str("            dt: 0.1,\n");
     code.push_str("            is_spiking: false,\n");
     code.push_str("            last_firing_time: None,\n");
+    if noisy {
+        code.push_str("            rng: <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(0),\n");
+    }
 
     for (name, _) in &model.ion_channels {
         code.push_str(&format!("            {}: {}Channel::default(),\n", name.to_lowercase(), name));
@@ -172,12 +224,19 @@ impl Default for {}Neuron {{
 
     code.push_str("        }\n    }\n}\n\n");
 
+    if noisy {
+        code.push_str(&generate_seeded_constructor(&model.name));
+    }
+
     // Add IterateAndSpike impl
     code.push_str(&format!("
 impl IterateAndSpike for {}Neuron {{
     fn iterate_and_spike(&mut self, input_current: f32) -> bool {{
-        // Generated from on_iteration
 ", model.name));
+    if noisy {
+        code.push_str(RNG_BINDING);
+    }
+    code.push_str("        // Generated from on_iteration\n");
 
     for iter_code in &model.on_iteration {
         code.push_str(&format!("        {}\n", iter_code));
@@ -201,6 +260,146 @@ impl IterateAndSpike for {}Neuron {{
     code
 }
 
+/// Generate Rust code implementing the shnn-core `Neuron` trait from the parsed .nb model
+///
+/// The `on_iteration` body becomes `integrate` (with `i` bound to the input current and
+/// `dt` to the step in milliseconds), while `spike_detection`/`on_spike` drive `update`
+/// and `reset`. The membrane potential is the `v` variable and the threshold is `v_th`
+/// when declared. The result can be pooled in a shnn-core `NeuronPool`. Noisy models
+/// get the same injected `rng` as [`generate_neuron_code`], bound in `integrate`,
+/// `update` and `reset`.
+pub fn generate_shnn_neuron_code(model: &NbNeuronModel) -> String {
+    let mut code = String::new();
+    let noisy = model.uses_noise();
+
+    // Sort for deterministic output
+    let mut variables: Vec<(&String, &f32)> = model.variables.iter().collect();
+    variables.sort_by(|a, b| a.0.cmp(b.0));
+    let mut channels: Vec<&String> = model.ion_channels.keys().collect();
+    channels.sort();
+    let has_v = model.variables.contains_key("v");
+
+    code.push_str(&format!("
+#[derive(Debug, Clone)]
+pub struct {}Neuron {{
+    pub id: shnn_core::neuron::NeuronId,
+", model.name));
+
+    if !has_v {
+        code.push_str("    pub v: f64,\n");
+    }
+    for (var, default) in &variables {
+        code.push_str(&format!("    pub {}: f64, // {}\n", var, default));
+    }
+
+    for name in &channels {
+        code.push_str(&format!("    pub {}: {}Channel,\n", name.to_lowercase(), name));
+    }
+    if noisy {
+        code.push_str("    pub rng: rand::rngs::StdRng,\n");
+    }
+
+    code.push_str("}\n\n");
+
+    // Add Default impl
+    code.push_str(&format!("
+impl Default for {}Neuron {{
+    fn default() -> Self {{
+        {}Neuron {{
+            id: shnn_core::neuron::NeuronId::new(0),
+", model.name, model.name));
+
+    if !has_v {
+        code.push_str("            v: 0.0,\n");
+    }
+    for (var, default) in &variables {
+        code.push_str(&format!("            {}: {:?},\n", var, **default as f64));
+    }
+
+    for name in &channels {
+        code.push_str(&format!("            {}: {}Channel::default(),\n", name.to_lowercase(), name));
+    }
+    if noisy {
+        code.push_str("            rng: <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(0),\n");
+    }
+
+    code.push_str("        }\n    }\n}\n\n");
+
+    if noisy {
+        code.push_str(&generate_seeded_constructor(&model.name));
+    }
+    let rng_binding = if noisy { RNG_BINDING } else { "" };
+
+    // Add Neuron impl
+    code.push_str(&format!("
+impl shnn_core::neuron::Neuron for {}Neuron {{
+    #[allow(unused_variables)]
+    fn integrate(&mut self, input_current: f64, dt: shnn_core::time::TimeStep) {{
+{}        let i = input_current;
+        let dt = dt as f64 / 1000.0;
+        // Generated from on_iteration
+", model.name, rng_binding));
+
+    for iter_code in &model.on_iteration {
+        code.push_str(&format!("        {}\n", iter_code));
+    }
+
+    code.push_str("    }
+
+    fn update(&mut self, _dt: shnn_core::time::TimeStep) -> Option<shnn_core::spike::Spike> {
+");
+    code.push_str(rng_binding);
+    code.push_str("        // Generated from spike_detection
+        if ");
+    code.push_str(&model.spike_detection);
+    code.push_str(" {
+            self.reset();
+            shnn_core::spike::Spike::new(self.id, shnn_core::time::Time::from_nanos(0), 1.0).ok()
+        } else {
+            None
+        }
+    }
+
+    fn membrane_potential(&self) -> f64 {
+        self.v
+    }
+
+    fn set_membrane_potential(&mut self, voltage: f64) {
+        self.v = voltage;
+    }
+
+    fn threshold(&self) -> f64 {
+");
+    if model.variables.contains_key("v_th") {
+        code.push_str("        self.v_th\n");
+    } else {
+        code.push_str("        f64::INFINITY\n");
+    }
+    code.push_str("    }
+
+    fn reset(&mut self) {
+");
+    code.push_str(rng_binding);
+    code.push_str("        // Generated from on_spike
+");
+    for spike_code in &model.on_spike {
+        code.push_str(&format!("        {}\n", spike_code));
+    }
+    code.push_str("    }
+
+    fn id(&self) -> shnn_core::neuron::NeuronId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: shnn_core::neuron::NeuronId) {
+        self.id = id;
+    }
+}
+");
+
+    code
+}
+
 /// Macro to load and generate neuron model from .nb file at compile time
 #[macro_export]
 macro_rules! nb_neuron {
@@ -240,4 +439,99 @@ mod tests {
         assert_eq!(model.spike_detection, "v >= v_th");
         assert_eq!(model.on_iteration, vec!["dv/dt = (v - e) + i"]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_generate_shnn_neuron_code() {
+        let content = r#"
+[neuron]
+    type: BasicIntegrateAndFire
+    vars: e = 0, v_reset = -75, v_th = -55
+    on_spike:
+        v = v_reset
+    spike_detection: v >= v_th
+    on_iteration:
+        dv/dt = (v - e) + i
+[end]
+"#;
+
+        let model = parse_nb_content(content).unwrap();
+        let code = generate_shnn_neuron_code(&model);
+        assert!(code.contains("impl shnn_core::neuron::Neuron for BasicIntegrateAndFireNeuron"));
+        for method in ["fn integrate(", "fn update(", "fn membrane_potential(", "fn threshold("] {
+            assert!(code.contains(method), "missing {}", method);
+        }
+        assert!(code.contains("pub v: f64,"));
+        assert!(code.contains("self.v_th"));
+    }
+
+    /// Noisy integrate-and-fire model matching `tests/fixtures/noisy_integrate_and_fire.rs`
+    fn noisy_model() -> NbNeuronModel {
+        NbNeuronModel {
+            name: "NoisyIntegrateAndFire".to_string(),
+            variables: HashMap::from([("v".to_string(), 0.5), ("v_th".to_string(), 1.5)]),
+            on_spike: vec!["self.v = 0.5;".to_string()],
+            spike_detection: "self.v >= self.v_th".to_string(),
+            on_iteration: vec!["self.v += input_current + rng.gen_range(-0.5..0.5);".to_string()],
+            ion_channels: HashMap::new(),
+        }
+    }
+
+    /// The generated code for `noisy_model`, compiled against a minimal
+    /// `IterateAndSpike` so the tests can step real instances of it
+    mod generated_noisy {
+        pub trait IterateAndSpike {
+            fn iterate_and_spike(&mut self, input_current: f32) -> bool;
+        }
+
+        include!("../tests/fixtures/noisy_integrate_and_fire.rs");
+    }
+
+    #[test]
+    fn test_noisy_model_gets_injected_seedable_rng() {
+        let model = noisy_model();
+        assert!(model.uses_noise());
+
+        for code in [generate_neuron_code(&model), generate_shnn_neuron_code(&model)] {
+            assert!(code.contains("pub rng: rand::rngs::StdRng,"));
+            assert!(code.contains("pub fn with_seed(seed: u64) -> Self"));
+            assert!(code.contains("let rng = &mut self.rng;"));
+            assert!(!code.contains("thread_rng"));
+        }
+
+        // The compiled fixture is what the generator emits; fields follow the
+        // HashMap's iteration order, so lines are compared as sorted sets
+        let sorted_lines = |code: &str| {
+            let mut lines: Vec<String> = code.lines()
+                .filter(|l| !l.starts_with("//"))
+                .map(str::to_string)
+                .collect();
+            lines.sort();
+            lines
+        };
+        let fixture = include_str!("../tests/fixtures/noisy_integrate_and_fire.rs");
+        assert_eq!(sorted_lines(&generate_neuron_code(&model)), sorted_lines(fixture));
+
+        let mut quiet = model.clone();
+        quiet.on_iteration = vec!["self.v += input_current;".to_string()];
+        assert!(!quiet.uses_noise());
+        assert!(!generate_neuron_code(&quiet).contains("rng"));
+    }
+
+    #[test]
+    fn test_seeded_noisy_neuron_traces_are_reproducible() {
+        use generated_noisy::{IterateAndSpike, NoisyIntegrateAndFireNeuron};
+
+        let trace = |seed: u64| {
+            let mut neuron = NoisyIntegrateAndFireNeuron::with_seed(seed);
+            (0..200)
+                .map(|_| {
+                    neuron.iterate_and_spike(0.05);
+                    neuron.v
+                })
+                .collect::<Vec<f32>>()
+        };
+
+        assert_eq!(trace(7), trace(7));
+        assert_ne!(trace(7), trace(8));
+    }
+}
@@ -110,8 +110,9 @@ fn diff<T: Sub<Output = T> + Copy>(x: &[T]) -> Vec<T> {
         .collect()
 }
 
-/// Returns indices of where voltages have peaked given a certain tolerance
-pub fn find_peaks(voltages: &[f32], tolerance: f32) -> Vec<usize> {
+/// Returns indices of where voltages have peaked given a certain tolerance,
+/// peaks closer than `min_distance` samples are merged (see [`merge_close_peaks`])
+pub fn find_peaks(voltages: &[f32], tolerance: f32, min_distance: usize) -> Vec<usize> {
     let first_diff: Vec<f32> = diff(voltages);
     let second_diff: Vec<f32> = diff(&first_diff);
 
@@ -138,16 +139,40 @@ pub fn find_peaks(voltages: &[f32], tolerance: f32) -> Vec<usize> {
             index += 1;
         }
 
-        if peak_spans.len() - 1 != index {
+        if peak_spans.len() != index + 1 {
             peak_spans.push(Vec::new());
         }
 
         peak_spans[index].push(*i);
     }
 
-    peak_spans.iter()
+    let peaks = peak_spans.iter()
         .map(|i| i[i.len() / 2])
-        .collect::<Vec<usize>>()
+        .collect::<Vec<usize>>();
+
+    merge_close_peaks(voltages, &peaks, min_distance)
+}
+
+/// Merges peaks that are closer than `min_distance` samples, keeping the highest
+/// voltage of each cluster (earliest peak wins ties), which acts as a refractory
+/// period constraint when counting spikes, a `min_distance` of `0` or `1` keeps all peaks;
+/// `peaks` are expected in ascending order (as returned by [`find_peaks`]), otherwise
+/// each peak is only compared with the last one kept
+pub fn merge_close_peaks(voltages: &[f32], peaks: &[usize], min_distance: usize) -> Vec<usize> {
+    let mut merged: Vec<usize> = Vec::with_capacity(peaks.len());
+
+    for &peak in peaks {
+        match merged.last_mut() {
+            Some(last) if peak.abs_diff(*last) < min_distance => {
+                if voltages[peak] > voltages[*last] {
+                    *last = peak;
+                }
+            },
+            _ => merged.push(peak),
+        }
+    }
+
+    merged
 }
 
 // https://github.com/swharden/pyHH/blob/master/src/pyhh/models.py
@@ -244,9 +269,10 @@ impl<T: NeurotransmitterKinetics, R: ReceptorKinetics> IterateAndSpike for Hodgk
 /// Takes in a static current as an input and iterates the given
 /// neuron for a given duration, set `gaussian` to true to add 
 /// normally distributed noise to the input as it iterates,
-/// returns various state variables over time including voltages
-/// and gating states, output hashmap has keys `""`,
-/// `"m"`, `"n"`, and `"h"`
+/// returns various state variables over time including voltages,
+/// gating states, and the (possibly noisy) input current applied at
+/// each step, output hashmap has keys `"current_voltage"`,
+/// `"m"`, `"n"`, `"h"`, and `"input_current"`
 pub fn run_static_input_hodgkin_huxley<T: NeurotransmitterKinetics, R: ReceptorKinetics>(
     hodgkin_huxley_neuron: &mut HodgkinHuxleyNeuron<T, R>,
     input_current: f32,
@@ -258,17 +284,20 @@ pub fn run_static_input_hodgkin_huxley<T: NeurotransmitterKinetics, R: ReceptorK
     state_output.insert("m".to_string(), vec![]);
     state_output.insert("n".to_string(), vec![]);
     state_output.insert("h".to_string(), vec![]);
+    state_output.insert("input_current".to_string(), vec![]);
 
     for _ in 0..iterations {
-        let _is_spiking = match gaussian {
-            Some(ref params) => hodgkin_huxley_neuron.iterate_and_spike(params.get_random_number() * input_current),
-            None => hodgkin_huxley_neuron.iterate_and_spike(input_current),
+        let step_input = match gaussian {
+            Some(ref params) => params.get_random_number() * input_current,
+            None => input_current,
         };
+        let _is_spiking = hodgkin_huxley_neuron.iterate_and_spike(step_input);
 
         if let Some(val) = state_output.get_mut("current_voltage") { val.push(hodgkin_huxley_neuron.current_voltage) }
         if let Some(val) = state_output.get_mut("m") { val.push(hodgkin_huxley_neuron.na_channel.m.state) }
         if let Some(val) = state_output.get_mut("n") { val.push(hodgkin_huxley_neuron.na_channel.h.state) }
         if let Some(val) = state_output.get_mut("h") { val.push(hodgkin_huxley_neuron.k_channel.n.state) }
+        if let Some(val) = state_output.get_mut("input_current") { val.push(step_input) }
     }
 
     state_output
@@ -0,0 +1,60 @@
+//! Spike train stimulus generators
+//!
+//! Produces per-step spike indicators that can be used to drive classifiers,
+//! digital twin regions or spike train lattices without hand-rolling input currents.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+
+/// Number of whole `dt_ms` steps in `duration_ms` (0 if either is non-positive)
+fn num_steps(duration_ms: f32, dt_ms: f32) -> usize {
+    if dt_ms <= 0. || duration_ms <= 0. {
+        return 0;
+    }
+
+    (duration_ms / dt_ms) as usize
+}
+
+/// Generates a Poisson spike train firing at `rate_hz` on average, each entry is
+/// whether a spike occurred during that step of `dt_ms` milliseconds,
+/// the same `seed` always produces the same train
+pub fn poisson_spike_train(rate_hz: f32, duration_ms: f32, dt_ms: f32, seed: u64) -> Vec<bool> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    // Same per-step firing chance as `PoissonNeuron::from_firing_rate`
+    let chance = (rate_hz * dt_ms / 1000.).clamp(0., 1.) as f64;
+
+    (0..num_steps(duration_ms, dt_ms))
+        .map(|_| rng.gen_bool(chance))
+        .collect()
+}
+
+/// Generates a regular spike train firing every `1000 / rate_hz` milliseconds,
+/// each entry is whether a spike occurred during that step of `dt_ms` milliseconds,
+/// the first spike occurs once a full period has elapsed
+pub fn regular_spike_train(rate_hz: f32, duration_ms: f32, dt_ms: f32) -> Vec<bool> {
+    let increment = (rate_hz.max(0.) as f64 * dt_ms as f64 / 1000.).min(1.);
+
+    // spikes whenever the number of elapsed periods crosses an integer
+    (0..num_steps(duration_ms, dt_ms))
+        .map(|step| ((step + 1) as f64 * increment).floor() > (step as f64 * increment).floor())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_high_rate_poisson_train_spikes_more() {
+        let count = |train: Vec<bool>| train.iter().filter(|&&spike| spike).count();
+
+        let low = poisson_spike_train(5., 10_000., 0.1, 1);
+        let high = poisson_spike_train(100., 10_000., 0.1, 1);
+        assert_eq!(low.len(), high.len());
+        assert!(count(high) > count(low));
+
+        assert_eq!(poisson_spike_train(50., 1_000., 0.1, 3), poisson_spike_train(50., 1_000., 0.1, 3));
+        assert_eq!(count(regular_spike_train(10., 1_000., 1.)), 10);
+    }
+}
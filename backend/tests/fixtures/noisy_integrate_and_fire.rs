@@ -0,0 +1,57 @@
+// Output of `nb_parsing::generate_neuron_code` for the noisy model in the
+// nb_parsing tests, compiled there to step real instances of the neuron
+
+#[derive(Debug, Clone)]
+pub struct NoisyIntegrateAndFireNeuron {
+    pub v: f32, // 0.5
+    pub v_th: f32, // 1.5
+    pub dt: f32,
+    pub is_spiking: bool,
+    pub last_firing_time: Option<usize>,
+    pub rng: rand::rngs::StdRng,
+}
+
+
+impl Default for NoisyIntegrateAndFireNeuron {
+    fn default() -> Self {
+        NoisyIntegrateAndFireNeuron {
+            v: 0.5,
+            v_th: 1.5,
+            dt: 0.1,
+            is_spiking: false,
+            last_firing_time: None,
+            rng: <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(0),
+        }
+    }
+}
+
+
+impl NoisyIntegrateAndFireNeuron {
+    /// Creates a neuron whose noise is drawn from an RNG seeded with `seed`,
+    /// instances built from the same seed produce identical traces
+    pub fn with_seed(seed: u64) -> Self {
+        NoisyIntegrateAndFireNeuron {
+            rng: <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(seed),
+            ..Default::default()
+        }
+    }
+}
+
+impl IterateAndSpike for NoisyIntegrateAndFireNeuron {
+    fn iterate_and_spike(&mut self, input_current: f32) -> bool {
+        #[allow(unused_imports)]
+        use rand::Rng;
+        #[allow(unused_variables)]
+        let rng = &mut self.rng;
+        // Generated from on_iteration
+        self.v += input_current + rng.gen_range(-0.5..0.5);
+
+        // Generated from spike_detection
+        self.is_spiking = self.v >= self.v_th;
+
+        if self.is_spiking {
+            self.v = 0.5;
+        }
+        self.is_spiking
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use spiking_neural_networks::neuron::hodgkin_huxley::{
+        find_peaks, merge_close_peaks, run_static_input_hodgkin_huxley, HodgkinHuxleyNeuron,
+    };
+    use spiking_neural_networks::neuron::iterate_and_spike::GaussianParameters;
+
+    #[test]
+    fn test_find_peaks_merges_peaks_within_min_distance() {
+        // Two distinct peaks 2 samples apart, the later one higher
+        let voltages = [0., 0., 0., 0., 0., 9., 8., 20., 0., 0., 0.];
+
+        let peaks = find_peaks(&voltages, 1.5, 0);
+        assert_eq!(peaks, vec![5, 7]);
+
+        let merged = find_peaks(&voltages, 1.5, 3);
+        assert_eq!(merged, vec![7]);
+    }
+
+    #[test]
+    fn test_merge_close_peaks_keeps_highest() {
+        let voltages = [0., 20., 0., 30., 0., 0., 0., 0., 0., 25., 0.];
+
+        assert_eq!(merge_close_peaks(&voltages, &[1, 3, 9], 5), vec![3, 9]);
+        assert_eq!(merge_close_peaks(&voltages, &[1, 3, 9], 1), vec![1, 3, 9]);
+        assert_eq!(merge_close_peaks(&voltages, &[1, 3, 9], 20), vec![3]);
+
+        // Unsorted input does not underflow
+        assert_eq!(merge_close_peaks(&voltages, &[9, 3, 1], 5), vec![9, 3]);
+    }
+
+    #[test]
+    fn test_static_input_records_input_current() {
+        let iterations = 100;
+
+        let mut neuron = HodgkinHuxleyNeuron::default_impl();
+        let plain = run_static_input_hodgkin_huxley(&mut neuron, 50., iterations, None);
+        let plain_inputs = &plain["input_current"];
+        assert_eq!(plain_inputs.len(), iterations);
+        assert!(plain_inputs.iter().all(|i| *i == 50.));
+
+        let mut neuron = HodgkinHuxleyNeuron::default_impl();
+        let gaussian = GaussianParameters { mean: 1.0, std: 0.5, max: 2.0, min: 0.0 };
+        let noisy = run_static_input_hodgkin_huxley(&mut neuron, 50., iterations, Some(gaussian));
+        let noisy_inputs = &noisy["input_current"];
+        assert_eq!(noisy_inputs.len(), iterations);
+        assert!(noisy_inputs.iter().any(|i| *i != noisy_inputs[0]));
+        assert!(noisy_inputs.iter().all(|i| (0. ..=100.).contains(i)));
+    }
+}